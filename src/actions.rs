@@ -7,9 +7,14 @@ use std::convert::From;
 
 use crate::seed_system::Distribution;
 use crate::simulation::*;
-use crate::simulation_state::cards::PlayCardContext;
-use crate::simulation_state::monsters::DoIntentContext;
+use crate::simulation_state::cards::{
+  combat_card_pool, random_potion_distribution, PlayCardContext,
+};
+use crate::simulation_state::monsters::{hitpoints_range, DoIntentContext};
+use crate::simulation_state::orbs::{orb_damage_info, orb_evoke_effect, orb_passive_effect};
 use crate::simulation_state::powers::PowerBehavior;
+use crate::simulation_state::relics::RelicBehavior;
+use crate::simulation_state::stances;
 use crate::simulation_state::*;
 
 macro_rules! actions {
@@ -59,47 +64,119 @@ actions! {
   [UseCardAction {pub card: SingleCard, pub target: usize, pub exhaust: bool, pub rebound: bool, pub energy_on_use: i32, pub purge_on_use: bool}],
   [FinishPlayingCard(UseCardAction);],
   [EndTurn;],
+  [DiscardAtEndOfTurn;],
+  [SkipMonsterTurnAction;],
   [StartMonsterTurn (pub usize);],
   [DoMonsterIntent (pub usize);],
   [FinishMonsterTurn (pub usize);],
   [ChooseMonsterIntent (pub usize);],
   [EndMonstersTurns;],
+  [StartCombat;],
+  [StartFirstTurn;],
 
   // used by many effects
   [DamageAction {pub target: CreatureIndex, pub info: DamageInfoAllPowers}],
-  [DamageAllEnemiesAction {pub info: DamageInfoOwnerPowers}],
+  [DamageAllEnemiesAction {pub infos: ArrayVec<DamageInfoAllPowers, MAX_MONSTERS>}],
   [DamageAllEnemiesActionIgnoringPowers {pub info: DamageInfoNoPowers}],
-  [AttackDamageRandomEnemyAction {pub info: DamageInfoOwnerPowers}],
+  [AttackDamageRandomEnemyAction {pub info: DamageInfoNoPowers}],
+  [MonsterAttackAction {pub info: DamageInfoNoPowers}],
   [DrawCardRandom;],
   [DrawCards (pub i32);],
   [ApplyPowerAction {pub source: CreatureIndex, pub target: CreatureIndex, pub power_id: PowerId, pub amount: i32}],
   [ReducePowerAction {pub target: CreatureIndex, pub power_id: PowerId, pub amount: i32}],
   [RemoveSpecificPowerAction {pub target: CreatureIndex, pub power_id: PowerId}],
   [DiscardNewCard (pub SingleCard);],
+  [AddNewCardToHand (pub SingleCard);],
+  [ShuffleNewCardIntoDrawPile (pub SingleCard);],
   [GainBlockAction {pub creature_index: CreatureIndex, pub amount: i32}],
   [HealAction {pub creature_index: CreatureIndex, pub amount: i32}],
   [GainEnergyAction (pub i32);],
+  [LoseEnergyAction (pub i32);],
+  [ChooseCardAction {pub choice_type: CardChoiceType}],
+  [SelectCard {pub card: SingleCard, pub choice_type: CardChoiceType}],
+  [SkipCardChoice {pub choice_type: CardChoiceType}],
+  [ChooseRandomCardAction {pub choice_type: CardChoiceType}],
+  [ApplyPowerRandomEnemyAction {pub power_id: PowerId, pub amount: i32}],
+  [RemoveAllBlockAction {pub target: CreatureIndex}],
+
+  // stances and scrying
+  [ChangeStanceAction (pub Stance);],
+  [ScryAction (pub i32);],
+  [RevealDrawPileTopAction (pub i32);],
+  [FinishScryAction;],
+
+  // orbs
+  [ChannelOrbAction (pub Orb);],
+  [ChannelRandomOrbAction;],
+  [EvokeOrbAction;],
+  [EvokeWithoutRemovingOrbAction;],
+  [RemoveAllOrbsAction;],
+  [TriggerEndOfTurnOrbsAction;],
+  [LightningOrbDamageAction {pub amount: i32}],
+  [DarkOrbEvokeAction {pub amount: i32}],
+  [IncreaseMaxOrbAction (pub i32);],
+  [DecreaseMaxOrbAction (pub i32);],
+
+  // potions
+  [DiscoveryAction {pub card_type: CardType, pub copies: i32}],
+  [ChooseOneAction {pub options: ArrayVec<CardId, 3>, pub upgraded: bool}],
+  [EscapeCombatAction;],
+  [ObtainRandomPotionAction;],
+  [IncreaseMaxHpAction (pub i32);],
 
   // generally card effects
-  [ArmamentsAction {pub upgraded: bool}],
-  [VampireDamageAllEnemiesAction {pub info: DamageInfoOwnerPowers}],
+  [UpgradeCardsAction {pub filter: UpgradeCardsFilter, pub piles: ArrayVec<CardPile, 4>}],
+  [SecondWindAction {pub block_per_card: i32}],
+  [PlayTopCardAction;],
+  [AutoplayCardAction {pub card: SingleCard, pub exhaust: bool, pub purge_on_use: bool}],
+  [VampireDamageAllEnemiesAction {pub infos: ArrayVec<DamageInfoAllPowers, MAX_MONSTERS>}],
+  [VampireHealAction {pub hitpoints_before: ArrayVec<i32, MAX_MONSTERS>}],
   [FiendFireAction {pub target: CreatureIndex, pub info: DamageInfoAllPowers}],
+  [CalculatedGambleAction;],
+  [StormOfSteelAction {pub upgraded: bool}],
+  [UnloadAction;],
+  [BulletTimeAction;],
+  [EscapePlanAction {pub block: i32}],
+  [EscapePlanBlockAction {pub block: i32, pub drawn_index: usize}],
+  [ExpertiseAction (pub usize);],
+  [CatalystAction {pub target: CreatureIndex, pub multiplier: i32}],
+  [RedoAction;],
+  [DarkImpulseAction;],
+  [AllForOneAction;],
+  [ShuffleHandAndDiscardIntoDrawPile;],
+  [TriggerMarksAction;],
+  [ExhaustSpecificCardAction (pub SingleCard);],
+  [WallopDamageAction {pub target: CreatureIndex, pub info: DamageInfoAllPowers}],
+  [WallopBlockAction {pub target: CreatureIndex, pub hitpoints_before: i32}],
+  [JudgementAction {pub target: CreatureIndex, pub threshold: i32}],
 
   // generally monster effects
   [InitializeMonsterInnateDamageAmount{pub monster_index: usize, pub range: (i32, i32)}],
+  [InitializeMonsterHitpoints {pub monster_index: usize, pub range: (i32, i32)}],
+  [InitializeMonsterPowerAmount {pub monster_index: usize, pub power_id: PowerId, pub range: (i32, i32)}],
   [GainBlockRandomMonsterAction {pub source: usize, pub amount: i32}],
+  [SpawnMonsterAction {pub monster_id: MonsterId, pub hitpoints: (i32, i32), pub minion: bool}],
   [SplitAction (pub usize, pub [MonsterId; 2]);],
   [EscapeAction (pub usize);],
+  [SuicideAction (pub usize);],
   [SummonGremlinAction;],
+  [StasisAction (pub usize);],
+  [MonsterVampireAttackAction {pub info: DamageInfoNoPowers}],
+  [MonsterVampireHealAction {pub monster_index: usize, pub hitpoints_before: i32}],
+  [RemoveDebuffsAction (pub CreatureIndex);],
+  [RandomizeCardCostAction (pub SingleCard);],
+  [SpireShieldDebuffAction (pub usize);],
+  [ReviveMonsterAction {pub monster_index: usize, pub hitpoints: i32}],
 }
 
 impl Action for PlayCard {
   fn execute(&self, runner: &mut impl Runner) {
     let state = runner.state_mut();
     let card_index = state.hand.iter().position(|c| *c == self.card).unwrap();
-    let card = state.hand.remove(card_index);
+    let mut card = state.hand.remove(card_index);
     let card_id = card.card_info.id;
     let cost = card.cost_in_practice(state);
+    card.free_to_play_once = false;
     let use_card_action = UseCardAction::new(card, self.target, state);
     state.player.energy -= cost;
     runner.action_now(&use_card_action);
@@ -107,7 +184,8 @@ impl Action for PlayCard {
 }
 impl UseCardAction {
   pub fn new(card: SingleCard, target: usize, state: &CombatState) -> UseCardAction {
-    let exhaust = card.card_info.exhausts;
+    let exhaust =
+      card.card_info.exhausts && !(card.upgrades > 0 && card.card_info.upgrade_removes_exhaust);
     UseCardAction {
       card,
       target,
@@ -123,15 +201,23 @@ impl Action for UseCardAction {
   fn execute(&self, runner: &mut impl Runner) {
     let card = self.card.clone();
     let mut this = self.clone();
+    runner.state_mut().cards_played_this_turn += 1;
     power_hook!(runner, AllCreatures, on_use_card(&card, &mut this));
+    relic_hook!(runner, on_use_card(&card, &mut this));
     let state = runner.state_mut();
     let card_id = card.card_info.id;
-    state.card_in_play = Some(card);
+    if card.card_info.card_type == CardType::Attack {
+      state.attacks_played_this_turn += 1;
+    }
+    state.card_in_play = Some(card.clone());
 
     card_id.behavior(&mut PlayCardContext {
       runner,
       target: this.target,
+      energy_on_use: this.energy_on_use,
     });
+    runner.state_mut().last_card_type_played = Some(card.card_info.card_type);
+    power_hook!(runner, CreatureIndex::Player, on_after_card_played(&card));
 
     runner.action_now(&FinishPlayingCard(this));
   }
@@ -149,18 +235,22 @@ impl Action for UsePotion {
     self.potion_info.id.behavior(&mut PlayCardContext {
       runner,
       target: self.target,
+      energy_on_use: 0,
     });
   }
 }
 
 impl Action for FinishPlayingCard {
   fn execute(&self, runner: &mut impl Runner) {
+    let card = runner.state_mut().card_in_play.take().unwrap();
+    power_hook!(runner, AllCreatures, on_after_use_card(&card));
     let state = runner.state_mut();
-    let card = state.card_in_play.take().unwrap();
     if self.0.purge_on_use || card.card_info.card_type == CardType::Power {
       // card disappears
     } else if self.0.rebound {
-      todo!() // on top of deck
+      state.put_card_on_top_of_draw_pile(card);
+    } else if card.card_info.shuffles_back_into_draw_pile {
+      runner.action_now(&ShuffleNewCardIntoDrawPile(card));
     } else if self.0.exhaust {
       state.exhaust_pile.push(card);
     } else {
@@ -171,17 +261,14 @@ impl Action for FinishPlayingCard {
 
 impl Action for EndTurn {
   fn execute(&self, runner: &mut impl Runner) {
+    runner.action_bottom(TriggerEndOfTurnOrbsAction);
+    relic_hook!(runner, at_end_of_turn());
     power_hook!(runner, CreatureIndex::Player, at_end_of_turn());
 
     let state = runner.state_mut();
     state.turn_has_ended = true;
     let mut actions: ArrayVec<DamageAction, 10> = ArrayVec::new();
-    let CombatState {
-      discard_pile,
-      exhaust_pile,
-      player,
-      ..
-    } = state;
+    let mut ethereal: ArrayVec<SingleCard, 10> = ArrayVec::new();
     state.hand.retain(|card| {
       if card.card_info.id == CardId::Burn {
         actions.push(DamageAction {
@@ -193,22 +280,60 @@ impl Action for EndTurn {
           )
           .ignore_powers(),
         });
-      } else if card.card_info.ethereal {
-        exhaust_pile.push(card.clone());
-      } else {
-        if player.creature.has_power(PowerId::RunicPyramid) {
-          return true;
-        } else {
-          discard_pile.push(card.clone());
-        }
+      } else if card.ethereal() {
+        ethereal.push(card.clone());
+        return false;
       }
-      false
+      true
     });
+    for card in ethereal {
+      exhaust_card(runner, card);
+    }
     for action in actions {
       runner.action_bottom(action);
     }
 
-    runner.action_now(&StartMonsterTurn(0));
+    // after anything the end-of-turn effects queued, such as Well-Laid Plans choosing what to retain
+    runner.action_bottom(DiscardAtEndOfTurn);
+  }
+}
+
+impl Action for DiscardAtEndOfTurn {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    let retain_all = state.has_relic(RelicId::RunicPyramid);
+    let CombatState {
+      hand, discard_pile, ..
+    } = state;
+    for card in hand.iter_mut().chain(discard_pile.iter_mut()) {
+      card.free_this_turn = false;
+    }
+    hand.retain(|card| {
+      if card.retain || card.retains() {
+        card.retain = false;
+        card.card_info.id.on_retained(card);
+        true
+      } else if retain_all {
+        true
+      } else {
+        discard_pile.push(card.clone());
+        false
+      }
+    });
+
+    if state.skip_monster_turn {
+      // the monsters' powers don't tick down either, since the round doesn't end
+      state.skip_monster_turn = false;
+      start_next_player_turn(runner);
+    } else {
+      runner.action_now(&StartMonsterTurn(0));
+    }
+  }
+}
+
+impl Action for SkipMonsterTurnAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    runner.state_mut().skip_monster_turn = true;
   }
 }
 
@@ -219,18 +344,19 @@ pub fn apply_end_of_turn_powers(runner: &mut impl Runner) {
 pub fn start_creature_turn(runner: &mut impl Runner, creature_index: CreatureIndex) {
   power_hook!(runner, creature_index, at_start_of_turn());
   let creature = runner.state_mut().get_creature_mut(creature_index);
-  if !creature.has_power(PowerId::Barricade) {
+  if !creature.has_power(PowerId::Barricade) && !creature.has_power(PowerId::Blur) {
     creature.block = 0;
   }
-  // TODO: make this actually post-draw
-  power_hook!(runner, creature_index, at_start_of_turn_post_draw());
 }
 
 impl Action for StartMonsterTurn {
   fn execute(&self, runner: &mut impl Runner) {
     if let Some(monster) = runner.state_mut().monsters.get_mut(self.0) {
       if !monster.gone {
-        start_creature_turn(runner, CreatureIndex::Monster(self.0));
+        let monster_index = CreatureIndex::Monster(self.0);
+        start_creature_turn(runner, monster_index);
+        // monsters don't draw, so there's nothing to wait for
+        power_hook!(runner, monster_index, at_start_of_turn_post_draw());
       }
       if !runner.state().combat_over() {
         runner.action_now(&StartMonsterTurn(self.0 + 1));
@@ -245,11 +371,14 @@ impl Action for DoMonsterIntent {
   fn execute(&self, runner: &mut impl Runner) {
     if let Some(monster) = runner.state().monsters.get(self.0) {
       let monster_id = monster.monster_id;
-      if !monster.gone {
+      // monsters spawned during this turn don't have an intent until the end of it
+      if (!monster.gone || monster.half_dead) && !monster.move_history.is_empty() {
         monster_id.intent_effects(&mut DoIntentContext::new(runner, self.0));
+        power_hook!(runner, CreatureIndex::Monster(self.0), during_turn());
       }
+      // each monster's turn resolves completely before the next one starts
       if !runner.state().combat_over() {
-        runner.action_now(&DoMonsterIntent(self.0 + 1));
+        runner.action_bottom(DoMonsterIntent(self.0 + 1));
       }
     } else {
       runner.action_bottom(FinishMonsterTurn(0));
@@ -260,7 +389,7 @@ impl Action for DoMonsterIntent {
 impl Action for FinishMonsterTurn {
   fn execute(&self, runner: &mut impl Runner) {
     if let Some(monster) = runner.state_mut().monsters.get_mut(self.0) {
-      if !monster.gone {
+      if !monster.gone || monster.half_dead {
         runner.action_now(&ChooseMonsterIntent(self.0));
       }
       if !runner.state().combat_over() {
@@ -275,20 +404,90 @@ impl Action for FinishMonsterTurn {
 impl Action for EndMonstersTurns {
   fn execute(&self, runner: &mut impl Runner) {
     apply_end_of_turn_powers(runner);
+    start_next_player_turn(runner);
+  }
+}
+
+fn start_next_player_turn(runner: &mut impl Runner) {
+  let state = runner.state_mut();
+  state.turn_number += 1;
+  state.turn_has_ended = false;
+  state.cards_discarded_this_turn = 0;
+  state.attacks_played_this_turn = 0;
+  state.cards_played_this_turn = 0;
+  start_player_turn(runner);
+}
+
+/// Everything that happens at the start of each of the player's turns, including the first.
+pub fn start_player_turn(runner: &mut impl Runner) {
+  start_creature_turn(runner, CreatureIndex::Player);
+  if runner.state().player.stance == Stance::Divinity {
+    runner.action_bottom(ChangeStanceAction(Stance::Neutral));
+  }
+  let state = runner.state_mut();
+  state.player.energy = 3
+    + state
+      .relics
+      .iter()
+      .map(|relic| relic.relic_id.inherent_energy())
+      .sum::<i32>();
+  power_hook!(runner, CreatureIndex::Player, on_energy_recharge());
+  relic_hook!(runner, at_turn_start());
+  for orb_index in 0..runner.state().player.orbs.len() {
+    if runner.state().player.orbs[orb_index].orb_type == OrbType::Plasma {
+      if let Some(action) = orb_passive_effect(runner.state_mut(), orb_index) {
+        runner.action_bottom(action);
+      }
+    }
+  }
+  let player = &runner.state().player.creature;
+  let mut draw = 5 + player.power_amount(PowerId::Draw);
+  if player.has_power(PowerId::DrawReduction) {
+    draw -= 1;
+  }
+  runner.action_bottom(DrawCards(draw));
+  // these hooks queue their actions at the bottom too, so they happen after the draw
+  power_hook!(runner, CreatureIndex::Player, at_start_of_turn_post_draw());
+  relic_hook!(runner, at_turn_start_post_draw());
+}
+
+/// Starts a combat whose monsters have their hitpoints, but no powers or intents yet. Expects
+/// `turn_number` to be 0, because the monsters choose their first intents the same way they
+/// choose the rest, at the end of the turn before.
+impl Action for StartCombat {
+  fn execute(&self, runner: &mut impl Runner) {
     let state = runner.state_mut();
-    state.turn_number += 1;
-    state.turn_has_ended = false;
-    start_creature_turn(runner, CreatureIndex::Player);
-    let state = runner.state_mut();
-    state.player.energy = 3
-      + state
-        .player
-        .creature
-        .powers
-        .iter()
-        .map(|power| power.power_id.inherent_energy())
-        .sum::<i32>();
-    runner.action_now(&DrawCards(5));
+    let mut innate = Vec::new();
+    state.draw_pile.retain(|card| {
+      if card.innate() {
+        innate.push(card.clone());
+        false
+      } else {
+        true
+      }
+    });
+    let num_innate = innate.len() as i32;
+    state.draw_pile_top.extend(innate);
+    for monster_index in 0..runner.state().monsters.len() {
+      let monster_id = runner.state().monsters[monster_index].monster_id;
+      monster_id.pre_battle(runner, monster_index);
+    }
+    for monster_index in 0..runner.state().monsters.len() {
+      runner.action_bottom(ChooseMonsterIntent(monster_index));
+    }
+    runner.action_bottom(StartFirstTurn);
+    // like the game, draw all the innate cards even if there are more than a hand's worth
+    if num_innate > 5 {
+      runner.action_bottom(DrawCards(num_innate - 5));
+    }
+  }
+}
+
+impl Action for StartFirstTurn {
+  fn execute(&self, runner: &mut impl Runner) {
+    runner.state_mut().turn_number = 1;
+    start_player_turn(runner);
+    relic_hook!(runner, at_battle_start());
   }
 }
 
@@ -304,7 +503,7 @@ impl Action for ChooseMonsterIntent {
   }
   fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
     let monster = &mut runner.state_mut().monsters[self.0];
-    if !monster.gone {
+    if !monster.gone || monster.half_dead {
       let monster_id = monster.monster_id;
       monster.push_intent(random_value as IntentId);
       monster_id.after_choosing_intent(runner, self.0);
@@ -312,68 +511,80 @@ impl Action for ChooseMonsterIntent {
   }
 }
 
-fn damage_amount_after_block_and_powers(
-  state: &CombatState,
-  target_index: CreatureIndex,
-  mut damage: i32,
-) -> i32 {
-  //TODO reduce duplicate code id 0d9fdje98
-  //TODO: intangible
-  if damage < 0 {
-    damage = 0;
-  }
-
-  let target = state.get_creature(target_index);
-  if damage >= target.block {
-    damage -= target.block;
-  } else {
-    damage = 0;
-  }
-
-  power_hook!(
-    state,
-    target_index,
-    damage = on_attacked_to_change_damage(damage)
-  );
-  damage
-}
-
 impl Action for DamageAction {
   fn execute(&self, runner: &mut impl Runner) {
-    let mut damage = self.info.output;
-    //TODO reduce duplicate code id 0d9fdje98
-    //TODO: intangible
-    if damage < 0 {
-      damage = 0;
-    }
+    let mut damage = std::cmp::max(self.info.output, 0);
+    power_hook!(
+      runner.state(),
+      self.target,
+      damage = modify_damage_before_block(&self.info, damage)
+    );
 
-    let target = runner.state_mut().get_creature_mut(self.target);
-    if damage >= target.block {
-      damage -= target.block;
-      target.block = 0;
-    } else {
-      target.block -= damage;
-      damage = 0;
+    // hitpoint loss goes straight past block
+    if self.info.damage_type != DamageType::HitpointLoss {
+      let target = runner.state_mut().get_creature_mut(self.target);
+      if damage >= target.block {
+        damage -= target.block;
+        target.block = 0;
+      } else {
+        target.block -= damage;
+        damage = 0;
+      }
     }
 
+    if self.target == CreatureIndex::Player {
+      relic_hook!(
+        runner,
+        on_attacked_to_change_damage(&self.info, &mut damage)
+      );
+    }
     power_hook!(
-      runner.state(),
+      runner,
       self.target,
-      damage = on_attacked_to_change_damage(damage)
+      on_attacked_to_change_damage(&self.info, &mut damage)
     );
+    if let Some(owner) = self.info.owner {
+      power_hook!(runner, owner, on_attack(&self.info, damage, self.target));
+    }
     power_hook!(runner, self.target, on_attacked(self.info.clone(), damage));
+    lose_hitpoints(runner, self.target, damage);
+  }
+}
 
-    let target = runner.state_mut().get_creature_mut(self.target);
-    target.hitpoints -= damage;
-    if target.hitpoints <= 0 {
-      target.hitpoints = 0;
-      match self.target {
-        CreatureIndex::Player => {}
-        CreatureIndex::Monster(monster_index) => {
-          runner.state_mut().monsters[monster_index].gone = true;
-          power_hook!(runner, self.target, on_death());
+/// The last step of every loss of hitpoints, whether from an attack, Poison or the player's own
+/// cards.
+fn lose_hitpoints(runner: &mut impl Runner, target_index: CreatureIndex, mut damage: i32) {
+  if target_index == CreatureIndex::Player {
+    relic_hook!(runner, on_lose_hp_last(&mut damage));
+  }
+
+  runner.state_mut().get_creature_mut(target_index).hitpoints -= damage;
+  if target_index == CreatureIndex::Player && damage > 0 {
+    relic_hook!(runner, was_hp_lost(damage));
+  }
+  let target = runner.state_mut().get_creature_mut(target_index);
+  if target.hitpoints <= 0 {
+    target.hitpoints = 0;
+    match target_index {
+      CreatureIndex::Player => {
+        let state = runner.state_mut();
+        if let Some(index) = state
+          .potions
+          .iter()
+          .position(|p| p.id == CardId::FairyPotion)
+        {
+          state.potions.remove(index);
+          let percent = if state.has_relic(RelicId::SacredBark) {
+            60
+          } else {
+            30
+          };
+          let amount = (state.player.creature.max_hitpoints * percent) as f64 / 100.0;
+          // it heals at least 1 hitpoint
+          state.heal(CreatureIndex::Player, std::cmp::max(amount as i32, 1));
         }
       }
+      CreatureIndex::Monster(monster_index) => kill_monster(runner, monster_index),
     }
   }
 }
@@ -381,11 +592,13 @@ impl Action for DamageAction {
 impl Action for DamageAllEnemiesAction {
   fn execute(&self, runner: &mut impl Runner) {
     let mut actions: ArrayVec<DamageAction, MAX_MONSTERS> = ArrayVec::new();
-    for monster_index in 0..runner.state().monsters.len() {
+    for (monster_index, info) in self.infos.iter().enumerate() {
       if !runner.state().monsters[monster_index].gone {
         let target = CreatureIndex::Monster(monster_index);
-        let info = self.info.apply_target_powers(runner.state(), target);
-        actions.push(DamageAction { target, info });
+        actions.push(DamageAction {
+          target,
+          info: info.clone(),
+        });
       }
     }
     for action in &actions {
@@ -412,22 +625,42 @@ impl Action for DamageAllEnemiesActionIgnoringPowers {
 
 impl Action for VampireDamageAllEnemiesAction {
   fn execute(&self, runner: &mut impl Runner) {
-    let mut heal_amount = 0;
     let mut actions: ArrayVec<DamageAction, MAX_MONSTERS> = ArrayVec::new();
-    for monster_index in 0..runner.state().monsters.len() {
+    for (monster_index, info) in self.infos.iter().enumerate() {
       if !runner.state().monsters[monster_index].gone {
         let target = CreatureIndex::Monster(monster_index);
-        let info = self.info.apply_target_powers(runner.state(), target);
-        heal_amount += damage_amount_after_block_and_powers(runner.state(), target, info.output);
-        actions.push(DamageAction { target, info });
+        actions.push(DamageAction {
+          target,
+          info: info.clone(),
+        });
       }
     }
+    let hitpoints_before = runner
+      .state()
+      .monsters
+      .iter()
+      .map(|monster| monster.creature.hitpoints)
+      .collect();
     for action in &actions {
       runner.action_now(action);
     }
+    // heal for the damage that was actually taken, after every modification
+    runner.action_now(&VampireHealAction { hitpoints_before });
+  }
+}
+
+impl Action for VampireHealAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state();
+    let amount = self
+      .hitpoints_before
+      .iter()
+      .zip(&state.monsters)
+      .map(|(&before, monster)| std::cmp::max(before - monster.creature.hitpoints, 0))
+      .sum();
     runner.action_bottom(HealAction {
       creature_index: CreatureIndex::Player,
-      amount: heal_amount,
+      amount,
     })
   }
 }
@@ -459,27 +692,56 @@ impl Action for AttackDamageRandomEnemyAction {
     ))
   }
   fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
-    // hack: this is not quite where powers are applied to card/monster damage in the actual code
+    // like the actual game, the damage is calculated only once the target is known
     let target = CreatureIndex::Monster(random_value as usize);
-    let info = self.info.apply_target_powers(runner.state(), target);
+    let info = self.info.apply_all_powers(runner.state(), target);
+    runner.action_now(&DamageAction { target, info });
+  }
+}
+
+impl Action for MonsterAttackAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    // In the actual game, a monster's damage info is recalculated whenever powers change,
+    // so the damage actually dealt is based on the powers at the time each hit resolves.
+    if let Some(CreatureIndex::Monster(monster_index)) = self.info.owner {
+      if runner.state().monsters[monster_index].gone {
+        return;
+      }
+    }
+    let target = CreatureIndex::Player;
+    let info = self.info.apply_all_powers(runner.state(), target);
     runner.action_now(&DamageAction { target, info });
   }
 }
 
 impl Action for DrawCardRandom {
   fn determinism(&self, state: &CombatState) -> Determinism {
-    Determinism::Random(Distribution(
-      (0..state.draw_pile.len() as i32)
-        .map(|index| (1.0, index))
-        .collect(),
-    ))
+    if state.draw_pile_top.is_empty() {
+      Determinism::Random(Distribution(
+        (0..state.draw_pile.len() as i32)
+          .map(|index| (1.0, index))
+          .collect(),
+      ))
+    } else {
+      Determinism::Deterministic
+    }
+  }
+  fn execute(&self, runner: &mut impl Runner) {
+    let card = runner.state_mut().draw_pile_top.pop().unwrap();
+    draw_card(runner, card);
   }
   fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
     let card = runner.state_mut().draw_pile.remove(random_value as usize);
-    runner.state_mut().hand.push(card);
+    draw_card(runner, card);
   }
 }
 
+fn draw_card(runner: &mut impl Runner, mut card: SingleCard) {
+  card.card_info.id.on_draw(runner, &mut card);
+  power_hook!(runner, CreatureIndex::Player, on_card_draw(&card));
+  runner.state_mut().hand.push(card);
+}
+
 impl Action for DrawCards {
   fn execute(&self, runner: &mut impl Runner) {
     if runner.state().player.creature.has_power(PowerId::NoDraw) {
@@ -494,20 +756,25 @@ impl Action for DrawCards {
     if state.hand.len() == 10 {
       return;
     }
-    if state.draw_pile.is_empty() {
-      state.num_reshuffles += 1;
-      // I considered sorting to prevent some rare kinds of draw order manipulation,
-      // but it turned out to cause about a 4% slowdown in playouts, which seems troublesome
-      //state.discard_pile.sort();
-      std::mem::swap(&mut state.draw_pile, &mut state.discard_pile);
+    if state.draw_pile_len() == 0 {
+      state.reshuffle_discard_pile();
     }
-    if !state.draw_pile.is_empty() {
+    if state.draw_pile_len() != 0 {
       runner.action_now(&DrawCardRandom);
       runner.action_now(&DrawCards(self.0 - 1));
     }
   }
 }
 
+/// Like the actual game, negative strength or dexterity counts as a debuff.
+pub fn is_debuff(power_id: PowerId, amount: i32) -> bool {
+  power_id.power_type() == PowerType::Debuff
+    || (matches!(
+      power_id,
+      PowerId::Strength | PowerId::Dexterity | PowerId::Focus
+    ) && amount < 0)
+}
+
 impl Action for ApplyPowerAction {
   fn execute(&self, runner: &mut impl Runner) {
     if let CreatureIndex::Monster(monster_index) = self.target {
@@ -518,11 +785,12 @@ impl Action for ApplyPowerAction {
 
     // TODO: Snecko Skull, Champion Belt, Ginger, Turnip
 
+    let debuff = is_debuff(self.power_id, self.amount);
     if runner
       .state()
       .get_creature(self.target)
       .has_power(PowerId::Artifact)
-      && self.power_id.power_type() == PowerType::Debuff
+      && debuff
     {
       power_hook!(
         runner,
@@ -554,6 +822,16 @@ impl Action for ApplyPowerAction {
       });
       target.powers.sort_by_key(|power| power.power_id.priority());
     }
+
+    if self.power_id == PowerId::Mantra {
+      runner.state_mut().mantra_gained_this_combat += self.amount;
+      power_hook!(
+        runner,
+        self.target,
+        power_id: PowerId::Mantra,
+        on_specific_trigger()
+      );
+    }
   }
 }
 
@@ -595,6 +873,11 @@ impl Action for GainBlockAction {
     let creature = runner.state_mut().get_creature_mut(self.creature_index);
     if self.amount > 0 {
       creature.block += self.amount;
+      power_hook!(
+        runner,
+        self.creature_index,
+        on_gained_block(self.amount as f64)
+      );
     }
   }
 }
@@ -611,114 +894,1227 @@ impl Action for GainEnergyAction {
   }
 }
 
+impl Action for LoseEnergyAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let energy = &mut runner.state_mut().player.energy;
+    *energy = std::cmp::max(0, *energy - self.0);
+  }
+}
+
 impl Action for DiscardNewCard {
   fn execute(&self, runner: &mut impl Runner) {
     runner.state_mut().discard_pile.push(self.0.clone());
   }
 }
 
-impl Action for ArmamentsAction {
+impl Action for AddNewCardToHand {
+  fn execute(&self, runner: &mut impl Runner) {
+    add_card_to_hand(runner.state_mut(), self.0.clone());
+  }
+}
+
+impl Action for ShuffleNewCardIntoDrawPile {
   fn determinism(&self, state: &CombatState) -> Determinism {
-    if self.upgraded {
+    // The card lands in a uniformly random position. The random value is how many of the known
+    // top cards end up above it; anywhere below all of them is just part of the unknown remainder.
+    let known = state.draw_pile_top.len() as i32;
+    if known == 0 {
       Determinism::Deterministic
     } else {
-      // TODO: Determinism::Choice
-      Determinism::Random(Distribution(
-        (0..state.hand.len()).map(|i| (1.0, i as i32)).collect(),
-      ))
+      let mut distribution = Distribution((0..known).map(|above| (1.0, above)).collect());
+      distribution
+        .0
+        .push((state.draw_pile.len() as f64 + 1.0, known));
+      Determinism::Random(distribution)
     }
   }
-
   fn execute(&self, runner: &mut impl Runner) {
-    if self.upgraded {
-      for card in &mut runner.state_mut().hand {
-        card.upgrade()
-      }
-    }
+    runner.state_mut().draw_pile.push(self.0.clone());
   }
   fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
-    runner.state_mut().hand[random_value as usize].upgrade()
+    let state = runner.state_mut();
+    let known = state.draw_pile_top.len();
+    let above = random_value as usize;
+    if above == known {
+      state.draw_pile.push(self.0.clone());
+    } else {
+      state.draw_pile_top.insert(known - above, self.0.clone());
+    }
   }
 }
 
-impl Action for InitializeMonsterInnateDamageAmount {
-  fn determinism(&self, state: &CombatState) -> Determinism {
-    Determinism::Random(Distribution(
-      (self.range.0..self.range.1)
-        .map(|index| (1.0, index))
-        .collect(),
-    ))
+pub fn exhaust_card(runner: &mut impl Runner, card: SingleCard) {
+  power_hook!(runner, CreatureIndex::Player, on_exhaust(&card));
+  runner.state_mut().exhaust_pile.push(card);
+}
+
+/// Discard a card because of an effect, rather than at the end of the turn; only this kind
+/// of discard triggers cards like Reflex and Tactician.
+pub fn discard_card(runner: &mut impl Runner, card: SingleCard) {
+  let state = runner.state_mut();
+  state.cards_discarded_this_turn += 1;
+  for other in &mut state.hand {
+    other.card_info.id.on_other_card_discarded(other);
   }
-  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
-    let mut monster = &mut runner.state_mut().monsters[self.monster_index];
-    monster.innate_damage_amount = Some(random_value);
+  card.card_info.id.on_manual_discard(runner, &card);
+  runner.state_mut().discard_pile.push(card);
+}
+
+pub fn add_card_to_hand(state: &mut CombatState, card: SingleCard) {
+  if state.hand.len() < 10 {
+    state.hand.push(card);
+  } else {
+    state.discard_pile.push(card);
   }
 }
 
-impl Action for GainBlockRandomMonsterAction {
-  fn determinism(&self, state: &CombatState) -> Determinism {
-    let others: SmallVec<_> = state
+/// Moves a copy of `card` from the discard pile to the hand, if there's room; for cards like
+/// Flurry of Blows that come back by themselves.
+pub fn return_from_discard_to_hand(state: &mut CombatState, card: &SingleCard) {
+  if state.hand.len() >= 10 {
+    return;
+  }
+  if let Some(index) = state.discard_pile.iter().position(|other| other == card) {
+    let card = state.discard_pile.remove(index);
+    state.hand.push(card);
+  }
+}
+
+pub fn kill_monster(runner: &mut impl Runner, monster_index: usize) {
+  let monster = &mut runner.state_mut().monsters[monster_index];
+  monster.creature.hitpoints = 0;
+  monster.gone = true;
+  let minion = monster.creature.has_power(PowerId::Minion);
+  let monster_id = monster.monster_id;
+  power_hook!(runner, CreatureIndex::Monster(monster_index), on_death());
+  monster_id.on_death(runner, monster_index);
+  // monsters that fall without dying don't take their minions with them
+  if runner.state().monsters[monster_index].half_dead {
+    return;
+  }
+
+  // Gremlin Leader, Reptomancer, The Collector and Bronze Automaton take their minions with them
+  let state = runner.state_mut();
+  if !minion
+    && state
       .monsters
       .iter()
-      .enumerate()
-      .filter(|&(index, monster)| index != self.source && !monster.gone)
-      .map(|(index, monster)| (1.0, index as i32))
+      .all(|monster| monster.gone || monster.creature.has_power(PowerId::Minion))
+  {
+    for monster in &mut state.monsters {
+      monster.gone = true;
+    }
+  }
+}
+
+impl ChooseCardAction {
+  pub fn eligible_cards(&self, state: &CombatState) -> Vec<SingleCard> {
+    let mut result: Vec<SingleCard> = self
+      .choice_type
+      .candidates(state)
+      .filter(|card| self.choice_type.eligible(card))
+      .cloned()
       .collect();
-    Determinism::Random(if others.is_empty() {
-      Distribution::from(self.source as i32)
-    } else {
-      Distribution(others)
-    })
+    // identical cards are not meaningfully different choices
+    result.sort();
+    result.dedup();
+    result
   }
-  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
-    let creature = &mut runner.state_mut().monsters[random_value as usize].creature;
-    if self.amount > 0 {
-      creature.block += self.amount;
+  pub fn legal_choices(&self, state: &CombatState) -> Vec<Choice> {
+    let mut result: Vec<Choice> = self
+      .eligible_cards(state)
+      .into_iter()
+      .map(|card| {
+        SelectCard {
+          card,
+          choice_type: self.choice_type,
+        }
+        .into()
+      })
+      .collect();
+    if self.choice_type.optional() {
+      result.push(
+        SkipCardChoice {
+          choice_type: self.choice_type,
+        }
+        .into(),
+      );
     }
+    result
   }
 }
 
-impl Action for SplitAction {
+impl Action for ChooseCardAction {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    // like the actual game, skip the selection screen when there's nothing to decide
+    let eligible = self.eligible_cards(state).len();
+    if eligible > 1 || (eligible == 1 && self.choice_type.optional()) {
+      Determinism::Choice
+    } else {
+      Determinism::Deterministic
+    }
+  }
   fn execute(&self, runner: &mut impl Runner) {
-    let &SplitAction(index, ids) = self;
-    let state = runner.state_mut();
-    let splitting = &mut state.monsters[index];
-
-    let new_monsters: [Monster; 2] = ids.map(|monster_id| Monster {
-      monster_id,
-      innate_damage_amount: None,
-      ascension: splitting.ascension,
-      move_history: Vec::new(),
-      gone: false,
-      creature: Creature {
-        hitpoints: splitting.creature.hitpoints,
-        max_hitpoints: splitting.creature.hitpoints,
-        block: 0,
-        powers: Vec::new(),
-      },
-    });
-
-    splitting.creature.hitpoints = 0;
-    splitting.gone = true;
-    runner
-      .state_mut()
-      .monsters
-      .extend(new_monsters.iter().cloned());
+    if self.choice_type.optional() {
+      // nothing to pick from, which is the same as picking nothing
+      runner.action_now(&SkipCardChoice {
+        choice_type: self.choice_type,
+      });
+      return;
+    }
+    if let Some(card) = self.eligible_cards(runner.state()).pop() {
+      runner.action_now(&SelectCard {
+        card,
+        choice_type: self.choice_type,
+      });
+    }
   }
 }
 
-impl Action for EscapeAction {
+impl Action for SelectCard {
   fn execute(&self, runner: &mut impl Runner) {
     let state = runner.state_mut();
-    let escaping = &mut state.monsters[self.0];
-
-    escaping.gone = true;
+    match self.choice_type {
+      CardChoiceType::UpgradeCard => {
+        let card = state.hand.iter_mut().find(|c| **c == self.card).unwrap();
+        card.upgrade();
+        return;
+      }
+      CardChoiceType::RetainCard { remaining } => {
+        let card = state.hand.iter_mut().find(|c| **c == self.card).unwrap();
+        card.retain = true;
+        if remaining > 1 {
+          runner.action_now(&ChooseCardAction {
+            choice_type: CardChoiceType::RetainCard {
+              remaining: remaining - 1,
+            },
+          });
+        }
+        return;
+      }
+      _ => {}
+    }
+    let card = match self.choice_type.pile() {
+      CardPile::Hand => {
+        let index = state.hand.iter().position(|c| *c == self.card).unwrap();
+        state.hand.remove(index)
+      }
+      CardPile::DrawPile => state.remove_from_draw_pile(&self.card).unwrap(),
+      CardPile::DiscardPile => {
+        let index = state
+          .discard_pile
+          .iter()
+          .position(|c| *c == self.card)
+          .unwrap();
+        state.discard_pile.remove(index)
+      }
+      CardPile::ExhaustPile => {
+        let index = state
+          .exhaust_pile
+          .iter()
+          .position(|c| *c == self.card)
+          .unwrap();
+        state.exhaust_pile.remove(index)
+      }
+      CardPile::Offered => {
+        let index = state
+          .offered_cards
+          .iter()
+          .position(|c| *c == self.card)
+          .unwrap();
+        state.offered_cards.remove(index)
+      }
+    };
+    match self.choice_type {
+      CardChoiceType::ExhaustCard => exhaust_card(runner, card),
+      CardChoiceType::DiscardCard => discard_card(runner, card),
+      CardChoiceType::HandTopdeck | CardChoiceType::DiscardTopdeck => {
+        state.put_card_on_top_of_draw_pile(card);
+      }
+      CardChoiceType::HandTopdeckFree => {
+        let mut card = card;
+        card.free_to_play_once = true;
+        state.put_card_on_top_of_draw_pile(card);
+      }
+      CardChoiceType::DuplicateAttackOrPower { copies } => {
+        for _ in 0..copies {
+          add_card_to_hand(state, card.clone());
+        }
+        add_card_to_hand(state, card);
+      }
+      CardChoiceType::DiscardToHand | CardChoiceType::DrawPileToHand => {
+        add_card_to_hand(state, card);
+      }
+      CardChoiceType::RecycleCard => {
+        let energy = card.cost_in_practice(state);
+        exhaust_card(runner, card);
+        runner.action_top(GainEnergyAction(energy));
+      }
+      CardChoiceType::ScryDiscard { cards } => {
+        state.discard_pile.push(card);
+        if cards > 1 {
+          runner.action_now(&ChooseCardAction {
+            choice_type: CardChoiceType::ScryDiscard { cards: cards - 1 },
+          });
+        }
+      }
+      CardChoiceType::DiscardToHandRetained => {
+        let mut card = card;
+        card.retain = true;
+        add_card_to_hand(state, card);
+      }
+      CardChoiceType::DiscardToHandFree => {
+        let mut card = card;
+        card.free_this_turn = true;
+        add_card_to_hand(state, card);
+      }
+      CardChoiceType::ExhaustAnyCards => {
+        exhaust_card(runner, card);
+        runner.action_now(&ChooseCardAction {
+          choice_type: CardChoiceType::ExhaustAnyCards,
+        });
+      }
+      CardChoiceType::GambleDiscard { discarded } => {
+        discard_card(runner, card);
+        runner.action_now(&ChooseCardAction {
+          choice_type: CardChoiceType::GambleDiscard {
+            discarded: discarded + 1,
+          },
+        });
+      }
+      CardChoiceType::DrawPilePlayTwice => {
+        // each one goes to the front of the card queue, so the copy that disappears plays first,
+        // and the card itself is exhausted after the second play
+        runner.action_now(&AutoplayCardAction {
+          card: card.clone(),
+          exhaust: true,
+          purge_on_use: false,
+        });
+        runner.action_now(&AutoplayCardAction {
+          card,
+          exhaust: false,
+          purge_on_use: true,
+        });
+      }
+      CardChoiceType::Discovery { copies } => {
+        // the cards that weren't picked disappear
+        state.offered_cards.clear();
+        let mut card = card;
+        card.free_this_turn = true;
+        for _ in 1..copies {
+          add_card_to_hand(state, card.clone());
+        }
+        add_card_to_hand(state, card);
+      }
+      CardChoiceType::ChooseOne => {
+        // the options that weren't picked disappear
+        state.offered_cards.clear();
+        card.card_info.id.on_chosen(runner, &card);
+      }
+      CardChoiceType::UpgradeCard | CardChoiceType::RetainCard { .. } => unreachable!(),
+    }
+  }
+}
+
+impl Action for SkipCardChoice {
+  fn execute(&self, runner: &mut impl Runner) {
+    // declining an "up to" selection ends it
+    if let CardChoiceType::GambleDiscard { discarded } = self.choice_type {
+      if discarded > 0 {
+        runner.action_now(&DrawCards(discarded));
+      }
+    }
+  }
+}
+
+impl Action for ChooseRandomCardAction {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    let eligible = self
+      .choice_type
+      .candidates(state)
+      .filter(|card| self.choice_type.eligible(card))
+      .count();
+    if eligible == 0 {
+      Determinism::Deterministic
+    } else {
+      Determinism::Random(Distribution(
+        (0..eligible as i32).map(|i| (1.0, i)).collect(),
+      ))
+    }
+  }
+  fn execute(&self, runner: &mut impl Runner) {
+    // nothing eligible
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    let card = self
+      .choice_type
+      .candidates(runner.state())
+      .filter(|card| self.choice_type.eligible(card))
+      .nth(random_value as usize)
+      .unwrap()
+      .clone();
+    runner.action_now(&SelectCard {
+      card,
+      choice_type: self.choice_type,
+    });
+  }
+}
+
+impl Action for ApplyPowerRandomEnemyAction {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    Determinism::Random(Distribution(
+      state
+        .monsters
+        .iter()
+        .enumerate()
+        .filter(|(index, monster)| !monster.gone)
+        .map(|(index, monster)| (1.0, index as i32))
+        .collect(),
+    ))
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    runner.action_now(&ApplyPowerAction {
+      source: CreatureIndex::Player,
+      target: CreatureIndex::Monster(random_value as usize),
+      power_id: self.power_id,
+      amount: self.amount,
+    });
+  }
+}
+
+impl Action for RemoveAllBlockAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    runner.state_mut().get_creature_mut(self.target).block = 0;
+  }
+}
+
+impl Action for ChangeStanceAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let old = runner.state().player.stance;
+    if old == self.0 {
+      return;
+    }
+    power_hook!(runner, CreatureIndex::Player, on_exit_stance(old));
+    runner.state_mut().player.stance = self.0;
+    stances::on_exit_stance(runner, old);
+    stances::on_enter_stance(runner, self.0);
+    power_hook!(runner, CreatureIndex::Player, on_enter_stance(self.0));
+
+    for card in runner.state().discard_pile.clone() {
+      card.card_info.id.on_stance_change(runner, &card);
+    }
+  }
+}
+
+impl Action for ScryAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    if runner.state().draw_pile_len() == 0 {
+      return;
+    }
+    power_hook!(runner, CreatureIndex::Player, on_scry());
+    runner.action_now(&RevealDrawPileTopAction(self.0));
+    runner.action_now(&ChooseCardAction {
+      choice_type: CardChoiceType::ScryDiscard { cards: self.0 },
+    });
+    runner.action_now(&FinishScryAction);
+  }
+}
+
+/// Make sure the top `self.0` cards of the draw pile are known, so they can be scried.
+impl Action for RevealDrawPileTopAction {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    if state.draw_pile_top.len() >= self.0 as usize || state.draw_pile.is_empty() {
+      Determinism::Deterministic
+    } else {
+      Determinism::Random(Distribution(
+        (0..state.draw_pile.len() as i32)
+          .map(|index| (1.0, index))
+          .collect(),
+      ))
+    }
+  }
+  fn execute(&self, runner: &mut impl Runner) {
+    // already known
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    let state = runner.state_mut();
+    let card = state.draw_pile.remove(random_value as usize);
+    // the revealed card is directly below the ones that were already known
+    state.draw_pile_top.insert(0, card);
+    runner.action_now(self);
+  }
+}
+
+impl Action for FinishScryAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    for card in runner.state().discard_pile.clone() {
+      card.card_info.id.on_scry(runner, &card);
+    }
+  }
+}
+
+impl Action for ChannelOrbAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    if state.player.orb_slots == 0 {
+      return;
+    }
+    match self.0.orb_type {
+      OrbType::Lightning => state.lightning_channeled_this_combat += 1,
+      OrbType::Frost => state.frost_channeled_this_combat += 1,
+      _ => {}
+    }
+    if !state.player.has_empty_orb_slot() {
+      // make room by evoking the leftmost orb
+      evoke_orb(runner, true);
+    }
+    runner.state_mut().player.orbs.push(self.0.clone());
+  }
+}
+
+impl Action for ChannelRandomOrbAction {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    Determinism::Random(Distribution((0..4).map(|index| (1.0, index)).collect()))
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    let orb_type = [
+      OrbType::Dark,
+      OrbType::Frost,
+      OrbType::Lightning,
+      OrbType::Plasma,
+    ][random_value as usize];
+    runner.action_now(&ChannelOrbAction(Orb::new(orb_type)));
+  }
+}
+
+/// Trigger the leftmost orb's evoke effect, if there is one; its effect happens before anything
+/// else in the queue.
+fn evoke_orb(runner: &mut impl Runner, remove: bool) {
+  let state = runner.state_mut();
+  if let Some(orb) = state.player.orbs.first().cloned() {
+    if remove {
+      state.player.orbs.remove(0);
+    }
+    let effect = orb_evoke_effect(runner.state(), &orb);
+    runner.action_top(effect);
+  }
+}
+
+impl Action for EvokeOrbAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    evoke_orb(runner, true);
+  }
+}
+
+impl Action for EvokeWithoutRemovingOrbAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    evoke_orb(runner, false);
+  }
+}
+
+impl Action for RemoveAllOrbsAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    runner.state_mut().player.orbs.clear();
+  }
+}
+
+impl Action for TriggerEndOfTurnOrbsAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    let mut effects: ArrayVec<DynAction, MAX_ORB_SLOTS> = ArrayVec::new();
+    for orb_index in 0..state.player.orbs.len() {
+      // Plasma triggers at the start of the turn instead
+      if state.player.orbs[orb_index].orb_type != OrbType::Plasma {
+        effects.extend(orb_passive_effect(state, orb_index));
+      }
+    }
+    // like the actual game, they happen before the rest of the end-of-turn effects
+    for effect in effects.into_iter().rev() {
+      runner.action_top(effect);
+    }
+  }
+}
+
+impl Action for LightningOrbDamageAction {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    if state.player.creature.has_power(PowerId::Electrodynamics) {
+      Determinism::Deterministic
+    } else {
+      Determinism::Random(Distribution(
+        state
+          .monsters
+          .iter()
+          .enumerate()
+          .filter(|(index, monster)| !monster.gone)
+          .map(|(index, monster)| (1.0, index as i32))
+          .collect(),
+      ))
+    }
+  }
+  fn execute(&self, runner: &mut impl Runner) {
+    // Electrodynamics: hit every enemy
+    let mut actions: ArrayVec<DamageAction, MAX_MONSTERS> = ArrayVec::new();
+    for (monster_index, monster) in runner.state().monsters.iter().enumerate() {
+      if !monster.gone {
+        let target = CreatureIndex::Monster(monster_index);
+        let info = orb_damage_info(runner.state(), target, self.amount);
+        actions.push(DamageAction { target, info });
+      }
+    }
+    for action in &actions {
+      runner.action_now(action);
+    }
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    let target = CreatureIndex::Monster(random_value as usize);
+    let info = orb_damage_info(runner.state(), target, self.amount);
+    runner.action_now(&DamageAction { target, info });
+  }
+}
+
+impl Action for DarkOrbEvokeAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    // the first of the monsters with the lowest hitpoints
+    let weakest = runner
+      .state()
+      .monsters
+      .iter()
+      .enumerate()
+      .filter(|(_, monster)| !monster.gone)
+      .min_by_key(|(_, monster)| monster.creature.hitpoints)
+      .map(|(index, _)| index);
+    if let Some(monster_index) = weakest {
+      let target = CreatureIndex::Monster(monster_index);
+      let info = orb_damage_info(runner.state(), target, self.amount);
+      runner.action_now(&DamageAction { target, info });
+    }
+  }
+}
+
+impl Action for IncreaseMaxOrbAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let player = &mut runner.state_mut().player;
+    player.orb_slots = std::cmp::min(MAX_ORB_SLOTS, player.orb_slots + self.0 as usize);
+  }
+}
+
+impl Action for DecreaseMaxOrbAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let player = &mut runner.state_mut().player;
+    player.orb_slots = player.orb_slots.saturating_sub(self.0 as usize);
+    // orbs that no longer fit are lost without being evoked
+    player.orbs.truncate(player.orb_slots);
+  }
+}
+
+/// Offers 3 different random cards one at a time, then lets the player pick one of them.
+impl DiscoveryAction {
+  fn unoffered_cards(&self, state: &CombatState) -> Vec<CardId> {
+    combat_card_pool(state.character, self.card_type)
+      .into_iter()
+      .filter(|&id| {
+        !state
+          .offered_cards
+          .iter()
+          .any(|card| card.card_info.id == id)
+      })
+      .collect()
+  }
+}
+
+impl Action for DiscoveryAction {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    let unoffered = self.unoffered_cards(state).len() as i32;
+    if state.offered_cards.len() >= 3 || unoffered == 0 {
+      Determinism::Deterministic
+    } else {
+      Determinism::Random(Distribution((0..unoffered).map(|i| (1.0, i)).collect()))
+    }
+  }
+  fn execute(&self, runner: &mut impl Runner) {
+    runner.action_now(&ChooseCardAction {
+      choice_type: CardChoiceType::Discovery {
+        copies: self.copies,
+      },
+    });
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    let state = runner.state_mut();
+    let card = SingleCard::create(self.unoffered_cards(state)[random_value as usize]);
+    state.offered_cards.push(card);
+    runner.action_now(self);
+  }
+}
+
+impl Action for ChooseOneAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    for &option in &self.options {
+      let mut card = SingleCard::create(option);
+      if self.upgraded {
+        card.upgrade();
+      }
+      state.offered_cards.push(card);
+    }
+    runner.action_now(&ChooseCardAction {
+      choice_type: CardChoiceType::ChooseOne,
+    });
+  }
+}
+
+impl Action for EscapeCombatAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    runner.state_mut().player.escaped = true;
+  }
+}
+
+impl Action for ObtainRandomPotionAction {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    if state.potions.len() >= state.potion_slots || state.has_relic(RelicId::Sozu) {
+      Determinism::Deterministic
+    } else {
+      Determinism::Random(Distribution(
+        random_potion_distribution(state.character)
+          .into_iter()
+          .enumerate()
+          .map(|(index, (weight, _))| (weight, index as i32))
+          .collect(),
+      ))
+    }
+  }
+  fn execute(&self, runner: &mut impl Runner) {
+    // no room for it, or Sozu
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    let state = runner.state_mut();
+    let (_, potion) = random_potion_distribution(state.character)[random_value as usize];
+    state.potions.push(potion.into());
+  }
+}
+
+impl Action for IncreaseMaxHpAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let creature = &mut runner.state_mut().player.creature;
+    creature.max_hitpoints += self.0;
+    creature.hitpoints += self.0;
+  }
+}
+
+impl UpgradeCardsAction {
+  pub fn new(filter: UpgradeCardsFilter, piles: &[CardPile]) -> UpgradeCardsAction {
+    UpgradeCardsAction {
+      filter,
+      piles: piles.iter().copied().collect(),
+    }
+  }
+}
+
+impl Action for UpgradeCardsAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    let filter = self.filter;
+    let upgrade = |cards: &mut [SingleCard]| {
+      for card in cards {
+        if filter.matches(card) {
+          card.upgrade();
+        }
+      }
+    };
+    for &pile in &self.piles {
+      match pile {
+        CardPile::Hand => upgrade(&mut state.hand),
+        CardPile::DrawPile => {
+          upgrade(&mut state.draw_pile_top);
+          upgrade(&mut state.draw_pile);
+        }
+        CardPile::DiscardPile => upgrade(&mut state.discard_pile),
+        CardPile::ExhaustPile => upgrade(&mut state.exhaust_pile),
+        CardPile::Offered => upgrade(&mut state.offered_cards),
+      }
+    }
+  }
+}
+
+impl Action for SecondWindAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    let mut exhausted = Vec::new();
+    state.hand.retain(|card| {
+      if card.card_info.card_type == CardType::Attack {
+        true
+      } else {
+        exhausted.push(card.clone());
+        false
+      }
+    });
+    for card in exhausted {
+      exhaust_card(runner, card);
+      runner.action_top(GainBlockAction {
+        creature_index: CreatureIndex::Player,
+        amount: self.block_per_card,
+      });
+    }
+  }
+}
+
+impl Action for CalculatedGambleAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    let count = state.hand.len();
+    let hand: ArrayVec<SingleCard, 10> = state.hand.drain(..).collect();
+    for card in hand {
+      discard_card(runner, card);
+    }
+    runner.action_bottom(DrawCards(count as i32));
+  }
+}
+
+impl Action for StormOfSteelAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    let count = state.hand.len();
+    let hand: ArrayVec<SingleCard, 10> = state.hand.drain(..).collect();
+    for card in hand {
+      discard_card(runner, card);
+    }
+    let mut shiv = SingleCard::create(CardId::Shiv);
+    if self.upgraded {
+      shiv.upgrade();
+    }
+    for _ in 0..count {
+      runner.action_bottom(AddNewCardToHand(shiv.clone()));
+    }
+  }
+}
+
+impl Action for UnloadAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    let mut discarded: ArrayVec<SingleCard, 10> = ArrayVec::new();
+    state.hand.retain(|card| {
+      if card.card_info.card_type == CardType::Attack {
+        true
+      } else {
+        discarded.push(card.clone());
+        false
+      }
+    });
+    for card in discarded {
+      discard_card(runner, card);
+    }
+  }
+}
+
+impl Action for BulletTimeAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    for card in &mut runner.state_mut().hand {
+      card.free_this_turn = true;
+    }
+  }
+}
+
+impl Action for EscapePlanAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    // whatever gets drawn goes after the cards already in hand
+    let drawn_index = runner.state().hand.len();
+    runner.action_now(&DrawCards(1));
+    runner.action_now(&EscapePlanBlockAction {
+      block: self.block,
+      drawn_index,
+    });
+  }
+}
+
+impl Action for EscapePlanBlockAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let drew_skill = runner
+      .state()
+      .hand
+      .get(self.drawn_index)
+      .map_or(false, |card| card.card_info.card_type == CardType::Skill);
+    if drew_skill {
+      runner.action_top(GainBlockAction {
+        creature_index: CreatureIndex::Player,
+        amount: self.block,
+      });
+    }
+  }
+}
+
+impl Action for ExpertiseAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let amount = self.0.saturating_sub(runner.state().hand.len());
+    runner.action_top(DrawCards(amount as i32));
+  }
+}
+
+impl Action for CatalystAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let poison = runner
+      .state()
+      .get_creature(self.target)
+      .power_amount(PowerId::Poison);
+    if poison > 0 {
+      runner.action_top(ApplyPowerAction {
+        source: CreatureIndex::Player,
+        target: self.target,
+        power_id: PowerId::Poison,
+        amount: poison * (self.multiplier - 1),
+      });
+    }
+  }
+}
+
+impl Action for RedoAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    if let Some(orb) = runner.state().player.orbs.first().cloned() {
+      // the same orb comes back, so a Dark orb keeps the damage it stored up
+      runner.action_top(ChannelOrbAction(orb));
+      runner.action_top(EvokeOrbAction);
+    }
+  }
+}
+
+impl Action for DarkImpulseAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    for orb_index in 0..state.player.orbs.len() {
+      if state.player.orbs[orb_index].orb_type == OrbType::Dark {
+        orb_passive_effect(state, orb_index);
+      }
+    }
+  }
+}
+
+impl Action for AllForOneAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    let mut index = 0;
+    while index < state.discard_pile.len() {
+      if state.discard_pile[index].cost_for_turn() == 0 && state.hand.len() < 10 {
+        let card = state.discard_pile.remove(index);
+        state.hand.push(card);
+      } else {
+        index += 1;
+      }
+    }
+  }
+}
+
+impl Action for ShuffleHandAndDiscardIntoDrawPile {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    state.num_reshuffles += 1;
+    // everything ends up in an unknown order, including the cards that were known to be on top
+    let CombatState {
+      draw_pile,
+      draw_pile_top,
+      discard_pile,
+      hand,
+      ..
+    } = state;
+    draw_pile.extend(draw_pile_top.drain(..));
+    draw_pile.extend(hand.drain(..));
+    draw_pile.extend(discard_pile.drain(..));
+  }
+}
+
+impl Action for TriggerMarksAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let mut actions: ArrayVec<DamageAction, MAX_MONSTERS> = ArrayVec::new();
+    for (monster_index, monster) in runner.state().monsters.iter().enumerate() {
+      let mark = monster.creature.power_amount(PowerId::Mark);
+      if !monster.gone && mark > 0 {
+        actions.push(DamageAction {
+          target: CreatureIndex::Monster(monster_index),
+          info: DamageInfoNoPowers::new(
+            Some(CreatureIndex::Player),
+            mark,
+            DamageType::HitpointLoss,
+          )
+          .ignore_powers(),
+        });
+      }
+    }
+    for action in &actions {
+      runner.action_now(action);
+    }
+  }
+}
+
+impl Action for ExhaustSpecificCardAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    if let Some(index) = state.hand.iter().position(|c| *c == self.0) {
+      let card = state.hand.remove(index);
+      exhaust_card(runner, card);
+    }
+  }
+}
+
+impl Action for WallopDamageAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let hitpoints_before = runner.state().get_creature(self.target).hitpoints;
+    runner.action_now(&DamageAction {
+      target: self.target,
+      info: self.info.clone(),
+    });
+    // block for the damage that was actually taken, after every modification
+    runner.action_now(&WallopBlockAction {
+      target: self.target,
+      hitpoints_before,
+    });
+  }
+}
+
+impl Action for WallopBlockAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let hitpoints = runner.state().get_creature(self.target).hitpoints;
+    runner.action_top(GainBlockAction {
+      creature_index: CreatureIndex::Player,
+      amount: std::cmp::max(self.hitpoints_before - hitpoints, 0),
+    });
+  }
+}
+
+impl Action for JudgementAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    if let CreatureIndex::Monster(monster_index) = self.target {
+      let monster = &runner.state().monsters[monster_index];
+      if !monster.gone && monster.creature.hitpoints <= self.threshold {
+        kill_monster(runner, monster_index);
+      }
+    }
+  }
+}
+
+impl Action for PlayTopCardAction {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    if state.draw_pile.is_empty() || !state.draw_pile_top.is_empty() {
+      Determinism::Deterministic
+    } else {
+      Determinism::Random(Distribution(
+        (0..state.draw_pile.len())
+          .map(|i| (1.0, i as i32))
+          .collect(),
+      ))
+    }
+  }
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    if let Some(card) = state.draw_pile_top.pop() {
+      runner.action_now(&AutoplayCardAction {
+        card,
+        exhaust: true,
+        purge_on_use: false,
+      });
+    } else if !state.discard_pile.is_empty() {
+      state.reshuffle_discard_pile();
+      runner.action_now(self);
+    }
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    let card = runner.state_mut().draw_pile.remove(random_value as usize);
+    runner.action_now(&AutoplayCardAction {
+      card,
+      exhaust: true,
+      purge_on_use: false,
+    });
+  }
+}
+
+impl Action for AutoplayCardAction {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    if self.card.card_info.has_target {
+      Determinism::Random(Distribution(
+        state
+          .monsters
+          .iter()
+          .enumerate()
+          .filter(|(index, monster)| !monster.gone)
+          .map(|(index, monster)| (1.0, index as i32))
+          .collect(),
+      ))
+    } else {
+      Determinism::Deterministic
+    }
+  }
+  fn execute(&self, runner: &mut impl Runner) {
+    self.execute_random(runner, 0);
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    let state = runner.state_mut();
+    let mut action = UseCardAction::new(self.card.clone(), random_value as usize, state);
+    action.exhaust = self.exhaust;
+    action.purge_on_use = self.purge_on_use;
+    state.card_queue.push_front(action);
+  }
+}
+
+impl Action for InitializeMonsterInnateDamageAmount {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    Determinism::Random(Distribution(
+      (self.range.0..self.range.1)
+        .map(|index| (1.0, index))
+        .collect(),
+    ))
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    let mut monster = &mut runner.state_mut().monsters[self.monster_index];
+    monster.innate_damage_amount = Some(random_value);
+  }
+}
+
+impl Action for InitializeMonsterHitpoints {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    Determinism::Random(Distribution(
+      (self.range.0..self.range.1)
+        .map(|hitpoints| (1.0, hitpoints))
+        .collect(),
+    ))
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    let creature = &mut runner.state_mut().monsters[self.monster_index].creature;
+    creature.hitpoints = random_value;
+    creature.max_hitpoints = random_value;
+  }
+}
+
+impl Action for InitializeMonsterPowerAmount {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    Determinism::Random(Distribution(
+      (self.range.0..self.range.1)
+        .map(|amount| (1.0, amount))
+        .collect(),
+    ))
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    runner.action_now(&ApplyPowerAction {
+      source: CreatureIndex::Monster(self.monster_index),
+      target: CreatureIndex::Monster(self.monster_index),
+      power_id: self.power_id,
+      amount: random_value,
+    });
+  }
+}
+
+impl Action for GainBlockRandomMonsterAction {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    let others: SmallVec<_> = state
+      .monsters
+      .iter()
+      .enumerate()
+      .filter(|&(index, monster)| index != self.source && !monster.gone)
+      .map(|(index, monster)| (1.0, index as i32))
+      .collect();
+    Determinism::Random(if others.is_empty() {
+      Distribution::from(self.source as i32)
+    } else {
+      Distribution(others)
+    })
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    let creature = &mut runner.state_mut().monsters[random_value as usize].creature;
+    if self.amount > 0 {
+      creature.block += self.amount;
+    }
+  }
+}
+
+impl Action for SpawnMonsterAction {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    if state.next_spawn_slot().is_some() {
+      Determinism::Random(Distribution(
+        (self.hitpoints.0..self.hitpoints.1)
+          .map(|hitpoints| (1.0, hitpoints))
+          .collect(),
+      ))
+    } else {
+      Determinism::Deterministic
+    }
+  }
+  fn execute(&self, _runner: &mut impl Runner) {
+    // no room for it
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    let state = runner.state_mut();
+    let slot = state.next_spawn_slot().unwrap();
+    let mut monster = Monster {
+      monster_id: self.monster_id,
+      // every monster in a combat has the same ascension
+      ascension: state
+        .monsters
+        .first()
+        .map_or(0, |monster| monster.ascension),
+      creature: Creature {
+        hitpoints: random_value,
+        max_hitpoints: random_value,
+        ..Default::default()
+      },
+      // it gets its first intent at the end of the monsters' turn, like everyone else
+      ..Default::default()
+    };
+    if self.minion {
+      monster.creature.powers.push(Power {
+        power_id: PowerId::Minion,
+        amount: -1,
+        ..Default::default()
+      });
+    }
+    if slot < state.monsters.len() {
+      state.monsters[slot] = monster;
+    } else {
+      state.monsters.push(monster);
+    }
+  }
+}
+
+impl Action for SplitAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let &SplitAction(index, ids) = self;
+    let splitting = &mut runner.state_mut().monsters[index];
+    let hitpoints = splitting.creature.hitpoints;
+    splitting.creature.hitpoints = 0;
+
+    for monster_id in ids {
+      runner.action_now(&SpawnMonsterAction {
+        monster_id,
+        hitpoints: (hitpoints, hitpoints + 1),
+        minion: false,
+      });
+    }
+    // it only leaves once its halves are there, so a lone slime splitting doesn't end the combat
+    runner.action_now(&EscapeAction(index));
+  }
+}
+
+impl Action for EscapeAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let state = runner.state_mut();
+    let escaping = &mut state.monsters[self.0];
+
+    escaping.gone = true;
+  }
+}
+
+impl Action for SuicideAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    if !runner.state().monsters[self.0].gone {
+      kill_monster(runner, self.0);
+    }
   }
 }
 
 impl Action for SummonGremlinAction {
   fn determinism(&self, state: &CombatState) -> Determinism {
-    Determinism::Random(Distribution((0..8).map(|n| (1.0, n)).collect()))
+    if state.next_spawn_slot().is_some() {
+      Determinism::Random(Distribution((0..8).map(|n| (1.0, n)).collect()))
+    } else {
+      Determinism::Deterministic
+    }
+  }
+  fn execute(&self, _runner: &mut impl Runner) {
+    // no room for it
   }
   fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
     use MonsterId::*;
@@ -732,22 +2128,177 @@ impl Action for SummonGremlinAction {
       GremlinWizard,
       ShieldGremlin,
     ][random_value as usize];
-
-    // TODO: actually implement summoning monsters properly
-    let gremlin = Monster {
+    let ascension = runner.state().monsters[0].ascension;
+    runner.action_now(&SpawnMonsterAction {
       monster_id,
-      ascension: 20,
-      creature: Creature {
-        hitpoints: 20,
-        max_hitpoints: 20,
-        ..Default::default()
-      },
+      hitpoints: hitpoints_range(monster_id, ascension),
+      minion: true,
+    });
+  }
+}
+
+impl StasisAction {
+  /// The rarest cards of the draw pile, or of the discard pile if the draw pile is empty.
+  fn candidates(&self, state: &CombatState) -> Vec<SingleCard> {
+    let pile: Vec<&SingleCard> = if state.draw_pile_len() > 0 {
+      state.draw_pile_cards().collect()
+    } else {
+      state.discard_pile.iter().collect()
+    };
+    for rarity in [Rarity::Rare, Rarity::Uncommon, Rarity::Common] {
+      let cards: Vec<SingleCard> = pile
+        .iter()
+        .filter(|card| card.card_info.rarity == rarity)
+        .map(|&card| card.clone())
+        .collect();
+      if !cards.is_empty() {
+        return cards;
+      }
+    }
+    pile.into_iter().cloned().collect()
+  }
+}
+
+impl Action for StasisAction {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    let candidates = self.candidates(state);
+    if candidates.is_empty() {
+      Determinism::Deterministic
+    } else {
+      Determinism::Random(Distribution(
+        (0..candidates.len() as i32).map(|n| (1.0, n)).collect(),
+      ))
+    }
+  }
+  fn execute(&self, _runner: &mut impl Runner) {
+    // nothing to take
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    let card = self.candidates(runner.state())[random_value as usize].clone();
+    let state = runner.state_mut();
+    if state.remove_from_draw_pile(&card).is_none() {
+      let index = state.discard_pile.iter().position(|c| *c == card).unwrap();
+      state.discard_pile.remove(index);
+    }
+    state.monsters[self.0].creature.powers.push(Power {
+      power_id: PowerId::Stasis,
+      amount: -1,
+      card: Some(card),
       ..Default::default()
+    });
+  }
+}
+
+impl Action for MonsterVampireAttackAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let monster_index = match self.info.owner {
+      Some(CreatureIndex::Monster(monster_index)) => monster_index,
+      _ => panic!("MonsterVampireAttackAction without a monster owner"),
     };
-    if let Some(slot) = runner.state_mut().monsters.iter_mut().find(|m| m.gone) {
-      *slot = gremlin;
+    if runner.state().monsters[monster_index].gone {
+      return;
+    }
+    let target = CreatureIndex::Player;
+    let info = self.info.apply_all_powers(runner.state(), target);
+    let hitpoints_before = runner.state().player.creature.hitpoints;
+    runner.action_now(&DamageAction { target, info });
+    // heal for the damage that was actually taken, after every modification
+    runner.action_now(&MonsterVampireHealAction {
+      monster_index,
+      hitpoints_before,
+    });
+  }
+}
+
+impl Action for MonsterVampireHealAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let amount = self.hitpoints_before - runner.state().player.creature.hitpoints;
+    if amount > 0 && !runner.state().monsters[self.monster_index].gone {
+      runner.action_top(HealAction {
+        creature_index: CreatureIndex::Monster(self.monster_index),
+        amount,
+      });
+    }
+  }
+}
+
+impl Action for RemoveDebuffsAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let debuffs: Vec<PowerId> = runner
+      .state()
+      .get_creature(self.0)
+      .powers
+      .iter()
+      .filter(|power| is_debuff(power.power_id, power.amount))
+      .map(|power| power.power_id)
+      .collect();
+    for power_id in debuffs {
+      runner.action_top(RemoveSpecificPowerAction {
+        target: self.0,
+        power_id,
+      });
+    }
+  }
+}
+
+impl Action for RandomizeCardCostAction {
+  fn determinism(&self, _state: &CombatState) -> Determinism {
+    Determinism::Random(Distribution((0..4).map(|cost| (1.0, cost)).collect()))
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    // the card was just drawn, so it's the last copy in hand
+    if let Some(card) = runner
+      .state_mut()
+      .hand
+      .iter_mut()
+      .rev()
+      .find(|card| **card == self.0)
+    {
+      card.cost = random_value;
+    }
+  }
+}
+
+impl SpireShieldDebuffAction {
+  fn apply(&self, runner: &mut impl Runner, power_id: PowerId) {
+    runner.action_top(ApplyPowerAction {
+      source: CreatureIndex::Monster(self.0),
+      target: CreatureIndex::Player,
+      power_id,
+      amount: -1,
+    });
+  }
+}
+
+impl Action for SpireShieldDebuffAction {
+  fn determinism(&self, state: &CombatState) -> Determinism {
+    // players with orbs lose Focus half the time instead; everyone else always loses Strength
+    if state.player.orbs.is_empty() {
+      Determinism::Deterministic
+    } else {
+      Determinism::Random(Distribution((0..2).map(|value| (1.0, value)).collect()))
+    }
+  }
+  fn execute(&self, runner: &mut impl Runner) {
+    self.apply(runner, PowerId::Strength);
+  }
+  fn execute_random(&self, runner: &mut impl Runner, random_value: i32) {
+    let power_id = if random_value == 0 {
+      PowerId::Focus
     } else {
-      runner.state_mut().monsters.push(gremlin);
+      PowerId::Strength
+    };
+    self.apply(runner, power_id);
+  }
+}
+
+impl Action for ReviveMonsterAction {
+  fn execute(&self, runner: &mut impl Runner) {
+    let monster = &mut runner.state_mut().monsters[self.monster_index];
+    if monster.half_dead {
+      monster.half_dead = false;
+      monster.gone = false;
+      monster.creature.hitpoints = self.hitpoints;
     }
   }
 }
@@ -5,7 +5,7 @@ use crate::simulation::{
   StandardRunnerHooks,
 };
 use crate::simulation_state::cards::consider_card_actions;
-use crate::simulation_state::{CardId, CombatState, PowerId, SingleCard, MAX_MONSTERS};
+use crate::simulation_state::{CardId, CombatState, PowerId, RelicId, SingleCard, MAX_MONSTERS};
 use arrayvec::ArrayVec;
 use ordered_float::OrderedFloat;
 use std::collections::{HashSet, VecDeque};
@@ -89,7 +89,7 @@ impl<'a, W: fmt::Write> NarrationHooks<'a, W> {
     writeln!(self.writer).unwrap();
   }
   fn write_hand(&mut self, state: &CombatState) {
-    write!(self.writer, "[{}] [", state.draw_pile.len()).unwrap();
+    write!(self.writer, "[{}] [", state.draw_pile_len()).unwrap();
     for card in &state.hand {
       write!(self.writer, "{}, ", card).unwrap();
     }
@@ -136,6 +136,9 @@ impl<'a, W: fmt::Write> StandardRunnerHooks for NarrationHooks<'a, W> {
         writeln!(self.writer, "=== EndTurn ===").unwrap();
         self.write_combatants(state);
       }
+      Choice::SelectCard(_) | Choice::SkipCardChoice(_) => {
+        writeln!(self.writer, "{}", choice).unwrap();
+      }
       _ => {}
     }
   }
@@ -212,11 +215,8 @@ pub fn card_play_stats(state: &CombatState, card: &SingleCard, target: usize) ->
         }
       }
       if let DynAction::DamageAllEnemiesAction(action) = action.clone().into() {
-        for (index, damage) in self.stats.damage.iter_mut().enumerate() {
-          *damage += action
-            .info
-            .apply_target_powers(self.state, CreatureIndex::Monster(index))
-            .output as f64;
+        for (damage, info) in self.stats.damage.iter_mut().zip(&action.infos) {
+          *damage += info.output as f64;
         }
       }
       if let DynAction::AttackDamageRandomEnemyAction(action) = action.clone().into() {
@@ -224,7 +224,7 @@ pub fn card_play_stats(state: &CombatState, card: &SingleCard, target: usize) ->
         for (index, damage) in self.stats.damage.iter_mut().enumerate() {
           *damage += action
             .info
-            .apply_target_powers(self.state, CreatureIndex::Monster(index))
+            .apply_all_powers(self.state, CreatureIndex::Monster(index))
             .output as f64
             / num_monsters;
         }
@@ -269,16 +269,25 @@ impl CombatResult {
         }
       }
       // ...and extra if you missed out and the combat reward (gold/potion chance) as well
-      if state.monsters.iter().all(|m| m.creature.hitpoints > 0) {
+      if state.player.escaped || state.monsters.iter().all(|m| m.creature.hitpoints > 0) {
         result.score -= 2.0 * hitpoint_value;
         potion_reward_chance = 0.0;
       }
 
+      for relic in &state.relics {
+        if relic.relic_id == RelicId::InkBottle {
+          let rewards = [0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 1.0, 2.0, 3.0];
+          result.score += rewards[relic.counter.max(0) as usize] * hitpoint_value;
+        }
+      }
       for power in &state.player.creature.powers {
         match power.power_id {
-          PowerId::InkBottle => {
-            let rewards = [0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 1.0, 2.0, 3.0];
-            result.score += rewards[power.amount as usize] * hitpoint_value;
+          PowerId::Repair => {
+            let healed = std::cmp::min(
+              power.amount,
+              state.player.creature.max_hitpoints - state.player.creature.hitpoints,
+            );
+            result.score += healed as f64 * hitpoint_value;
           }
           _ => {}
         }
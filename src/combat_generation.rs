@@ -0,0 +1,91 @@
+use crate::actions::{InitializeMonsterHitpoints, StartCombat};
+use crate::encounters;
+use crate::encounters::hitpoints_range;
+use crate::seed_system::{MaybeSeedView, TrivialSeed};
+use crate::simulation::{Runner, StandardRunner};
+use crate::simulation_state::*;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64Mcg;
+use serde::{Deserialize, Serialize};
+
+/// A combat that hasn't started yet: what the player brings into it, and which encounter they're
+/// facing. Lets us try decks against fights we haven't reached, without needing CommunicationMod.
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct CombatSpecification {
+  pub character: Character,
+  pub deck: Vec<SingleCard>,
+  pub relics: Vec<Relic>,
+  #[serde(default)]
+  pub potions: Vec<&'static CardInfo>,
+  pub hitpoints: i32,
+  pub max_hitpoints: i32,
+  pub ascension: i32,
+  /// The game's name for the encounter, like "Jaw Worm" or "Gremlin Gang".
+  pub encounter: String,
+}
+
+impl CombatSpecification {
+  /// Picks the encounter's monsters using `rng`, then starts the combat, seeded from `rng` too.
+  pub fn generate(&self, rng: &mut impl Rng) -> Result<CombatState, String> {
+    let monsters = encounters::by_name(&self.encounter)
+      .ok_or_else(|| format!("unknown encounter {:?}", self.encounter))?
+      .monsters(rng);
+    Ok(self.start(
+      &monsters,
+      TrivialSeed::new(Pcg64Mcg::from_rng(rng).unwrap()),
+    ))
+  }
+
+  /// Builds the combat against the given monsters and plays it up to the player's first choice.
+  /// Monster hitpoints and other starting values, the monsters' first intents and the first draw
+  /// all come from `seed`, so the same seed gives the same start.
+  pub fn start(
+    &self,
+    monsters: &[MonsterId],
+    seed: impl MaybeSeedView<CombatState>,
+  ) -> CombatState {
+    let mut draw_pile = self.deck.clone();
+    draw_pile.sort();
+    let mut state = CombatState {
+      draw_pile,
+      character: self.character,
+      potion_slots: if self.ascension >= 11 { 2 } else { 3 },
+      potions: self.potions.clone(),
+      relics: self.relics.clone(),
+      player: Player {
+        creature: Creature {
+          hitpoints: self.hitpoints,
+          max_hitpoints: self.max_hitpoints,
+          ..Default::default()
+        },
+        orb_slots: if self.character == Character::Defect {
+          3
+        } else {
+          0
+        },
+        ..Default::default()
+      },
+      monsters: monsters
+        .iter()
+        .map(|&monster_id| Monster {
+          monster_id,
+          ascension: self.ascension,
+          ..Default::default()
+        })
+        .collect(),
+      turn_number: 0,
+      ..Default::default()
+    };
+
+    let mut runner = StandardRunner::new(&mut state, seed);
+    for (monster_index, &monster_id) in monsters.iter().enumerate() {
+      runner.action_bottom(InitializeMonsterHitpoints {
+        monster_index,
+        range: hitpoints_range(monster_id, self.ascension),
+      });
+    }
+    runner.action_bottom(StartCombat);
+    runner.run_until_unable();
+    state
+  }
+}
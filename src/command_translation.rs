@@ -0,0 +1,495 @@
+//! Translates between the simulation's choices and CommunicationMod's commands.
+//!
+//! Choices identify cards and potions by value, while commands use positions in the game's hand,
+//! potion slots and monster list. Everything here is relative to a particular
+//! `communication_mod_state::GameState`, and the `CombatState` imported from it; positions in one
+//! state don't mean anything in another.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::actions::{DynAction, EndTurn, PlayCard, UsePotion};
+use crate::communication_mod_state as communication;
+use crate::simulation::Choice;
+use crate::simulation_state::{CardId, CardInfo, CombatState, MonsterId, SingleCard};
+
+/// The combat commands CommunicationMod accepts.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum CombatCommand {
+  /// `card_index` counts from 0, although CommunicationMod counts from 1. `target` is an index into
+  /// the game's list of monsters, which includes dead ones.
+  Play {
+    card_index: usize,
+    target: Option<usize>,
+  },
+  UsePotion {
+    slot: usize,
+    target: Option<usize>,
+  },
+  End,
+}
+
+impl Display for CombatCommand {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match *self {
+      CombatCommand::Play { card_index, target } => {
+        write!(f, "play {}", card_index + 1)?;
+        if let Some(target) = target {
+          write!(f, " {}", target)?;
+        }
+        Ok(())
+      }
+      CombatCommand::UsePotion { slot, target } => {
+        write!(f, "potion use {}", slot)?;
+        if let Some(target) = target {
+          write!(f, " {}", target)?;
+        }
+        Ok(())
+      }
+      CombatCommand::End => write!(f, "end"),
+    }
+  }
+}
+
+impl FromStr for CombatCommand {
+  type Err = String;
+  fn from_str(command: &str) -> Result<Self, Self::Err> {
+    let words: Vec<String> = command.split_whitespace().map(str::to_lowercase).collect();
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    let number = |word: &str| {
+      word
+        .parse::<usize>()
+        .map_err(|_| format!("{:?} isn't a number, in {:?}", word, command))
+    };
+    match words[..] {
+      ["end"] => Ok(CombatCommand::End),
+      ["play", card, ref target @ ..] if target.len() <= 1 => {
+        let card = number(card)?;
+        if card > 10 {
+          return Err(format!("there's no card {}, in {:?}", card, command));
+        }
+        Ok(CombatCommand::Play {
+          // CommunicationMod uses 0 for the tenth card
+          card_index: (card + 9) % 10,
+          target: target.first().map(|target| number(target)).transpose()?,
+        })
+      }
+      ["potion", "use", slot, ref target @ ..] if target.len() <= 1 => {
+        Ok(CombatCommand::UsePotion {
+          slot: number(slot)?,
+          target: target.first().map(|target| number(target)).transpose()?,
+        })
+      }
+      _ => Err(format!("{:?} isn't a combat command we know", command)),
+    }
+  }
+}
+
+impl CombatCommand {
+  /// The first word of the command, as CommunicationMod lists it in `available_commands`.
+  pub fn name(&self) -> &'static str {
+    match self {
+      CombatCommand::Play { .. } => "play",
+      CombatCommand::UsePotion { .. } => "potion",
+      CombatCommand::End => "end",
+    }
+  }
+
+  /// The card this command plays, as the game has it.
+  pub fn card<'a>(
+    &self,
+    observed: &'a communication::GameState,
+  ) -> Option<&'a communication::Card> {
+    match *self {
+      CombatCommand::Play { card_index, .. } => {
+        observed.combat_state.as_ref()?.hand.get(card_index)
+      }
+      _ => None,
+    }
+  }
+}
+
+fn combat(observed: &communication::GameState) -> Result<&communication::CombatState, String> {
+  observed
+    .combat_state
+    .as_ref()
+    .ok_or_else(|| "not in combat".to_string())
+}
+
+/// Checks that `target` is a monster you can target in both the game and the simulation. The
+/// simulation puts monsters that spawn mid-combat in different places than the game does, so
+/// indices only agree when the simulated monsters were imported from `observed`.
+fn check_target(
+  target: usize,
+  observed: &communication::GameState,
+  state: &CombatState,
+) -> Result<(), String> {
+  let observed_monster = combat(observed)?
+    .monsters
+    .get(target)
+    .ok_or_else(|| format!("there's no monster {}", target))?;
+  let monster = state
+    .monsters
+    .get(target)
+    .ok_or_else(|| format!("there's no simulated monster {}", target))?;
+  if MonsterId::from(&*observed_monster.id) != monster.monster_id {
+    return Err(format!(
+      "monster {} is {} in the game, but {:?} in the simulation",
+      target, observed_monster.id, monster.monster_id
+    ));
+  }
+  if observed_monster.is_gone || observed_monster.half_dead || monster.gone {
+    return Err(format!(
+      "monster {} ({}) can't be targeted",
+      target, observed_monster.id
+    ));
+  }
+  Ok(())
+}
+
+/// The command that makes `choice` in `observed`. `state` must be imported from `observed`.
+///
+/// When there are several copies of a card in hand, this plays the first; they're identical, as
+/// far as the simulation can tell, but they have different `uuid`s.
+pub fn command_for_choice(
+  choice: &Choice,
+  observed: &communication::GameState,
+  state: &CombatState,
+) -> Result<CombatCommand, String> {
+  match choice {
+    DynAction::EndTurn(_) => Ok(CombatCommand::End),
+    DynAction::PlayCard(play) => {
+      let card_index = combat(observed)?
+        .hand
+        .iter()
+        .position(|card| SingleCard::from(card) == play.card)
+        .ok_or_else(|| format!("{} isn't in the game's hand", play.card))?;
+      let target = if play.card.card_info.has_target {
+        check_target(play.target, observed, state)?;
+        Some(play.target)
+      } else {
+        None
+      };
+      Ok(CombatCommand::Play { card_index, target })
+    }
+    DynAction::UsePotion(potion) => {
+      let slot = observed
+        .potions
+        .iter()
+        .position(|observed| CardId::from(&*observed.id) == potion.potion_info.id)
+        .ok_or_else(|| format!("there's no {:?} in the game", potion.potion_info.id))?;
+      let target = if observed.potions[slot].requires_target {
+        check_target(potion.target, observed, state)?;
+        Some(potion.target)
+      } else {
+        None
+      };
+      Ok(CombatCommand::UsePotion { slot, target })
+    }
+    DynAction::SelectCard(_) | DynAction::SkipCardChoice(_) => Err(format!(
+      "{:?} is a choice in the middle of a card's effect, which we can't send to the game's \
+       selection screens yet",
+      choice
+    )),
+    _ => Err(format!("{:?} has no command", choice)),
+  }
+}
+
+/// The choice `command` makes in `observed`. `state` must be imported from `observed`.
+pub fn choice_for_command(
+  command: &CombatCommand,
+  observed: &communication::GameState,
+  state: &CombatState,
+) -> Result<Choice, String> {
+  match *command {
+    CombatCommand::End => Ok(EndTurn.into()),
+    CombatCommand::Play { card_index, target } => {
+      let observed_card = command
+        .card(observed)
+        .ok_or_else(|| format!("there's no card {} in the game's hand", card_index + 1))?;
+      let card = SingleCard::from(observed_card);
+      if !state.hand.contains(&card) {
+        return Err(format!("{} isn't in the simulated hand", card));
+      }
+      if card.card_info.has_target {
+        let target = target.ok_or_else(|| format!("{} needs a target", card))?;
+        check_target(target, observed, state)?;
+      }
+      Ok(
+        PlayCard {
+          card,
+          target: target.unwrap_or(0),
+        }
+        .into(),
+      )
+    }
+    CombatCommand::UsePotion { slot, target } => {
+      let observed_potion = observed
+        .potions
+        .get(slot)
+        .ok_or_else(|| format!("there's no potion slot {}", slot))?;
+      let potion_info = <&CardInfo>::from(CardId::from(&*observed_potion.id));
+      if !state.potions.iter().any(|info| info.id == potion_info.id) {
+        return Err(format!("{} isn't a simulated potion", observed_potion.id));
+      }
+      if observed_potion.requires_target {
+        let target = target.ok_or_else(|| format!("{} needs a target", observed_potion.id))?;
+        check_target(target, observed, state)?;
+      }
+      Ok(
+        UsePotion {
+          potion_info,
+          target: target.unwrap_or(0),
+        }
+        .into(),
+      )
+    }
+  }
+}
+
+/// The choice of playing the card with this `uuid`, like when reconstructing what the player did
+/// from the card that left their hand.
+pub fn choice_for_card_uuid(
+  uuid: &str,
+  target: Option<usize>,
+  observed: &communication::GameState,
+  state: &CombatState,
+) -> Result<Choice, String> {
+  let card_index = combat(observed)?
+    .hand
+    .iter()
+    .position(|card| card.uuid == uuid)
+    .ok_or_else(|| format!("there's no card with uuid {} in hand", uuid))?;
+  choice_for_command(&CombatCommand::Play { card_index, target }, observed, state)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn card(id: &str, has_target: bool) -> serde_json::Value {
+    json!({"name": id, "uuid": format!("{}-uuid", id), "cost": 1, "upgrades": 0, "id": id,
+      "type": "ATTACK", "rarity": "BASIC", "has_target": has_target, "exhausts": false})
+  }
+
+  fn monster(id: &str, half_dead: bool, is_gone: bool) -> serde_json::Value {
+    json!({"name": id, "id": id, "current_hp": 40, "max_hp": 40, "block": 0, "intent": "ATTACK",
+      "last_move_id": null, "second_last_move_id": null, "half_dead": half_dead,
+      "is_gone": is_gone, "powers": []})
+  }
+
+  fn potion(id: &str, requires_target: bool) -> serde_json::Value {
+    json!({"name": id, "id": id, "can_use": true, "can_discard": true,
+      "requires_target": requires_target})
+  }
+
+  /// Ten cards in hand, with duplicates; a live monster at 0 and 3, a half-dead one at 1 and a
+  /// dead one at 2; and an empty potion slot before two potions.
+  fn observed() -> communication::GameState {
+    let hand = vec![
+      card("Strike_R", true),
+      card("Bash", true),
+      card("Strike_R", true),
+      card("Defend_R", false),
+      card("Defend_R", false),
+      card("Anger", true),
+      card("Strike_R", true),
+      card("Defend_R", false),
+      card("Clothesline", true),
+      card("Flex", false),
+    ];
+    serde_json::from_value(json!({
+      "screen_name": "NONE", "is_screen_up": false, "screen_type": "NONE", "screen_state": {},
+      "room_phase": "COMBAT", "action_phase": "WAITING_ON_USER", "room_type": "MonsterRoom",
+      "current_hp": 70, "max_hp": 80, "floor": 3, "act": 1, "act_boss": "Hexaghost", "gold": 99,
+      "seed": 1, "class": "IRONCLAD", "ascension_level": 0, "relics": [], "deck": [],
+      "potions": [
+        potion("Potion Slot", false),
+        potion("Fire Potion", true),
+        potion("Block Potion", false),
+      ],
+      "map": null, "current_action": null, "choice_list": null,
+      "combat_state": {
+        "draw_pile": [], "discard_pile": [], "exhaust_pile": [], "hand": hand, "limbo": [],
+        "card_in_play": null, "cards_discarded_this_turn": 0, "turn": 1,
+        "player": {"current_hp": 70, "max_hp": 80, "block": 0, "powers": [], "energy": 3,
+          "orbs": []},
+        "monsters": [
+          monster("JawWorm", false, false),
+          monster("Darkling", true, false),
+          monster("JawWorm", false, true),
+          monster("Darkling", false, false),
+        ],
+      },
+    }))
+    .unwrap()
+  }
+
+  fn imported(observed: &communication::GameState) -> CombatState {
+    CombatState::from_communication_mod(observed, None, Default::default())
+      .unwrap()
+      .0
+  }
+
+  #[test]
+  fn parses_card_numbers_like_communication_mod() {
+    let play = |command: &str| match command.parse::<CombatCommand>() {
+      Ok(CombatCommand::Play { card_index, target }) => Ok((card_index, target)),
+      other => Err(other),
+    };
+    assert_eq!(play("play 1 0"), Ok((0, Some(0))));
+    assert_eq!(play("PLAY 9"), Ok((8, None)));
+    assert_eq!(play("play 10"), Ok((9, None)));
+    assert_eq!(play("play 0"), Ok((9, None)));
+    assert!("play 11".parse::<CombatCommand>().is_err());
+    assert!("play 20 1".parse::<CombatCommand>().is_err());
+    assert!("play x".parse::<CombatCommand>().is_err());
+  }
+
+  #[test]
+  fn commands_survive_display_and_parsing() {
+    for command in [
+      CombatCommand::Play {
+        card_index: 9,
+        target: Some(3),
+      },
+      CombatCommand::Play {
+        card_index: 0,
+        target: None,
+      },
+      CombatCommand::UsePotion {
+        slot: 1,
+        target: Some(0),
+      },
+      CombatCommand::End,
+    ] {
+      assert_eq!(command.to_string().parse::<CombatCommand>(), Ok(command));
+    }
+  }
+
+  #[test]
+  fn card_choices_round_trip() {
+    let observed = observed();
+    let state = imported(&observed);
+    let hand = &observed.combat_state.as_ref().unwrap().hand;
+    for (card_index, observed_card) in hand.iter().enumerate() {
+      let targets = if observed_card.has_target {
+        vec![Some(0), Some(3)]
+      } else {
+        vec![None]
+      };
+      for target in targets {
+        let command = CombatCommand::Play { card_index, target };
+        let choice = choice_for_command(&command, &observed, &state).unwrap();
+        let back = command_for_choice(&choice, &observed, &state).unwrap();
+        // copies of a card all come back as the first one
+        let first = hand.iter().position(|card| card.id == observed_card.id);
+        assert_eq!(
+          back,
+          CombatCommand::Play {
+            card_index: first.unwrap(),
+            target
+          }
+        );
+        assert_eq!(choice_for_command(&back, &observed, &state), Ok(choice));
+      }
+    }
+  }
+
+  #[test]
+  fn the_tenth_card_round_trips() {
+    let observed = observed();
+    let state = imported(&observed);
+    let command: CombatCommand = "play 0".parse().unwrap();
+    let choice = choice_for_command(&command, &observed, &state).unwrap();
+    assert_eq!(
+      command_for_choice(&choice, &observed, &state),
+      Ok(CombatCommand::Play {
+        card_index: 9,
+        target: None
+      })
+    );
+    assert_eq!(
+      command_for_choice(&choice, &observed, &state)
+        .unwrap()
+        .to_string(),
+      "play 10"
+    );
+  }
+
+  #[test]
+  fn gone_and_half_dead_monsters_cant_be_targeted() {
+    let observed = observed();
+    let state = imported(&observed);
+    for target in 1..=2 {
+      let command = CombatCommand::Play {
+        card_index: 0,
+        target: Some(target),
+      };
+      assert!(choice_for_command(&command, &observed, &state).is_err());
+      let choice = PlayCard {
+        card: SingleCard::create(CardId::StrikeR),
+        target,
+      }
+      .into();
+      assert!(command_for_choice(&choice, &observed, &state).is_err());
+    }
+    // past the end of the monster list
+    let command = CombatCommand::Play {
+      card_index: 0,
+      target: Some(4),
+    };
+    assert!(choice_for_command(&command, &observed, &state).is_err());
+  }
+
+  #[test]
+  fn potions_skip_empty_slots() {
+    let observed = observed();
+    let state = imported(&observed);
+    assert!(choice_for_command(
+      &CombatCommand::UsePotion {
+        slot: 0,
+        target: None
+      },
+      &observed,
+      &state
+    )
+    .is_err());
+    for &(slot, target) in &[(1, Some(3)), (2, None)] {
+      let command = CombatCommand::UsePotion { slot, target };
+      let choice = choice_for_command(&command, &observed, &state).unwrap();
+      assert_eq!(command_for_choice(&choice, &observed, &state), Ok(command));
+    }
+    // a potion the game doesn't have
+    let choice = UsePotion {
+      potion_info: <&CardInfo>::from(CardId::SwiftPotion),
+      target: 0,
+    }
+    .into();
+    assert!(command_for_choice(&choice, &observed, &state).is_err());
+  }
+
+  #[test]
+  fn mid_action_card_choices_have_no_command() {
+    use crate::actions::{SelectCard, SkipCardChoice};
+    use crate::simulation::CardChoiceType;
+    let observed = observed();
+    let state = imported(&observed);
+    let choices: [Choice; 2] = [
+      SelectCard {
+        card: SingleCard::create(CardId::StrikeR),
+        choice_type: CardChoiceType::DiscardCard,
+      }
+      .into(),
+      SkipCardChoice {
+        choice_type: CardChoiceType::ExhaustAnyCards,
+      }
+      .into(),
+    ];
+    for choice in &choices {
+      let error = command_for_choice(choice, &observed, &state).unwrap_err();
+      assert!(error.contains("middle of a card's effect"), "{}", error);
+    }
+  }
+}
@@ -1,9 +1,33 @@
-use std::io::BufRead;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::Deserialize;
+use std::fs::File;
+use std::io::{BufRead, BufWriter, Write};
+use std::panic::AssertUnwindSafe;
 use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
 
-pub fn communicate(state_file: PathBuf) {
+use crate::command_translation::command_for_choice;
+use crate::communication_mod_state::CommunicationState;
+use crate::competing_optimizers::CompetitorSpecification;
+use crate::differential_validation::{SessionEvent, SessionRecord};
+use crate::simulation::Choice;
+use crate::simulation_state::{CombatState, ImportFidelity, UnknownMonsterModel};
+use crate::start_and_strategy_ai::SearchState;
+
+/// What the autopilot writes to stderr when it has decided not to answer a message that was
+/// ready for a command, so that a stand-in for CommunicationMod doesn't have to wait and see.
+pub const AUTOPILOT_PASSING: &str = "Autopilot: passing";
+
+pub fn communicate(
+  state_file: PathBuf,
+  archive: Option<PathBuf>,
+  mut autopilot: Option<Autopilot>,
+) {
   println!("ready");
 
+  let mut recorder = archive.map(SessionRecorder::new);
+
   let input = std::io::stdin();
   let input = input.lock();
 
@@ -13,10 +37,329 @@ pub fn communicate(state_file: PathBuf) {
       if line.starts_with(r#"{"error""#) {
         eprintln!("Received error from communication mod: {}", line);
       }
-      let result = std::fs::write(&state_file, line);
+      let result = std::fs::write(&state_file, &line);
       if let Err(e) = result {
         eprintln!("Error writing state to file: {}", e);
       }
+      if let Some(recorder) = &mut recorder {
+        recorder.received(&line);
+      }
+      if let Some(command) = autopilot
+        .as_mut()
+        .and_then(|autopilot| autopilot.respond(&line))
+      {
+        println!("{}", command);
+        if let Some(recorder) = &mut recorder {
+          recorder.sent(&command);
+        }
+      }
+    }
+  }
+}
+
+/// Plays combats by itself: whenever CommunicationMod is waiting for a command during an ordinary
+/// player turn, it searches for the best choice for a while and sends it. Anything else – other
+/// screens, states we can't import well, choices we can't express as commands – it leaves to
+/// the player, by not sending anything (and saying `AUTOPILOT_PASSING` on stderr).
+pub struct Autopilot {
+  time_budget: Duration,
+  unknown_monster_model: UnknownMonsterModel,
+  /// Searches with this instead of `SearchState`, if set.
+  optimizer: Option<CompetitorSpecification>,
+  previous: Option<CombatState>,
+}
+
+impl Autopilot {
+  /// `time_budget` is how long to search before each command.
+  pub fn new(
+    time_budget: Duration,
+    unknown_monster_model: UnknownMonsterModel,
+    optimizer: Option<CompetitorSpecification>,
+  ) -> Autopilot {
+    Autopilot {
+      time_budget,
+      unknown_monster_model,
+      optimizer,
+      previous: None,
+    }
+  }
+
+  /// The command to send in response to a line from CommunicationMod, if any.
+  pub fn respond(&mut self, line: &str) -> Option<String> {
+    let message: CommunicationState = serde_json::from_str(line).ok()?;
+    if !message.ready_for_command {
+      return None;
+    }
+    let command = self.choose_command(message);
+    if command.is_none() {
+      eprintln!("{}", AUTOPILOT_PASSING);
+    }
+    command
+  }
+
+  fn choose_command(&mut self, message: CommunicationState) -> Option<String> {
+    let observed = message.game_state?;
+    let (state, report) = match CombatState::from_communication_mod(
+      &observed,
+      self.previous.as_ref(),
+      self.unknown_monster_model,
+    ) {
+      Some(imported) => imported,
+      None => {
+        self.previous = None;
+        return None;
+      }
+    };
+    self.previous = Some(state.clone());
+    if matches!(&*observed.screen_type, "HAND_SELECT" | "GRID") || state.pending_choice().is_some()
+    {
+      eprintln!(
+        "Autopilot: can't pick cards for a card's effect yet; leaving the {} screen to you",
+        observed.screen_type
+      );
+      return None;
+    }
+    if observed.screen_type != "NONE" || state.combat_over() {
+      return None;
+    }
+    let audit = state.fidelity_audit();
+    if report.fidelity_with_audit(&audit) == ImportFidelity::Low {
+      eprintln!(
+        "Autopilot: not playing from a state we can't simulate well:\n{}{}",
+        report, audit
+      );
+      return None;
+    }
+
+    let planned = std::panic::catch_unwind(AssertUnwindSafe(|| self.plan(&state)));
+    let choice = match planned {
+      Ok(Some(choice)) => choice,
+      Ok(None) => {
+        eprintln!("Autopilot: found nothing to do");
+        return None;
+      }
+      Err(_) => {
+        eprintln!("Autopilot: the simulation panicked; leaving this one to you");
+        return None;
+      }
+    };
+    match command_for_choice(&choice, &observed, &state) {
+      Ok(command)
+        if message
+          .available_commands
+          .iter()
+          .any(|available| available == command.name()) =>
+      {
+        Some(command.to_string())
+      }
+      Ok(command) => {
+        eprintln!(
+          "Autopilot: can't send {:?} now; available commands are {:?}",
+          command.to_string(),
+          message.available_commands
+        );
+        None
+      }
+      Err(error) => {
+        eprintln!("Autopilot: can't send {:?} as a command: {}", choice, error);
+        None
+      }
+    }
+  }
+
+  fn plan(&self, state: &CombatState) -> Option<Choice> {
+    let start = Instant::now();
+    match &self.optimizer {
+      None => {
+        let mut search = SearchState::new(state.clone());
+        loop {
+          search.search_step();
+          if start.elapsed() >= self.time_budget {
+            break;
+          }
+        }
+        search
+          .starting_points
+          .first()
+          .and_then(|start| start.choices.first().cloned())
+      }
+      Some(specification) => {
+        // `Competitor::step` reports on stdout, which is CommunicationMod's, so stay quiet
+        let mut rng = ChaCha8Rng::from_entropy();
+        let mut competitor = specification.clone().build(state, &mut rng);
+        loop {
+          competitor.step_quietly(state, &mut rng);
+          if start.elapsed() >= self.time_budget {
+            break;
+          }
+        }
+        competitor.choose_choice(state).first().cloned()
+      }
+    }
+  }
+}
+
+/// Just enough of a message to tell which run it's from.
+#[derive(Deserialize)]
+struct RunMessage {
+  game_state: Option<RunGameState>,
+}
+
+#[derive(Deserialize)]
+struct RunGameState {
+  class: String,
+  seed: i64,
+}
+
+/// Appends everything CommunicationMod sends, and everything we send it, to a file per run, in the
+/// form `differential_validation::read_session` reads. Runs are kept apart by character and seed,
+/// in `<archive>/<character>-<seed>/session.jsonl`, so a run that gets continued later goes on in
+/// the same file. Anything from before the first run goes in `<archive>/no-run/session.jsonl`.
+pub struct SessionRecorder {
+  archive: PathBuf,
+  /// The run we're recording, and its file.
+  current: Option<(RunKey, BufWriter<File>)>,
+}
+
+/// A run's (character, seed), or None for messages from before the first run.
+type RunKey = Option<(String, i64)>;
+
+impl SessionRecorder {
+  pub fn new(archive: PathBuf) -> SessionRecorder {
+    SessionRecorder {
+      archive,
+      current: None,
+    }
+  }
+
+  /// Records a line from CommunicationMod exactly as it was sent, so nothing is lost even if we
+  /// can't parse all of it. Messages from outside a run, like the main menu, go with the run
+  /// before them, if any.
+  pub fn received(&mut self, line: &str) {
+    let line = line.trim();
+    let message = match serde_json::from_str::<serde_json::Value>(line) {
+      Ok(message) if message.is_object() => message,
+      _ => {
+        // it can't be embedded in the record as a message, so keep it as a string
+        self.record(SessionEvent::Unrecognized(line.to_string()));
+        return;
+      }
+    };
+    if let Ok(RunMessage {
+      game_state: Some(game_state),
+    }) = RunMessage::deserialize(&message)
+    {
+      self.switch_run(Some((game_state.class, game_state.seed)));
+    }
+    let kind = if message.get("error").is_some() {
+      "error"
+    } else {
+      "received"
+    };
+    self.write(&format!(
+      "{{\"time\":{},\"{}\":{}}}",
+      milliseconds_now(),
+      kind,
+      line
+    ));
+  }
+
+  pub fn sent(&mut self, command: &str) {
+    self.record(SessionEvent::Sent(command.to_string()));
+  }
+
+  fn switch_run(&mut self, key: RunKey) {
+    if matches!(&self.current, Some((current, _)) if *current == key) {
+      return;
     }
+    self.current = None;
+    let directory = match &key {
+      Some((character, seed)) => self.archive.join(format!("{}-{}", character, seed)),
+      None => self.archive.join("no-run"),
+    };
+    let file = std::fs::create_dir_all(&directory).and_then(|()| {
+      std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(directory.join("session.jsonl"))
+    });
+    match file {
+      Ok(file) => self.current = Some((key, BufWriter::new(file))),
+      Err(e) => eprintln!("Error opening session archive: {}", e),
+    }
+  }
+
+  fn record(&mut self, event: SessionEvent) {
+    let record = SessionRecord {
+      time: milliseconds_now(),
+      event,
+    };
+    match serde_json::to_string(&record) {
+      Ok(record) => self.write(&record),
+      Err(e) => eprintln!("Error writing to session archive: {}", e),
+    }
+  }
+
+  fn write(&mut self, record: &str) {
+    if self.current.is_none() {
+      self.switch_run(None);
+    }
+    let (_, file) = match &mut self.current {
+      Some(current) => current,
+      None => return,
+    };
+    // flush every time, so the archive is complete even if the game kills us
+    let result = writeln!(file, "{}", record).and_then(|()| file.flush());
+    if let Err(e) = result {
+      eprintln!("Error writing to session archive: {}", e);
+    }
+  }
+}
+
+fn milliseconds_now() -> u64 {
+  SystemTime::now()
+    .duration_since(SystemTime::UNIX_EPOCH)
+    .map_or(0, |duration| duration.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn messages_are_archived_as_they_were_sent() {
+    let archive = std::env::temp_dir().join(format!("session-recorder-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&archive);
+    let mut recorder = SessionRecorder::new(archive.clone());
+    let menu = r#"{"ready_for_command":true,"in_game":false,"something_new":1}"#;
+    let in_run = r#"{"game_state":{"class":"IRONCLAD","seed":5,"unparsed":[1, 2]}}"#;
+    recorder.received(menu);
+    recorder.received(in_run);
+    recorder.sent("end");
+    recorder.received(r#"{"error":"Invalid command","ready_for_command":true}"#);
+    recorder.received("not json");
+    drop(recorder);
+
+    let read =
+      |run: &str| std::fs::read_to_string(archive.join(run).join("session.jsonl")).unwrap();
+    let before_the_run = read("no-run");
+    assert_eq!(before_the_run.lines().count(), 1);
+    assert!(before_the_run.contains(&format!(r#""received":{}}}"#, menu)));
+
+    let run = read("IRONCLAD-5");
+    let lines: Vec<&str> = run.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].ends_with(&format!(r#""received":{}}}"#, in_run)));
+    let events: Vec<SessionEvent> = lines[1..]
+      .iter()
+      .map(|line| serde_json::from_str::<SessionRecord>(line).unwrap().event)
+      .collect();
+    assert!(matches!(&events[0], SessionEvent::Sent(command) if command == "end"));
+    assert!(
+      matches!(&events[1], SessionEvent::Error(message) if message["error"] == "Invalid command")
+    );
+    assert!(matches!(&events[2], SessionEvent::Unrecognized(line) if line == "not json"));
+
+    std::fs::remove_dir_all(&archive).unwrap();
   }
 }
@@ -0,0 +1,143 @@
+use crate::combat_generation::CombatSpecification;
+use crate::encounters::{self, FightRoom};
+use crate::simulation_state::{CardId, Character, Relic, RelicId, SingleCard};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::path::Path;
+
+/// Builds a `CombatSpecification` from command-line arguments.
+pub fn parse_specification(
+  character: &str,
+  deck: &str,
+  relics: &str,
+  hitpoints: &str,
+  ascension: i32,
+  encounter: &str,
+) -> Result<CombatSpecification, String> {
+  let character = Character::from_communication_mod(character)
+    .ok_or_else(|| format!("unknown character {:?}", character))?;
+  let (hitpoints, max_hitpoints) = parse_hitpoints(hitpoints)?;
+  Ok(CombatSpecification {
+    character,
+    deck: parse_deck(deck)?,
+    relics: parse_relics(relics)?,
+    potions: Vec::new(),
+    hitpoints,
+    max_hitpoints,
+    ascension,
+    encounter: encounter.to_string(),
+  })
+}
+
+/// For sampling the encounter, instead of naming it.
+pub struct FloorFight {
+  pub floor: i32,
+  pub room: FightRoom,
+  pub fights_this_act: i32,
+}
+
+/// Generates a fresh combat, prints it, and optionally saves it as JSON, in the same form as the
+/// saved states in `data/`.
+pub fn run(
+  specification: &CombatSpecification,
+  floor_fight: Option<FloorFight>,
+  seed: Option<u64>,
+  output: Option<&Path>,
+) -> Result<(), String> {
+  let mut rng = match seed {
+    Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+    None => ChaCha8Rng::from_entropy(),
+  };
+  let mut specification = specification.clone();
+  if let Some(FloorFight {
+    floor,
+    room,
+    fights_this_act,
+  }) = floor_fight
+  {
+    let encounter = encounters::sample_for_floor(floor, room, fights_this_act, &mut rng)
+      .ok_or_else(|| format!("no {:?} fights on floor {}", room, floor))?;
+    println!("Encounter: {}", encounter.name);
+    specification.encounter = encounter.name.to_string();
+  }
+  let state = specification.generate(&mut rng)?;
+  println!("{}", state);
+  if let Some(output) = output {
+    let file = std::fs::File::create(output).map_err(|error| error.to_string())?;
+    serde_json::to_writer_pretty(std::io::BufWriter::new(file), &state)
+      .map_err(|error| error.to_string())?;
+  }
+  Ok(())
+}
+
+pub fn parse_room(room: &str) -> Result<FightRoom, String> {
+  match room {
+    "monster" => Ok(FightRoom::Monster),
+    "elite" => Ok(FightRoom::Elite),
+    "boss" => Ok(FightRoom::Boss),
+    _ => Err(format!("unknown room {:?}", room)),
+  }
+}
+
+/// Either "current/max" or just the max.
+fn parse_hitpoints(hitpoints: &str) -> Result<(i32, i32), String> {
+  let parse = |number: &str| {
+    number
+      .trim()
+      .parse::<i32>()
+      .map_err(|_| format!("invalid hitpoints {:?}", hitpoints))
+  };
+  match hitpoints.split_once('/') {
+    Some((current, max)) => Ok((parse(current)?, parse(max)?)),
+    None => {
+      let max = parse(hitpoints)?;
+      Ok((max, max))
+    }
+  }
+}
+
+/// A comma-separated list of CommunicationMod card IDs, with `+` for upgraded cards and `*N` for
+/// N copies, like "Strike_R*5,Defend_R*4,Bash+".
+pub fn parse_deck(deck: &str) -> Result<Vec<SingleCard>, String> {
+  let mut result = Vec::new();
+  for entry in deck
+    .split(',')
+    .map(str::trim)
+    .filter(|entry| !entry.is_empty())
+  {
+    let (name, copies) = match entry.rsplit_once('*') {
+      Some((name, copies)) => (
+        name,
+        copies
+          .parse::<usize>()
+          .map_err(|_| format!("invalid number of copies in {:?}", entry))?,
+      ),
+      None => (entry, 1),
+    };
+    let (name, upgraded) = match name.strip_suffix('+') {
+      Some(name) => (name, true),
+      None => (name, false),
+    };
+    let id =
+      CardId::from_communication_mod(name).ok_or_else(|| format!("unknown card {:?}", name))?;
+    let mut card = SingleCard::create(id);
+    if upgraded {
+      card.upgrade();
+    }
+    result.extend(std::iter::repeat(card).take(copies));
+  }
+  Ok(result)
+}
+
+/// A comma-separated list of CommunicationMod relic IDs.
+pub fn parse_relics(relics: &str) -> Result<Vec<Relic>, String> {
+  relics
+    .split(',')
+    .map(str::trim)
+    .filter(|name| !name.is_empty())
+    .map(|name| match RelicId::from(name) {
+      RelicId::Unknown => Err(format!("unknown or unimplemented relic {:?}", name)),
+      relic_id => Ok(Relic::new(relic_id)),
+    })
+    .collect()
+}
@@ -0,0 +1,139 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Sender};
+use std::time::Duration;
+
+use crate::commands::communicate::AUTOPILOT_PASSING;
+use crate::differential_validation::{read_session, SessionEvent};
+
+/// How the command answered a message.
+#[derive(PartialEq, Eq, Debug)]
+enum Response {
+  Command(String),
+  /// It said `AUTOPILOT_PASSING` on stderr.
+  Pass,
+}
+
+fn forward_lines(
+  stream: impl Read + Send + 'static,
+  sender: Sender<Response>,
+  interpret: fn(String) -> Option<Response>,
+) {
+  std::thread::spawn(move || {
+    for line in BufReader::new(stream).lines() {
+      let line = match line {
+        Ok(line) => line,
+        Err(_) => break,
+      };
+      if let Some(response) = interpret(line) {
+        if sender.send(response).is_err() {
+          break;
+        }
+      }
+    }
+  });
+}
+
+/// Stands in for CommunicationMod, for testing `communicate --autopilot` without the game: runs
+/// `command` as CommunicationMod would, feeds it the messages from a recorded session in order,
+/// and prints what it sends back next to what was sent in the recording.
+///
+/// Each message that's ready for a command gets exactly one answer: a command on stdout, or
+/// `AUTOPILOT_PASSING` on stderr. Other stderr output is passed through. When an answer takes
+/// longer than `timeout`, we move on, and skip that answer when it does arrive, so it isn't
+/// mistaken for the answer to a later message.
+///
+/// The game would respond to the commands; this just moves on to the next recorded message, so
+/// once the two disagree, the rest of the comparison only shows what it would have done instead.
+pub fn run(session: &Path, command: &[&str], timeout: Duration) -> Result<(), String> {
+  let events = read_session(session)?;
+  let (program, args) = command.split_first().ok_or("no command to run")?;
+  let mut child = Command::new(program)
+    .args(args)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .map_err(|error| format!("couldn't run {:?}: {}", program, error))?;
+  let mut input = child.stdin.take().unwrap();
+  let (sender, receiver) = mpsc::channel();
+  forward_lines(child.stdout.take().unwrap(), sender.clone(), |line| {
+    Some(Response::Command(line))
+  });
+  forward_lines(child.stderr.take().unwrap(), sender, |line| {
+    if line.trim() == AUTOPILOT_PASSING {
+      Some(Response::Pass)
+    } else {
+      eprintln!("{}", line);
+      None
+    }
+  });
+
+  match receiver.recv_timeout(timeout) {
+    Ok(Response::Command(line)) if line.trim() == "ready" => {}
+    Ok(response) => return Err(format!("expected \"ready\", got {:?}", response)),
+    Err(_) => return Err("never got \"ready\"".to_string()),
+  }
+
+  let (mut sent, mut matching, mut timed_out) = (0, 0, 0);
+  // answers to earlier messages that we stopped waiting for
+  let mut overdue = 0;
+  for (index, (line, event)) in events.iter().enumerate() {
+    let message = match event {
+      SessionEvent::Received(message) => message,
+      _ => continue,
+    };
+    let serialized = serde_json::to_string(message).map_err(|error| error.to_string())?;
+    writeln!(input, "{}", serialized).map_err(|error| format!("child exited: {}", error))?;
+    if !message.ready_for_command {
+      continue;
+    }
+    let recorded = events[index + 1..]
+      .iter()
+      .map(|(_, event)| event)
+      .take_while(|event| !matches!(event, SessionEvent::Received(_)))
+      .find_map(|event| match event {
+        SessionEvent::Sent(command) => Some(command.as_str()),
+        _ => None,
+      });
+    let response = loop {
+      match receiver.recv_timeout(timeout) {
+        Ok(_) if overdue > 0 => overdue -= 1,
+        Ok(response) => break Some(response),
+        Err(_) => {
+          overdue += 1;
+          break None;
+        }
+      }
+    };
+    let response = match response {
+      Some(Response::Command(command)) => {
+        sent += 1;
+        if Some(command.as_str()) == recorded {
+          matching += 1;
+        }
+        format!("sent {:?}", command)
+      }
+      Some(Response::Pass) => "passed".to_string(),
+      None => {
+        timed_out += 1;
+        "timed out".to_string()
+      }
+    };
+    println!(
+      "{}:{}: {}, recorded {:?}",
+      session.display(),
+      line,
+      response,
+      recorded
+    );
+  }
+  drop(input);
+  let _ = child.wait();
+  println!(
+    "Sent {} commands, {} the same as the recording; timed out {} times.",
+    sent, matching, timed_out
+  );
+  Ok(())
+}
@@ -0,0 +1,12 @@
+use crate::differential_validation::{read_session, validate_sessions};
+use std::path::PathBuf;
+
+/// Replays the given recorded sessions and prints where the simulation disagreed with the game.
+pub fn run(sessions: &[PathBuf]) -> Result<(), String> {
+  let sessions = sessions
+    .iter()
+    .map(|path| Ok((path.display().to_string(), read_session(path)?)))
+    .collect::<Result<Vec<_>, String>>()?;
+  print!("{}", validate_sessions(&sessions));
+  Ok(())
+}
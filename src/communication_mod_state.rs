@@ -76,7 +76,19 @@ pub struct Player {
   pub block: i32,
   pub powers: Vec<Power>,
   pub energy: i32,
-  pub orbs: Vec<Value>,
+  pub orbs: Vec<Orb>,
+  #[serde(default)]
+  pub stance: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Orb {
+  pub name: String,
+  pub id: String,
+  #[serde(default)]
+  pub evoke_amount: i32,
+  #[serde(default)]
+  pub passive_amount: i32,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
@@ -28,6 +28,8 @@ use std::sync::Arc;
 pub trait StrategyOptimizer: 'static {
   type Strategy: Strategy;
   fn step(&mut self, state: &CombatState, rng: &mut ChaCha8Rng);
+  /// The best strategy so far, like `report`, but without printing anything.
+  fn current_strategy(&self) -> Arc<Self::Strategy>;
   fn report(&self) -> Arc<Self::Strategy>;
   fn print_extra_info(&self, state: &CombatState) {}
 }
@@ -170,6 +172,10 @@ impl<T: Strategy + 'static> StrategyOptimizer for OriginalExplorationOptimizer<T
     }
   }
 
+  fn current_strategy(&self) -> Arc<Self::Strategy> {
+    self.best_strategy().strategy.clone()
+  }
+
   fn report(&self) -> Arc<Self::Strategy> {
     let best = self.best_strategy();
 
@@ -264,6 +270,15 @@ impl<T: Strategy + 'static> StrategyOptimizer for IndependentSeedsExplorationOpt
     }
   }
 
+  fn current_strategy(&self) -> Arc<Self::Strategy> {
+    self
+      .candidate_strategies
+      .last_key_value()
+      .unwrap()
+      .1
+      .clone()
+  }
+
   fn report(&self) -> Arc<Self::Strategy> {
     let (average, best) = self.candidate_strategies.last_key_value().unwrap();
 
@@ -282,9 +297,13 @@ impl StrategyOptimizer for NeuralStrategy {
     self.do_training_playout(state);
   }
 
-  fn report(&self) -> Arc<Self::Strategy> {
+  fn current_strategy(&self) -> Arc<Self::Strategy> {
     Arc::new(self.clone())
   }
+
+  fn report(&self) -> Arc<Self::Strategy> {
+    self.current_strategy()
+  }
 }
 
 pub fn optimizer_step(
@@ -420,6 +439,10 @@ pub fn run_benchmark (name: & str, state: & CombatState, optimization_playouts:
 
 pub trait Competitor {
   fn step(&mut self, state: &CombatState, rng: &mut ChaCha8Rng, last: bool);
+  /// Optimizes a little, without printing anything, unlike `step`.
+  fn step_quietly(&mut self, state: &CombatState, rng: &mut ChaCha8Rng);
+  /// What the best strategy so far would do in `state`.
+  fn choose_choice(&self, state: &CombatState) -> Vec<Choice>;
 }
 struct OptimizerCompetitor<T> {
   name: String,
@@ -429,9 +452,15 @@ impl<T: StrategyOptimizer> Competitor for OptimizerCompetitor<T> {
   fn step(&mut self, state: &CombatState, rng: &mut ChaCha8Rng, last: bool) {
     optimizer_step(&self.name, state, rng, &mut self.optimizer, last);
   }
+  fn step_quietly(&mut self, state: &CombatState, rng: &mut ChaCha8Rng) {
+    self.optimizer.step(state, rng);
+  }
+  fn choose_choice(&self, state: &CombatState) -> Vec<Choice> {
+    self.optimizer.current_strategy().choose_choice(state)
+  }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug)]
 pub enum CompetitorSpecification {
   ExplorationOptimizer(
     ExplorationOptimizerKindSpecification,
@@ -589,6 +618,10 @@ pub fn run(competitors: impl IntoIterator<Item = CompetitorSpecification>) {
   let ghost_file = std::fs::File::open("data/hexaghost.json").unwrap();
   let ghost_state: CombatState =
     serde_json::from_reader(std::io::BufReader::new(ghost_file)).unwrap();
+  let audit = ghost_state.fidelity_audit();
+  if !audit.is_complete() {
+    println!("Scores may be off, because of these:\n{}", audit);
+  }
   let mut rng = ChaCha8Rng::from_entropy();
   let mut competitors: Vec<_> = competitors
     .into_iter()
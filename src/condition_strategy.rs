@@ -177,6 +177,9 @@ impl EvaluationData {
               cost: 0,
               upgrades: 0,
               card_info: potion_info,
+              retain: false,
+              free_this_turn: false,
+              free_to_play_once: false,
             },
             target,
           ),
@@ -322,7 +325,7 @@ impl Condition {
     for card_id in state
       .hand
       .iter()
-      .chain(&state.draw_pile)
+      .chain(state.draw_pile_cards())
       .chain(&state.discard_pile)
       .map(|c| c.card_info.id)
       .collect::<HashSet<_>>()
@@ -478,7 +481,7 @@ impl ConditionStrategy {
     for card_id in state
       .hand
       .iter()
-      .chain(&state.draw_pile)
+      .chain(state.draw_pile_cards())
       .chain(&state.discard_pile)
       .map(|c| c.card_info.id)
       .collect::<HashSet<_>>()
@@ -296,6 +296,10 @@ impl StrategyOptimizer for StrategyGeneratorsWithSharedRepresenativeSeeds {
     self.step(rng);
   }
 
+  fn current_strategy(&self) -> Arc<Self::Strategy> {
+    self.seed_search.best_strategy()
+  }
+
   fn report(&self) -> Arc<Self::Strategy> {
     let result = self.seed_search.best_strategy();
     self.seed_search.report();
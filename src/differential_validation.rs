@@ -0,0 +1,730 @@
+//! Replays recorded CommunicationMod sessions through the simulation, and reports everywhere its
+//! prediction of the next state differs from what the game actually did.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+use std::io::BufRead;
+use std::path::Path;
+
+use crate::actions::{ChooseMonsterIntent, DynAction};
+use crate::command_translation::{choice_for_command, CombatCommand};
+use crate::communication_mod_state as communication;
+use crate::seed_system::SeedView;
+use crate::simulation::{Action, Determinism, Runner, StandardRunner};
+use crate::simulation_state::fidelity::AuditedEntity;
+use crate::simulation_state::*;
+
+/// One line of a recorded session.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionEvent {
+  /// A message from CommunicationMod.
+  Received(Box<communication::CommunicationState>),
+  /// A command we sent to CommunicationMod.
+  Sent(String),
+  /// CommunicationMod rejected the last command. This is its whole message, like
+  /// `{"error": "Invalid command: ..."}`.
+  Error(serde_json::Value),
+  /// A line from CommunicationMod that wasn't JSON at all.
+  Unrecognized(String),
+}
+
+/// A `SessionEvent`, with when it happened, in milliseconds since the Unix epoch. This is what
+/// `communicate` archives, one per line.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SessionRecord {
+  #[serde(default)]
+  pub time: u64,
+  #[serde(flatten)]
+  pub event: SessionEvent,
+}
+
+/// A command, with the game state it was sent in and the one the game responded with.
+#[derive(Clone, Debug)]
+pub struct RecordedTransition {
+  /// Where the response was recorded, like `session.jsonl:12`.
+  pub location: String,
+  pub before: communication::GameState,
+  pub command: String,
+  pub after: communication::GameState,
+}
+
+/// Reads a session file, one `SessionRecord` per line.
+pub fn read_session(path: &Path) -> Result<Vec<(usize, SessionEvent)>, String> {
+  let file = std::fs::File::open(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+  let mut result = Vec::new();
+  for (index, line) in std::io::BufReader::new(file).lines().enumerate() {
+    let line = line.map_err(|error| format!("{}: {}", path.display(), error))?;
+    if line.trim().is_empty() {
+      continue;
+    }
+    let record: SessionRecord = serde_json::from_str(&line)
+      .map_err(|error| format!("{}:{}: {}", path.display(), index + 1, error))?;
+    result.push((index + 1, record.event));
+  }
+  Ok(result)
+}
+
+/// Pairs each command with the states before and after it. Commands sent without a game state to
+/// compare against, or with no response before the next command, are dropped.
+pub fn transitions(name: &str, events: &[(usize, SessionEvent)]) -> Vec<RecordedTransition> {
+  let mut result = Vec::new();
+  let mut before: Option<&communication::GameState> = None;
+  let mut command: Option<&str> = None;
+  for (line, event) in events {
+    match event {
+      SessionEvent::Sent(sent) => {
+        if command.is_some() {
+          // we never saw the state in between
+          before = None;
+        }
+        command = Some(sent);
+      }
+      SessionEvent::Received(message) => {
+        if let (Some(before), Some(command), Some(after)) = (before, command, &message.game_state) {
+          result.push(RecordedTransition {
+            location: format!("{}:{}", name, line),
+            before: before.clone(),
+            command: command.to_string(),
+            after: after.clone(),
+          });
+        }
+        before = message.game_state.as_ref();
+        command = None;
+      }
+      // the state didn't change
+      SessionEvent::Error(_) => command = None,
+      SessionEvent::Unrecognized(_) => {}
+    }
+  }
+  result
+}
+
+/// Makes random choices agree with an observed outcome where it can tell how: drawing the cards
+/// that showed up in hand, and picking the intents the monsters showed. Everything else gets its
+/// most likely outcome.
+#[derive(Clone, Debug)]
+pub struct ObservedOutcomes<'a> {
+  observed: Option<&'a CombatState>,
+  /// Random choices with more than one option that we couldn't match to the observation.
+  pub unconstrained: usize,
+  /// The value picked for the action being executed, by `num_actions`.
+  decided: Option<(i32, i32)>,
+}
+
+impl<'a> ObservedOutcomes<'a> {
+  pub fn new(observed: Option<&'a CombatState>) -> Self {
+    ObservedOutcomes {
+      observed,
+      unconstrained: 0,
+      decided: None,
+    }
+  }
+
+  fn pick(&mut self, state: &CombatState, action: &DynAction) -> Option<i32> {
+    let distribution = match action.determinism(state) {
+      Determinism::Random(distribution) => distribution,
+      _ => return None,
+    };
+    if distribution.0.len() == 1 {
+      return Some(distribution.0[0].1);
+    }
+    let matched = self.observed.and_then(|observed| match action {
+      DynAction::DrawCardRandom(_) => {
+        let key = |card: &SingleCard| (card.card_info.id, card.upgrades);
+        let wanted = |card: &SingleCard| {
+          let count = |hand: &[SingleCard]| hand.iter().filter(|c| key(c) == key(card)).count();
+          count(&observed.hand) > count(&state.hand)
+        };
+        state
+          .draw_pile
+          .iter()
+          .position(wanted)
+          .map(|index| index as i32)
+      }
+      &DynAction::ChooseMonsterIntent(ChooseMonsterIntent(monster_index)) => {
+        let intent = *observed.monsters.get(monster_index)?.move_history.last()?;
+        Some(intent).filter(|&intent| distribution.0.iter().any(|&(_, value)| value == intent))
+      }
+      _ => None,
+    });
+    if matched.is_none() {
+      self.unconstrained += 1;
+    }
+    matched.or_else(|| {
+      distribution
+        .0
+        .iter()
+        .fold(
+          None,
+          |best: Option<(f64, i32)>, &(weight, value)| match best {
+            Some((best_weight, _)) if best_weight >= weight => best,
+            _ => Some((weight, value)),
+          },
+        )
+        .map(|(_, value)| value)
+    })
+  }
+}
+
+impl<'a> SeedView<CombatState> for ObservedOutcomes<'a> {
+  fn gen(&mut self, state: &CombatState, action: &DynAction, &choice: &i32) -> f64 {
+    if self.decided.map(|(num_actions, _)| num_actions) != Some(state.num_actions) {
+      let picked = self.pick(state, action).unwrap_or(choice);
+      self.decided = Some((state.num_actions, picked));
+    }
+    if self.decided.map(|(_, picked)| picked) == Some(choice) {
+      0.0
+    } else {
+      1.0
+    }
+  }
+}
+
+/// A field where the simulation predicted something different from what the game did.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Mismatch {
+  pub field: String,
+  /// The power or monster the field belongs to, if any.
+  pub subject: Option<AuditedEntity>,
+  pub predicted: String,
+  pub observed: String,
+}
+
+fn cards_text<'a>(cards: impl Iterator<Item = &'a SingleCard>) -> String {
+  let mut cards: Vec<String> = cards.map(ToString::to_string).collect();
+  cards.sort();
+  cards.join(" ")
+}
+
+struct Comparison {
+  mismatches: Vec<Mismatch>,
+}
+
+impl Comparison {
+  fn field(
+    &mut self,
+    field: impl Into<String>,
+    subject: Option<AuditedEntity>,
+    predicted: impl ToString,
+    observed: impl ToString,
+  ) {
+    let (predicted, observed) = (predicted.to_string(), observed.to_string());
+    if predicted != observed {
+      self.mismatches.push(Mismatch {
+        field: field.into(),
+        subject,
+        predicted,
+        observed,
+      });
+    }
+  }
+  fn creature(&mut self, name: &str, predicted: &Creature, observed: &Creature) {
+    self.field(
+      format!("{} hitpoints", name),
+      None,
+      predicted.hitpoints,
+      observed.hitpoints,
+    );
+    self.field(
+      format!("{} max hitpoints", name),
+      None,
+      predicted.max_hitpoints,
+      observed.max_hitpoints,
+    );
+    self.field(
+      format!("{} block", name),
+      None,
+      predicted.block,
+      observed.block,
+    );
+    let mut power_ids: Vec<PowerId> = predicted
+      .powers
+      .iter()
+      .chain(&observed.powers)
+      .map(|power| power.power_id)
+      .collect();
+    power_ids.sort();
+    power_ids.dedup();
+    let amount = |creature: &Creature, power_id: PowerId| {
+      creature
+        .powers
+        .iter()
+        .find(|power| power.power_id == power_id)
+        .map_or_else(|| "none".to_string(), |power| power.amount.to_string())
+    };
+    for power_id in power_ids {
+      self.field(
+        format!("{} {:?}", name, power_id),
+        Some(AuditedEntity::Power(power_id)),
+        amount(predicted, power_id),
+        amount(observed, power_id),
+      );
+    }
+  }
+}
+
+/// Compares everything CommunicationMod tells us about a combat.
+pub fn compare_states(predicted: &CombatState, observed: &CombatState) -> Vec<Mismatch> {
+  let mut comparison = Comparison {
+    mismatches: Vec::new(),
+  };
+  comparison.creature(
+    "player",
+    &predicted.player.creature,
+    &observed.player.creature,
+  );
+  comparison.field(
+    "player energy",
+    None,
+    predicted.player.energy,
+    observed.player.energy,
+  );
+  comparison.field(
+    "player stance",
+    None,
+    format!("{:?}", predicted.player.stance),
+    format!("{:?}", observed.player.stance),
+  );
+  let orbs = |state: &CombatState| {
+    let orbs: Vec<String> = state
+      .player
+      .orbs
+      .iter()
+      .map(|orb| format!("{:?}", orb.orb_type))
+      .collect();
+    orbs.join(" ")
+  };
+  comparison.field("player orbs", None, orbs(predicted), orbs(observed));
+  comparison.field(
+    "hand",
+    None,
+    cards_text(predicted.hand.iter()),
+    cards_text(observed.hand.iter()),
+  );
+  comparison.field(
+    "draw pile",
+    None,
+    cards_text(predicted.draw_pile_cards()),
+    cards_text(observed.draw_pile_cards()),
+  );
+  comparison.field(
+    "discard pile",
+    None,
+    cards_text(predicted.discard_pile.iter()),
+    cards_text(observed.discard_pile.iter()),
+  );
+  comparison.field(
+    "exhaust pile",
+    None,
+    cards_text(predicted.exhaust_pile.iter()),
+    cards_text(observed.exhaust_pile.iter()),
+  );
+  let potions = |state: &CombatState| {
+    let potions: Vec<String> = state
+      .potions
+      .iter()
+      .map(|potion| format!("{:?}", potion.id))
+      .collect();
+    potions.join(" ")
+  };
+  comparison.field("potions", None, potions(predicted), potions(observed));
+  comparison.field(
+    "number of monsters",
+    None,
+    predicted.monsters.len(),
+    observed.monsters.len(),
+  );
+  for (index, (predicted, observed)) in predicted
+    .monsters
+    .iter()
+    .zip(&observed.monsters)
+    .enumerate()
+  {
+    let name = format!("monster {} ({:?})", index, observed.monster_id);
+    let subject = Some(AuditedEntity::Monster(observed.monster_id));
+    comparison.field(
+      format!("{} gone", name),
+      subject,
+      predicted.gone,
+      observed.gone,
+    );
+    if observed.gone && !observed.half_dead {
+      continue;
+    }
+    let before = comparison.mismatches.len();
+    comparison.creature(&name, &predicted.creature, &observed.creature);
+    // powers are their own subject, but the rest belongs to the monster
+    for mismatch in &mut comparison.mismatches[before..] {
+      mismatch.subject = mismatch.subject.or(subject);
+    }
+    let intent = |monster: &Monster| {
+      monster
+        .move_history
+        .last()
+        .map_or_else(|| "none".to_string(), ToString::to_string)
+    };
+    comparison.field(
+      format!("{} intent", name),
+      subject,
+      intent(predicted),
+      intent(observed),
+    );
+  }
+  comparison.mismatches
+}
+
+/// What happened when replaying one transition.
+#[derive(Clone, Debug)]
+pub enum TransitionOutcome {
+  Skipped(&'static str),
+  Checked {
+    /// The card or potion the command played, if any.
+    played: Option<CardId>,
+    mismatches: Vec<Mismatch>,
+    unconstrained: usize,
+  },
+}
+
+/// Replays `transition.command` from `transition.before`. `previous` should be the last state
+/// imported from the same combat, if any, to carry over what CommunicationMod doesn't report.
+pub fn check_transition(
+  transition: &RecordedTransition,
+  previous: Option<&CombatState>,
+) -> TransitionOutcome {
+  if transition.before.screen_type != "NONE" {
+    return TransitionOutcome::Skipped("not at an ordinary point in a player turn");
+  }
+  let before =
+    match CombatState::from_communication_mod(&transition.before, previous, Default::default()) {
+      Some((state, _report)) => state,
+      None => return TransitionOutcome::Skipped("not in combat"),
+    };
+  let command: CombatCommand = match transition.command.parse() {
+    Ok(command) => command,
+    Err(_) => return TransitionOutcome::Skipped("command isn't one we replay"),
+  };
+  let choice = match choice_for_command(&command, &transition.before, &before) {
+    Ok(choice) => choice,
+    Err(_) => return TransitionOutcome::Skipped("command doesn't fit the state it was sent in"),
+  };
+  let played = match &choice {
+    DynAction::PlayCard(play) => Some(play.card.card_info.id),
+    DynAction::UsePotion(potion) => Some(potion.potion_info.id),
+    _ => None,
+  };
+  let combat_continues = transition.after.room_phase == "COMBAT";
+  let observed = if combat_continues {
+    CombatState::from_communication_mod(&transition.after, Some(&before), Default::default())
+      .map(|(state, _)| state)
+  } else {
+    None
+  };
+
+  let mut predicted = before.clone();
+  let mut runner = StandardRunner::new(&mut predicted, ObservedOutcomes::new(observed.as_ref()));
+  runner.apply_choice(&choice);
+  let unconstrained = runner.seed_view.unconstrained;
+
+  let mut mismatches = Vec::new();
+  match observed {
+    None => {
+      if !predicted.combat_over() {
+        mismatches.push(Mismatch {
+          field: "combat over".to_string(),
+          subject: None,
+          predicted: "false".to_string(),
+          observed: "true".to_string(),
+        });
+      }
+    }
+    Some(observed) => {
+      if transition.after.screen_type != "NONE" || predicted.pending_choice().is_some() {
+        return TransitionOutcome::Skipped("leads to a selection screen");
+      }
+      if predicted.combat_over() {
+        mismatches.push(Mismatch {
+          field: "combat over".to_string(),
+          subject: None,
+          predicted: "true".to_string(),
+          observed: "false".to_string(),
+        });
+      } else {
+        mismatches = compare_states(&predicted, &observed);
+      }
+    }
+  }
+  TransitionOutcome::Checked {
+    played,
+    mismatches,
+    unconstrained,
+  }
+}
+
+#[derive(Clone, Debug)]
+pub struct MismatchExample {
+  pub location: String,
+  pub command: String,
+  pub mismatch: Mismatch,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MismatchGroup {
+  pub count: usize,
+  /// The first few, with at most one per field.
+  pub examples: Vec<MismatchExample>,
+}
+
+/// Mismatches from many transitions, grouped by the card, power or monster they're about.
+#[derive(Clone, Debug, Default)]
+pub struct ValidationReport {
+  pub checked: usize,
+  pub matching: usize,
+  pub skipped: BTreeMap<&'static str, usize>,
+  /// Random outcomes we couldn't tie to the observed state, which may explain some mismatches.
+  pub unconstrained: usize,
+  /// `None` holds mismatches after ending the turn that aren't about a specific power or monster.
+  pub groups: BTreeMap<Option<AuditedEntity>, MismatchGroup>,
+}
+
+impl ValidationReport {
+  const EXAMPLES_PER_GROUP: usize = 3;
+
+  pub fn add(&mut self, transition: &RecordedTransition, outcome: TransitionOutcome) {
+    let (played, mismatches, unconstrained) = match outcome {
+      TransitionOutcome::Skipped(reason) => {
+        *self.skipped.entry(reason).or_insert(0) += 1;
+        return;
+      }
+      TransitionOutcome::Checked {
+        played,
+        mismatches,
+        unconstrained,
+      } => (played, mismatches, unconstrained),
+    };
+    self.checked += 1;
+    self.unconstrained += unconstrained;
+    if mismatches.is_empty() {
+      self.matching += 1;
+    }
+    for mismatch in mismatches {
+      // Count each mismatch once, under the thing it's about if we know it, else the played card.
+      let key = mismatch.subject.or_else(|| played.map(AuditedEntity::Card));
+      let group = self.groups.entry(key).or_default();
+      group.count += 1;
+      if group.examples.len() < Self::EXAMPLES_PER_GROUP
+        && !group
+          .examples
+          .iter()
+          .any(|example| example.mismatch.field == mismatch.field)
+      {
+        group.examples.push(MismatchExample {
+          location: transition.location.clone(),
+          command: transition.command.clone(),
+          mismatch,
+        });
+      }
+    }
+  }
+}
+
+/// Checks every transition in the given sessions, in order.
+pub fn validate_sessions(sessions: &[(String, Vec<(usize, SessionEvent)>)]) -> ValidationReport {
+  let mut report = ValidationReport::default();
+  for (name, events) in sessions {
+    let mut previous: Option<CombatState> = None;
+    for transition in transitions(name, events) {
+      let outcome = check_transition(&transition, previous.as_ref());
+      report.add(&transition, outcome);
+      previous = CombatState::from_communication_mod(
+        &transition.after,
+        previous.as_ref(),
+        Default::default(),
+      )
+      .map(|(state, _)| state);
+    }
+  }
+  report
+}
+
+impl Display for Mismatch {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}: predicted {:?}, observed {:?}",
+      self.field, self.predicted, self.observed
+    )?;
+    if self.predicted.contains(' ') || self.observed.contains(' ') {
+      let diff = difference::Changeset::new(&self.predicted, &self.observed, " ");
+      write!(f, "\n      {}", diff)?;
+    }
+    Ok(())
+  }
+}
+
+impl Display for ValidationReport {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    writeln!(
+      f,
+      "Checked {} transitions; {} matched exactly.",
+      self.checked, self.matching
+    )?;
+    for (reason, count) in &self.skipped {
+      writeln!(f, "Skipped {}: {}.", count, reason)?;
+    }
+    if self.unconstrained > 0 {
+      writeln!(
+        f,
+        "{} random outcomes couldn't be matched to what the game did.",
+        self.unconstrained
+      )?;
+    }
+    let mut groups: Vec<_> = self.groups.iter().collect();
+    groups.sort_by_key(|(_, group)| std::cmp::Reverse(group.count));
+    for (key, group) in groups {
+      let mismatches = if group.count == 1 {
+        "mismatch"
+      } else {
+        "mismatches"
+      };
+      writeln!(f)?;
+      match key {
+        Some(entity) => {
+          write!(f, "{}: {} {}", entity, group.count, mismatches)?;
+          if let Fidelity::Missing(what) | Fidelity::Partial(what) = entity.fidelity() {
+            write!(f, " (known gap: {})", what)?;
+          }
+          writeln!(f)?;
+        }
+        None => writeln!(f, "Ending the turn: {} {}", group.count, mismatches)?,
+      }
+      for example in &group.examples {
+        writeln!(
+          f,
+          "  at {}, after `{}`:\n    {}",
+          example.location, example.command, example.mismatch
+        )?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  /// A first turn against a Jaw Worm, with the given attacks in hand and discard pile.
+  fn game_state(
+    hand: &[&str],
+    discard_pile: &[&str],
+    energy: i32,
+    block: i32,
+    monster_hp: i32,
+  ) -> communication::GameState {
+    let cards = |ids: &[&str]| -> Vec<serde_json::Value> {
+      ids
+        .iter()
+        .map(|id| {
+          json!({"name": id, "uuid": format!("{}-uuid", id), "cost": 1, "upgrades": 0, "id": id,
+            "type": "ATTACK", "rarity": "BASIC", "has_target": true, "exhausts": false})
+        })
+        .collect()
+    };
+    let jaw_worm = json!({"name": "Jaw Worm", "id": "JawWorm", "current_hp": monster_hp,
+      "max_hp": 40, "block": 0, "intent": "ATTACK", "last_move_id": null,
+      "second_last_move_id": null, "half_dead": false, "is_gone": false, "powers": []});
+    serde_json::from_value(json!({
+      "screen_name": "NONE", "is_screen_up": false, "screen_type": "NONE", "screen_state": {},
+      "room_phase": "COMBAT", "action_phase": "WAITING_ON_USER", "room_type": "MonsterRoom",
+      "current_hp": 70, "max_hp": 80, "floor": 3, "act": 1, "act_boss": "Hexaghost", "gold": 99,
+      "seed": 1, "class": "IRONCLAD", "ascension_level": 0, "relics": [], "deck": [],
+      "potions": [], "map": null, "current_action": null, "choice_list": null,
+      "combat_state": {
+        "draw_pile": [], "discard_pile": cards(discard_pile), "exhaust_pile": [],
+        "hand": cards(hand), "limbo": [], "card_in_play": null, "cards_discarded_this_turn": 0,
+        "turn": 1,
+        "player": {"current_hp": 70, "max_hp": 80, "block": block, "powers": [],
+          "energy": energy, "orbs": []},
+        "monsters": [jaw_worm],
+      },
+    }))
+    .unwrap()
+  }
+
+  fn strike(after: communication::GameState) -> RecordedTransition {
+    RecordedTransition {
+      location: "session.jsonl:2".to_string(),
+      before: game_state(&["Strike_R"], &[], 3, 0, 40),
+      command: "play 1 0".to_string(),
+      after,
+    }
+  }
+
+  #[test]
+  fn matching_transition_has_no_mismatches() {
+    let transition = strike(game_state(&[], &["Strike_R"], 2, 0, 34));
+    match check_transition(&transition, None) {
+      TransitionOutcome::Checked {
+        played, mismatches, ..
+      } => {
+        assert_eq!(played, Some(CardId::StrikeR));
+        assert_eq!(mismatches, Vec::new());
+      }
+      outcome => panic!("expected the transition to be checked, got {:?}", outcome),
+    }
+  }
+
+  #[test]
+  fn compare_states_names_the_subject() {
+    let before = game_state(&["Strike_R"], &[], 3, 0, 40);
+    let after = game_state(&[], &["Strike_R"], 3, 0, 34);
+    let before = CombatState::from_communication_mod(&before, None, Default::default())
+      .unwrap()
+      .0;
+    let after = CombatState::from_communication_mod(&after, None, Default::default())
+      .unwrap()
+      .0;
+    assert_eq!(compare_states(&after, &after), Vec::new());
+    let subjects: Vec<(String, Option<AuditedEntity>)> = compare_states(&before, &after)
+      .into_iter()
+      .map(|mismatch| (mismatch.field, mismatch.subject))
+      .collect();
+    assert_eq!(
+      subjects,
+      vec![
+        ("hand".to_string(), None),
+        ("discard pile".to_string(), None),
+        (
+          "monster 0 (JawWorm) hitpoints".to_string(),
+          Some(AuditedEntity::Monster(MonsterId::JawWorm))
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn each_mismatch_is_counted_once() {
+    let transition = strike(game_state(&[], &["Strike_R"], 2, 1, 33));
+    let outcome = check_transition(&transition, None);
+    let mut report = ValidationReport::default();
+    report.add(&transition, outcome);
+    assert_eq!(report.checked, 1);
+    assert_eq!(report.matching, 0);
+    // the block is charged to the Strike, and the hitpoints to the monster
+    let counts: Vec<(Option<AuditedEntity>, usize)> = report
+      .groups
+      .iter()
+      .map(|(key, group)| (*key, group.count))
+      .collect();
+    assert_eq!(
+      counts,
+      vec![
+        (Some(AuditedEntity::Card(CardId::StrikeR)), 1),
+        (Some(AuditedEntity::Monster(MonsterId::JawWorm)), 1),
+      ]
+    );
+  }
+}
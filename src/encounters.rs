@@ -0,0 +1,429 @@
+use crate::simulation_state::MonsterId;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+pub use crate::simulation_state::monsters::hitpoints_range;
+
+/// Which list the game draws an encounter from.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub enum EncounterPool {
+  /// The first few normal fights of each act.
+  Weak,
+  /// The rest of the normal fights.
+  Strong,
+  Elite,
+  Boss,
+  /// Fights that only happen in events.
+  Event,
+}
+
+/// A combat the game can put the player in. Monster hitpoints don't depend on the encounter, just
+/// on the monster and the ascension; see `hitpoints_range`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Encounter {
+  /// The game's name for the encounter, like "Jaw Worm" or "Gremlin Gang".
+  pub name: &'static str,
+  pub act: i32,
+  pub pool: EncounterPool,
+  /// How likely the game is to choose it, relative to the rest of its pool.
+  pub weight: f64,
+}
+
+macro_rules! encounters {
+  ($([$act: expr, $pool: ident, $weight: expr, $name: expr],)*) => {
+    pub const ENCOUNTERS: &[Encounter] = &[
+      $(Encounter {
+        name: $name,
+        act: $act,
+        pool: EncounterPool::$pool,
+        weight: $weight,
+      },)*
+    ];
+  }
+}
+
+encounters! {
+  [1, Weak, 2.0, "Cultist"],
+  [1, Weak, 2.0, "Jaw Worm"],
+  [1, Weak, 2.0, "2 Louse"],
+  [1, Weak, 2.0, "Small Slimes"],
+  [1, Strong, 1.0, "Gremlin Gang"],
+  [1, Strong, 1.0, "Lots of Slimes"],
+  [1, Strong, 1.0, "Red Slaver"],
+  [1, Strong, 1.5, "Exordium Thugs"],
+  [1, Strong, 1.5, "Exordium Wildlife"],
+  [1, Strong, 2.0, "Blue Slaver"],
+  [1, Strong, 2.0, "Looter"],
+  [1, Strong, 2.0, "Large Slime"],
+  [1, Strong, 2.0, "3 Louse"],
+  [1, Strong, 2.0, "2 Fungi Beasts"],
+  [1, Elite, 1.0, "Gremlin Nob"],
+  [1, Elite, 1.0, "Lagavulin"],
+  [1, Elite, 1.0, "3 Sentries"],
+  [1, Boss, 1.0, "The Guardian"],
+  [1, Boss, 1.0, "Hexaghost"],
+  [1, Boss, 1.0, "Slime Boss"],
+
+  [2, Weak, 2.0, "Spheric Guardian"],
+  [2, Weak, 2.0, "Chosen"],
+  [2, Weak, 2.0, "Shell Parasite"],
+  [2, Weak, 2.0, "3 Byrds"],
+  [2, Weak, 2.0, "2 Thieves"],
+  [2, Strong, 2.0, "Chosen and Byrds"],
+  [2, Strong, 2.0, "Sentry and Sphere"],
+  [2, Strong, 6.0, "Snake Plant"],
+  [2, Strong, 4.0, "Snecko"],
+  [2, Strong, 6.0, "Centurion and Healer"],
+  [2, Strong, 3.0, "Cultist and Chosen"],
+  [2, Strong, 3.0, "3 Cultists"],
+  [2, Strong, 3.0, "Shelled Parasite and Fungi"],
+  [2, Elite, 1.0, "Gremlin Leader"],
+  [2, Elite, 1.0, "Slavers"],
+  [2, Elite, 1.0, "Book of Stabbing"],
+  [2, Boss, 1.0, "Automaton"],
+  [2, Boss, 1.0, "Collector"],
+  [2, Boss, 1.0, "Champ"],
+  [2, Event, 1.0, "Masked Bandits"],
+
+  [3, Weak, 2.0, "3 Darklings"],
+  [3, Weak, 2.0, "Orb Walker"],
+  [3, Weak, 2.0, "3 Shapes"],
+  [3, Strong, 1.0, "Spire Growth"],
+  [3, Strong, 1.0, "Transient"],
+  [3, Strong, 1.0, "4 Shapes"],
+  [3, Strong, 1.0, "Maw"],
+  [3, Strong, 1.0, "Sphere and 2 Shapes"],
+  [3, Strong, 1.0, "Jaw Worm Horde"],
+  [3, Strong, 1.0, "3 Darklings"],
+  [3, Strong, 1.0, "Writhing Mass"],
+  [3, Elite, 2.0, "Giant Head"],
+  [3, Elite, 2.0, "Nemesis"],
+  [3, Elite, 2.0, "Reptomancer"],
+  [3, Boss, 1.0, "Awakened One"],
+  [3, Boss, 1.0, "Time Eater"],
+  [3, Boss, 1.0, "Donu and Deca"],
+
+  [4, Elite, 1.0, "Shield and Spear"],
+  [4, Boss, 1.0, "The Heart"],
+}
+
+impl Encounter {
+  /// The encounter's monsters, in the order the game places them. Some encounters are made up
+  /// of randomly chosen monsters; those are chosen using `rng`.
+  pub fn monsters(&self, rng: &mut impl Rng) -> Vec<MonsterId> {
+    encounter_monsters(self.name, rng).unwrap()
+  }
+}
+
+/// Some encounters appear in more than one pool; this finds the first.
+pub fn by_name(name: &str) -> Option<&'static Encounter> {
+  ENCOUNTERS.iter().find(|encounter| encounter.name == name)
+}
+
+pub fn encounters(act: i32, pool: EncounterPool) -> impl Iterator<Item = &'static Encounter> {
+  ENCOUNTERS
+    .iter()
+    .filter(move |encounter| encounter.act == act && encounter.pool == pool)
+}
+
+/// Chooses from a pool the way the game does, by weight. Unlike the game, it doesn't avoid
+/// repeating the previous encounters.
+pub fn sample(act: i32, pool: EncounterPool, rng: &mut impl Rng) -> Option<&'static Encounter> {
+  let candidates: Vec<_> = encounters(act, pool).collect();
+  candidates
+    .choose_weighted(rng, |encounter| encounter.weight)
+    .ok()
+    .copied()
+}
+
+/// The act a floor belongs to, counting each act's boss floor and the treasure floor after it.
+pub fn act_of_floor(floor: i32) -> i32 {
+  match floor {
+    i32::MIN..=17 => 1,
+    18..=34 => 2,
+    35..=51 => 3,
+    _ => 4,
+  }
+}
+
+/// The kinds of room that have fights in them.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub enum FightRoom {
+  Monster,
+  Elite,
+  Boss,
+}
+
+/// Samples an encounter for a room on the given floor. `fights_this_act` is how many normal
+/// fights the player has already had this act, which decides between the weak and strong pools.
+/// Act 4 only has its elite and boss, so asking it for a normal fight gives `None`.
+pub fn sample_for_floor(
+  floor: i32,
+  room: FightRoom,
+  fights_this_act: i32,
+  rng: &mut impl Rng,
+) -> Option<&'static Encounter> {
+  let act = act_of_floor(floor);
+  let pool = match room {
+    FightRoom::Monster => {
+      let weak_fights = if act == 1 { 3 } else { 2 };
+      if fights_this_act < weak_fights {
+        EncounterPool::Weak
+      } else {
+        EncounterPool::Strong
+      }
+    }
+    FightRoom::Elite => EncounterPool::Elite,
+    FightRoom::Boss => EncounterPool::Boss,
+  };
+  sample(act, pool, rng)
+}
+
+fn encounter_monsters(encounter: &str, rng: &mut impl Rng) -> Option<Vec<MonsterId>> {
+  use MonsterId::*;
+  Some(match encounter {
+    "Cultist" => vec![Cultist],
+    "Jaw Worm" => vec![JawWorm],
+    "2 Louse" => vec![louse(rng), louse(rng)],
+    "Small Slimes" => {
+      if rng.gen() {
+        vec![SpikeSlimeS, AcidSlimeM]
+      } else {
+        vec![AcidSlimeS, SpikeSlimeM]
+      }
+    }
+    "Blue Slaver" => vec![SlaverBlue],
+    "Red Slaver" => vec![SlaverRed],
+    "Gremlin Gang" => random_subset(rng, &GREMLINS, 4),
+    "Looter" => vec![Looter],
+    "Large Slime" => vec![*[AcidSlimeL, SpikeSlimeL].choose(rng).unwrap()],
+    "Lots of Slimes" => random_subset(
+      rng,
+      &[
+        SpikeSlimeS,
+        SpikeSlimeS,
+        SpikeSlimeS,
+        AcidSlimeS,
+        AcidSlimeS,
+      ],
+      5,
+    ),
+    "Exordium Thugs" => {
+      let slaver = *[SlaverBlue, SlaverRed].choose(rng).unwrap();
+      vec![
+        weak_bottom(rng),
+        *[Cultist, slaver, Looter].choose(rng).unwrap(),
+      ]
+    }
+    "Exordium Wildlife" => vec![
+      weak_bottom(rng),
+      *[FungiBeast, JawWorm].choose(rng).unwrap(),
+    ],
+    "3 Louse" => vec![louse(rng), louse(rng), louse(rng)],
+    "2 Fungi Beasts" => vec![FungiBeast, FungiBeast],
+    "Gremlin Nob" => vec![GremlinNob],
+    "Lagavulin" => vec![Lagavulin],
+    "3 Sentries" => vec![Sentry, Sentry, Sentry],
+    "The Guardian" => vec![TheGuardian],
+    "Hexaghost" => vec![Hexaghost],
+    "Slime Boss" => vec![SlimeBoss],
+
+    "Spheric Guardian" => vec![SphericGuardian],
+    "Chosen" => vec![Chosen],
+    "Shell Parasite" => vec![ShelledParasite],
+    "3 Byrds" => vec![Byrd, Byrd, Byrd],
+    "2 Thieves" => vec![Looter, Mugger],
+    "Chosen and Byrds" => vec![Byrd, Chosen],
+    "Sentry and Sphere" => vec![Sentry, SphericGuardian],
+    "Snake Plant" => vec![SnakePlant],
+    "Snecko" => vec![Snecko],
+    "Centurion and Healer" => vec![Centurion, Mystic],
+    "Cultist and Chosen" => vec![Cultist, Chosen],
+    "3 Cultists" => vec![Cultist, Cultist, Cultist],
+    "Shelled Parasite and Fungi" => vec![ShelledParasite, FungiBeast],
+    "Masked Bandits" => vec![Pointy, Romeo, Bear],
+    "Gremlin Leader" => {
+      let mut monsters = random_subset(rng, &GREMLINS, 2);
+      monsters.push(GremlinLeader);
+      monsters
+    }
+    "Slavers" => vec![SlaverBlue, Taskmaster, SlaverRed],
+    "Book of Stabbing" => vec![BookOfStabbing],
+    "Automaton" => vec![BronzeAutomaton],
+    "Collector" => vec![TheCollector],
+    "Champ" => vec![TheChamp],
+
+    "3 Darklings" => vec![Darkling, Darkling, Darkling],
+    "Orb Walker" => vec![OrbWalker],
+    "3 Shapes" => random_subset(rng, &SHAPES, 3),
+    "4 Shapes" => random_subset(rng, &SHAPES, 4),
+    "Spire Growth" => vec![SpireGrowth],
+    "Transient" => vec![Transient],
+    "Maw" => vec![Maw],
+    "Sphere and 2 Shapes" => {
+      let mut monsters = random_subset(rng, &SHAPES, 2);
+      monsters.push(SphericGuardian);
+      monsters
+    }
+    // TODO: these Jaw Worms start with Strength and Block
+    "Jaw Worm Horde" => vec![JawWorm, JawWorm, JawWorm],
+    "Writhing Mass" => vec![WrithingMass],
+    "Giant Head" => vec![GiantHead],
+    "Nemesis" => vec![Nemesis],
+    "Reptomancer" => vec![SnakeDagger, Reptomancer, SnakeDagger],
+    "Awakened One" => vec![Cultist, Cultist, AwakenedOne],
+    "Time Eater" => vec![TimeEater],
+    "Donu and Deca" => vec![Deca, Donu],
+
+    "Shield and Spear" => vec![SpireShield, SpireSpear],
+    "The Heart" => vec![CorruptHeart],
+    _ => return None,
+  })
+}
+
+const GREMLINS: [MonsterId; 8] = [
+  MonsterId::MadGremlin,
+  MonsterId::MadGremlin,
+  MonsterId::SneakyGremlin,
+  MonsterId::SneakyGremlin,
+  MonsterId::FatGremlin,
+  MonsterId::FatGremlin,
+  MonsterId::GremlinWizard,
+  MonsterId::ShieldGremlin,
+];
+const SHAPES: [MonsterId; 6] = [
+  MonsterId::Repulsor,
+  MonsterId::Repulsor,
+  MonsterId::Exploder,
+  MonsterId::Exploder,
+  MonsterId::Spiker,
+  MonsterId::Spiker,
+];
+
+/// `count` monsters drawn from `pool` without replacement, in random order.
+fn random_subset(rng: &mut impl Rng, pool: &[MonsterId], count: usize) -> Vec<MonsterId> {
+  let mut result = pool.to_vec();
+  result.shuffle(rng);
+  result.truncate(count);
+  result
+}
+
+fn louse(rng: &mut impl Rng) -> MonsterId {
+  *[MonsterId::RedLouse, MonsterId::GreenLouse]
+    .choose(rng)
+    .unwrap()
+}
+
+/// The weaker monster of Exordium Thugs and Exordium Wildlife.
+fn weak_bottom(rng: &mut impl Rng) -> MonsterId {
+  match rng.gen_range(0..3) {
+    0 => louse(rng),
+    1 => MonsterId::SpikeSlimeM,
+    _ => MonsterId::AcidSlimeM,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rand::SeedableRng;
+  use rand_pcg::Pcg64Mcg;
+
+  #[test]
+  fn every_encounter_has_monsters() {
+    let mut rng = Pcg64Mcg::seed_from_u64(0);
+    for encounter in ENCOUNTERS {
+      assert!(encounter.weight > 0.0, "{}", encounter.name);
+      for _ in 0..20 {
+        let monsters = encounter_monsters(encounter.name, &mut rng)
+          .unwrap_or_else(|| panic!("{} has no monsters", encounter.name));
+        assert!(!monsters.is_empty(), "{}", encounter.name);
+      }
+    }
+    assert_eq!(encounter_monsters("Not an Encounter", &mut rng), None);
+  }
+
+  #[test]
+  fn random_encounters_draw_from_their_pools() {
+    let mut rng = Pcg64Mcg::seed_from_u64(1);
+    for _ in 0..100 {
+      let gang = encounter_monsters("Gremlin Gang", &mut rng).unwrap();
+      assert_eq!(gang.len(), 4);
+      assert!(gang.iter().all(|monster| GREMLINS.contains(monster)));
+      // there's only one of each of these in the pool
+      for &unique in &[MonsterId::GremlinWizard, MonsterId::ShieldGremlin] {
+        assert!(gang.iter().filter(|&&monster| monster == unique).count() <= 1);
+      }
+
+      let slimes = encounter_monsters("Lots of Slimes", &mut rng).unwrap();
+      let mut sorted = slimes.clone();
+      sorted.sort_by_key(|monster| format!("{:?}", monster));
+      use MonsterId::*;
+      assert_eq!(
+        sorted,
+        [
+          AcidSlimeS,
+          AcidSlimeS,
+          SpikeSlimeS,
+          SpikeSlimeS,
+          SpikeSlimeS
+        ]
+      );
+
+      let leader = encounter_monsters("Gremlin Leader", &mut rng).unwrap();
+      assert_eq!(leader.len(), 3);
+      assert_eq!(leader.last(), Some(&GremlinLeader));
+    }
+  }
+
+  #[test]
+  fn floors_belong_to_acts() {
+    assert_eq!(act_of_floor(1), 1);
+    assert_eq!(act_of_floor(17), 1);
+    assert_eq!(act_of_floor(18), 2);
+    assert_eq!(act_of_floor(34), 2);
+    assert_eq!(act_of_floor(35), 3);
+    assert_eq!(act_of_floor(51), 3);
+    assert_eq!(act_of_floor(52), 4);
+  }
+
+  #[test]
+  fn sample_for_floor_chooses_the_pool() {
+    let mut rng = Pcg64Mcg::seed_from_u64(2);
+    let mut sample = |floor, room, fights_this_act| {
+      sample_for_floor(floor, room, fights_this_act, &mut rng).map(|encounter| {
+        assert_eq!(encounter.act, act_of_floor(floor));
+        encounter.pool
+      })
+    };
+    for _ in 0..20 {
+      assert_eq!(sample(2, FightRoom::Monster, 2), Some(EncounterPool::Weak));
+      assert_eq!(
+        sample(5, FightRoom::Monster, 3),
+        Some(EncounterPool::Strong)
+      );
+      assert_eq!(sample(19, FightRoom::Monster, 1), Some(EncounterPool::Weak));
+      assert_eq!(
+        sample(20, FightRoom::Monster, 2),
+        Some(EncounterPool::Strong)
+      );
+      assert_eq!(sample(40, FightRoom::Elite, 0), Some(EncounterPool::Elite));
+      assert_eq!(sample(16, FightRoom::Boss, 5), Some(EncounterPool::Boss));
+      assert_eq!(sample(55, FightRoom::Elite, 0), Some(EncounterPool::Elite));
+      assert_eq!(sample(55, FightRoom::Monster, 0), None);
+    }
+  }
+
+  #[test]
+  fn sampling_follows_the_weights() {
+    let mut rng = Pcg64Mcg::seed_from_u64(3);
+    let samples = 20000;
+    let snake_plants = (0..samples)
+      .filter(|_| sample(2, EncounterPool::Strong, &mut rng).unwrap().name == "Snake Plant")
+      .count();
+    // 6 out of a total weight of 29
+    let expected = samples as f64 * 6.0 / 29.0;
+    assert!((snake_plants as f64 - expected).abs() < expected * 0.1);
+  }
+}
@@ -62,14 +62,31 @@ macro_rules! power_hook {
   };
 }
 
+macro_rules! relic_hook {
+  ($runner: expr, $hook: ident ( $($arguments:tt)*)) => {
+    {
+      let runner = &mut* $runner;
+      for index in 0..runner.state().relics.len() {
+        let relic_id = runner.state().relics [index].relic_id;
+        $crate::simulation_state::relics::RelicBehavior::$hook (&relic_id, &mut $crate::simulation_state::relics::RelicHookContext {runner, relic_index: index}, $($arguments)*);
+      }
+    }
+  };
+}
+
 pub mod actions;
+pub mod combat_generation;
+pub mod command_translation;
 pub mod communication_mod_state;
 pub mod competing_optimizers;
 //mod cow;
 pub mod ai_utils;
 pub mod commands {
   pub mod communicate;
+  pub mod generate_combat;
   pub mod sandbox;
+  pub mod stand_in;
+  pub mod validate;
   pub mod watch;
 }
 pub mod neural_net_ai;
@@ -77,9 +94,12 @@ pub mod neural_net_ai;
 pub mod analysis_flows;
 pub mod condition_strategy;
 pub mod condition_strategy_generators;
+pub mod differential_validation;
+pub mod encounters;
 pub mod representative_sampling;
 pub mod seed_system;
 pub mod seeds_concrete;
+pub mod spire_seed;
 pub mod simulation;
 pub mod simulation_state;
 pub mod start_and_strategy_ai;
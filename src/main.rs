@@ -1,10 +1,46 @@
 use borg_the_spire::competing_optimizers::CompetitorSpecification;
+use borg_the_spire::simulation_state::UnknownMonsterModel;
 use borg_the_spire::{
-  commands::{communicate, sandbox, watch},
+  commands::{communicate, generate_combat, sandbox, stand_in, validate, watch},
   competing_optimizers, webserver,
 };
-use clap::{App, AppSettings, Arg, SubCommand};
-use std::path::PathBuf;
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const UNKNOWN_MONSTER_MODEL_HELP: &str = "A JSON file with the `UnknownMonsterModel` fields to assume for monsters we don't simulate, like `{\"attack_damage\": 20}`; the ones left out keep their defaults";
+
+fn read_json<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+  let file = std::fs::File::open(path).map_err(|error| format!("{}: {}", path, error))?;
+  serde_json::from_reader(std::io::BufReader::new(file))
+    .map_err(|error| format!("{}: {}", path, error))
+}
+
+fn unknown_monster_model(matches: &ArgMatches) -> Result<UnknownMonsterModel, String> {
+  match matches.value_of("unknown-monster-model") {
+    Some(path) => read_json(path),
+    None => Ok(UnknownMonsterModel::default()),
+  }
+}
+
+fn autopilot(matches: &ArgMatches) -> Result<Option<communicate::Autopilot>, String> {
+  let milliseconds = match matches.value_of("autopilot") {
+    Some(milliseconds) => milliseconds,
+    None => return Ok(None),
+  };
+  let milliseconds = milliseconds
+    .parse::<u64>()
+    .map_err(|_| format!("invalid --autopilot time {:?}", milliseconds))?;
+  Ok(Some(communicate::Autopilot::new(
+    Duration::from_millis(milliseconds),
+    unknown_monster_model(matches)?,
+    matches
+      .value_of("autopilot-optimizer")
+      .map(read_json)
+      .transpose()?,
+  )))
+}
 
 fn main() {
   let matches = App::new("Borg the Spire")
@@ -13,7 +49,11 @@ fn main() {
     .subcommand(
       SubCommand::with_name("communicate")
         .long_about("The command to run as the child process for CommunicationMod. Listens for game states and saves them into the given state-file.")
-        .arg(Arg::with_name("state-file").required(true)),
+        .arg(Arg::with_name("state-file").required(true))
+        .arg(Arg::with_name("archive").long("archive").takes_value(true).help("Also append every message, with timestamps, to a session file per run in this directory, for `validate` and post-mortems"))
+        .arg(Arg::with_name("autopilot").long("autopilot").takes_value(true).value_name("MILLISECONDS").help("Play combats automatically, searching for this long before each command. Other screens are left to you."))
+        .arg(Arg::with_name("autopilot-optimizer").long("autopilot-optimizer").requires("autopilot").takes_value(true).value_name("FILE").help("A JSON file with a competitor specification, like the ones run_competing_optimizers takes, for the autopilot to search with instead of the default search"))
+        .arg(Arg::with_name("unknown-monster-model").long("unknown-monster-model").takes_value(true).value_name("FILE").help(UNKNOWN_MONSTER_MODEL_HELP)),
     )
       .subcommand(
         SubCommand::with_name("live-analyze")
@@ -22,7 +62,8 @@ fn main() {
             .arg(Arg::with_name("ip").long("ip").required(true).takes_value(true))
             .arg(Arg::with_name("port").long("port").required(true).takes_value(true))
             .arg(Arg::with_name("static-files").long("static-files").required(true).takes_value(true).help("The path to the static html/etc files for BtS, typically `./static`"))
-            .arg(Arg::with_name("data-files").long("data-files").required(true).takes_value(true).help("The path to the data files for BtS, typically `./data`")),
+            .arg(Arg::with_name("data-files").long("data-files").required(true).takes_value(true).help("The path to the data files for BtS, typically `./data`"))
+            .arg(Arg::with_name("unknown-monster-model").long("unknown-monster-model").takes_value(true).value_name("FILE").help(UNKNOWN_MONSTER_MODEL_HELP)),
       )
       .subcommand(
         SubCommand::with_name("watch")
@@ -36,20 +77,65 @@ fn main() {
         .arg(Arg::with_name("competitor-spec-file").required(true)),
     )
     .subcommand(SubCommand::with_name("sandbox").arg(Arg::with_name("root-path").required(true)))
+    .subcommand(
+      SubCommand::with_name("generate-combat")
+        .long_about("Build the start of a combat from a deck, relics and an encounter, without needing CommunicationMod.")
+        .arg(Arg::with_name("character").long("character").takes_value(true).default_value("IRONCLAD").help("IRONCLAD, THE_SILENT, DEFECT or WATCHER"))
+        .arg(Arg::with_name("deck").long("deck").required(true).takes_value(true).help("Comma-separated card IDs, like `Strike_R*5,Defend_R*4,Bash+`"))
+        .arg(Arg::with_name("relics").long("relics").takes_value(true).default_value("").help("Comma-separated relic IDs"))
+        .arg(Arg::with_name("hp").long("hp").required(true).takes_value(true).help("`current/max`, or just `max`"))
+        .arg(Arg::with_name("ascension").long("ascension").takes_value(true).default_value("0"))
+        .arg(Arg::with_name("encounter").long("encounter").required_unless("floor").takes_value(true).help("The game's name for the encounter, like `Gremlin Gang`"))
+        .arg(Arg::with_name("floor").long("floor").conflicts_with("encounter").takes_value(true).help("Sample the encounter for this floor instead"))
+        .arg(Arg::with_name("room").long("room").takes_value(true).possible_values(&["monster", "elite", "boss"]).default_value("monster").help("The kind of room, with --floor"))
+        .arg(Arg::with_name("fights-this-act").long("fights-this-act").takes_value(true).default_value("0").help("With --floor, how many normal fights came before this one in the act, which decides whether it's from the weak pool"))
+        .arg(Arg::with_name("seed").long("seed").takes_value(true))
+        .arg(Arg::with_name("output").long("output").takes_value(true).help("Where to save the combat state as JSON")),
+    )
+    .subcommand(
+      SubCommand::with_name("stand-in")
+        .long_about("Pretend to be CommunicationMod for a command, like `communicate --autopilot`, replaying the messages from a recorded session and comparing its commands with the recorded ones.")
+        .setting(AppSettings::TrailingVarArg)
+        .arg(Arg::with_name("session").required(true))
+        .arg(Arg::with_name("timeout").long("timeout").takes_value(true).value_name("MILLISECONDS").default_value("5000").help("How long to wait for each command"))
+        .arg(Arg::with_name("command").required(true).multiple(true)),
+    )
+    .subcommand(
+      SubCommand::with_name("validate")
+        .long_about("Replay recorded CommunicationMod sessions through the simulation, and report where it disagreed with the game.")
+        .arg(Arg::with_name("sessions").required(true).multiple(true).help("Session files, like the ones `communicate --archive` writes")),
+    )
     .get_matches();
 
   match matches.subcommand() {
     ("communicate", Some(matches)) => {
-      communicate::communicate(PathBuf::from(matches.value_of("state-file").unwrap()))
+      let result = autopilot(matches).map(|autopilot| {
+        communicate::communicate(
+          PathBuf::from(matches.value_of("state-file").unwrap()),
+          matches.value_of("archive").map(PathBuf::from),
+          autopilot,
+        )
+      });
+      if let Err(error) = result {
+        eprintln!("{}", error);
+        std::process::exit(1);
+      }
     }
     ("live-analyze", Some(matches)) => {
-      webserver::run(
-        PathBuf::from(matches.value_of("static-files").unwrap()),
-        PathBuf::from(matches.value_of("data-files").unwrap()),
-        PathBuf::from(matches.value_of("state-file").unwrap()),
-        matches.value_of("ip").unwrap(),
-        matches.value_of("port").unwrap().parse::<u16>().unwrap(),
-      );
+      let result = unknown_monster_model(matches).map(|unknown_monster_model| {
+        webserver::run(
+          PathBuf::from(matches.value_of("static-files").unwrap()),
+          PathBuf::from(matches.value_of("data-files").unwrap()),
+          PathBuf::from(matches.value_of("state-file").unwrap()),
+          unknown_monster_model,
+          matches.value_of("ip").unwrap(),
+          matches.value_of("port").unwrap().parse::<u16>().unwrap(),
+        )
+      });
+      if let Err(error) = result {
+        eprintln!("{}", error);
+        std::process::exit(1);
+      }
     }
     ("watch", Some(matches)) => {
       watch::watch(
@@ -67,6 +153,75 @@ fn main() {
     ("sandbox", Some(matches)) => {
       sandbox::run(PathBuf::from(matches.value_of("root-path").unwrap()));
     }
+    ("generate-combat", Some(matches)) => {
+      let number = |name: &str| {
+        let value = matches.value_of(name).unwrap();
+        value
+          .parse::<i32>()
+          .map_err(|_| format!("invalid --{} {:?}", name, value))
+      };
+      let result = number("ascension")
+        .and_then(|ascension| {
+          generate_combat::parse_specification(
+            matches.value_of("character").unwrap(),
+            matches.value_of("deck").unwrap(),
+            matches.value_of("relics").unwrap(),
+            matches.value_of("hp").unwrap(),
+            ascension,
+            matches.value_of("encounter").unwrap_or(""),
+          )
+        })
+        .and_then(|specification| {
+          let floor_fight = match matches.value_of("floor") {
+            Some(_) => Some(generate_combat::FloorFight {
+              floor: number("floor")?,
+              room: generate_combat::parse_room(matches.value_of("room").unwrap())?,
+              fights_this_act: number("fights-this-act")?,
+            }),
+            None => None,
+          };
+          let seed = matches
+            .value_of("seed")
+            .map(|seed| {
+              seed
+                .parse::<u64>()
+                .map_err(|_| format!("invalid --seed {:?}", seed))
+            })
+            .transpose()?;
+          generate_combat::run(
+            &specification,
+            floor_fight,
+            seed,
+            matches.value_of("output").map(Path::new),
+          )
+        });
+      if let Err(error) = result {
+        eprintln!("{}", error);
+        std::process::exit(1);
+      }
+    }
+    ("stand-in", Some(matches)) => {
+      let result = stand_in::run(
+        Path::new(matches.value_of("session").unwrap()),
+        &matches.values_of("command").unwrap().collect::<Vec<&str>>(),
+        Duration::from_millis(matches.value_of("timeout").unwrap().parse().unwrap()),
+      );
+      if let Err(error) = result {
+        eprintln!("{}", error);
+        std::process::exit(1);
+      }
+    }
+    ("validate", Some(matches)) => {
+      let sessions: Vec<PathBuf> = matches
+        .values_of("sessions")
+        .unwrap()
+        .map(PathBuf::from)
+        .collect();
+      if let Err(error) = validate::run(&sessions) {
+        eprintln!("{}", error);
+        std::process::exit(1);
+      }
+    }
     _ => {}
   }
 
@@ -99,7 +254,10 @@ fn main() {
               "player energy: {:?}",
               game_state.combat_state.as_ref().map(|cs| cs.player.energy)
             );
-            simulation_state::CombatState::from_communication_mod(game_state, None)
+            let (state, report) =
+              simulation_state::CombatState::from_communication_mod(game_state, None)?;
+            eprintln!("{}", report);
+            Some(state)
           });
           if let Some(state) = state {
             eprintln!("combat happening:\n{:#?}", state);
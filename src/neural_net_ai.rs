@@ -150,9 +150,10 @@ fn inputs(state: &CombatState) -> Vec<f64> {
     }
   }
 
+  let draw_pile: Vec<SingleCard> = state.draw_pile_cards().cloned().collect();
   for cards in &[
     state.hand.as_slice(),
-    state.draw_pile.as_slice(),
+    draw_pile.as_slice(),
     state.discard_pile.as_slice(),
     state.exhaust_pile.as_slice(),
   ] {
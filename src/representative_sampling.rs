@@ -1003,6 +1003,10 @@ impl<S: Strategy + 'static> StrategyOptimizer for FractalRepresentativeSeedSearc
     self.seed_search.diagnose_exploitations();
   }
 
+  fn current_strategy(&self) -> Arc<Self::Strategy> {
+    Arc::new(self.seed_search.meta_strategy())
+  }
+
   fn report(&self) -> Arc<Self::Strategy> {
     self.seed_search.report()
   }
@@ -1063,6 +1067,10 @@ impl<S: Strategy + 'static> StrategyOptimizer for NewFractalRepresentativeSeedSe
     //self.seed_search.diagnose_exploitations();
   }
 
+  fn current_strategy(&self) -> Arc<Self::Strategy> {
+    Arc::new(self.seed_search.meta_strategy())
+  }
+
   fn report(&self) -> Arc<Self::Strategy> {
     self.seed_search.report()
   }
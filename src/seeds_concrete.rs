@@ -1,6 +1,7 @@
 use crate::actions::{
-  AttackDamageRandomEnemyAction, ChooseMonsterIntent, DynAction, GainBlockRandomMonsterAction,
-  InitializeMonsterInnateDamageAmount,
+  ApplyPowerRandomEnemyAction, AttackDamageRandomEnemyAction, ChooseMonsterIntent, DynAction,
+  GainBlockRandomMonsterAction, InitializeMonsterHitpoints, InitializeMonsterInnateDamageAmount,
+  InitializeMonsterPowerAmount, LightningOrbDamageAction,
 };
 use crate::seed_system::{
   ChoiceLineageIdentity, ChoiceLineages, ChoiceLineagesKind, GameState, MaybeSeedView, NeverSeed,
@@ -23,7 +24,8 @@ struct TurnMap<T> {
 
 impl<T: Default> TurnMap<T> {
   fn get_mut(&mut self, turn: u8) -> &mut T {
-    let turn = turn as usize - 1;
+    // monsters choose their first intents on turn 0
+    let turn = turn as usize;
     if turn >= self.values.len() {
       self.values.resize_with(turn + 1, Default::default);
     }
@@ -49,10 +51,24 @@ pub enum CombatChoiceLineageIdentity {
   InitializeMonsterInnateDamageAmount {
     monster_index: u8,
   },
+  InitializeMonsterHitpoints {
+    monster_index: u8,
+  },
+  InitializeMonsterPowerAmount {
+    monster_index: u8,
+  },
   GainBlockRandomMonster {
     turn: u8,
     target: u8,
   },
+  LightningTarget {
+    turn: u8,
+    target: u8,
+  },
+  SpawnMonster {
+    turn: u8,
+    slot: u8,
+  },
   Uncategorized,
 }
 #[derive(Clone, Debug, Default)]
@@ -61,7 +77,11 @@ pub struct CombatChoiceLineages<T> {
   choose_monster_intent: [TurnMap<[T; MAX_INTENTS]>; MAX_MONSTERS],
   attack_random_enemy: [T; MAX_MONSTERS],
   initialize_monster_innate_damage_amount: [T; MAX_MONSTERS],
+  initialize_monster_hitpoints: [T; MAX_MONSTERS],
+  initialize_monster_power_amount: [T; MAX_MONSTERS],
   gain_block_random_monster: [TurnMap<T>; MAX_MONSTERS],
+  lightning_target: [TurnMap<T>; MAX_MONSTERS],
+  spawn_monster: [TurnMap<T>; MAX_MONSTERS],
   uncategorized: T,
 }
 pub struct CombatChoiceLineagesKind;
@@ -70,7 +90,9 @@ impl ChoiceLineageIdentity<CombatState> for CombatChoiceLineageIdentity {
   #[inline(always)]
   fn lineage_identity(state: &CombatState, action: &DynAction, &choice: &i32) -> Self {
     match action {
-      DynAction::DrawCardRandom(_) => CombatChoiceLineageIdentity::DrawCard {
+      DynAction::DrawCardRandom(_)
+      | DynAction::PlayTopCardAction(_)
+      | DynAction::RevealDrawPileTopAction(_) => CombatChoiceLineageIdentity::DrawCard {
         card: state.draw_pile[choice as usize].card_info.id,
         turn: state.turn_number as u8,
         reshuffles: state.num_reshuffles as u8,
@@ -82,7 +104,8 @@ impl ChoiceLineageIdentity<CombatState> for CombatChoiceLineageIdentity {
           intent: choice as u8,
         }
       }
-      &DynAction::AttackDamageRandomEnemyAction(AttackDamageRandomEnemyAction { .. }) => {
+      &DynAction::AttackDamageRandomEnemyAction(AttackDamageRandomEnemyAction { .. })
+      | &DynAction::ApplyPowerRandomEnemyAction(ApplyPowerRandomEnemyAction { .. }) => {
         CombatChoiceLineageIdentity::AttackRandomEnemy {
           target: choice as u8,
         }
@@ -93,12 +116,38 @@ impl ChoiceLineageIdentity<CombatState> for CombatChoiceLineageIdentity {
       }) => CombatChoiceLineageIdentity::InitializeMonsterInnateDamageAmount {
         monster_index: monster_index as u8,
       },
+      &DynAction::InitializeMonsterHitpoints(InitializeMonsterHitpoints {
+        monster_index, ..
+      }) => CombatChoiceLineageIdentity::InitializeMonsterHitpoints {
+        monster_index: monster_index as u8,
+      },
+      &DynAction::InitializeMonsterPowerAmount(InitializeMonsterPowerAmount {
+        monster_index,
+        ..
+      }) => CombatChoiceLineageIdentity::InitializeMonsterPowerAmount {
+        monster_index: monster_index as u8,
+      },
       DynAction::GainBlockRandomMonsterAction(GainBlockRandomMonsterAction { .. }) => {
         CombatChoiceLineageIdentity::GainBlockRandomMonster {
           turn: state.turn_number as u8,
           target: choice as u8,
         }
       }
+      // kept apart from AttackRandomEnemy, so that whether a strategy plays random-target cards
+      // doesn't change where its Lightning goes
+      DynAction::LightningOrbDamageAction(LightningOrbDamageAction { .. }) => {
+        CombatChoiceLineageIdentity::LightningTarget {
+          turn: state.turn_number as u8,
+          target: choice as u8,
+        }
+      }
+      // which gremlin Gremlin Leader summons, and the hitpoints of anything spawned
+      DynAction::SummonGremlinAction(_) | DynAction::SpawnMonsterAction(_) => {
+        CombatChoiceLineageIdentity::SpawnMonster {
+          turn: state.turn_number as u8,
+          slot: state.next_spawn_slot().unwrap_or(0) as u8,
+        }
+      }
       _ => CombatChoiceLineageIdentity::Uncategorized,
     }
   }
@@ -134,9 +183,21 @@ impl<T: Clone + Debug + Default + Send + Sync> ChoiceLineages for CombatChoiceLi
       CombatChoiceLineageIdentity::InitializeMonsterInnateDamageAmount { monster_index } => {
         &mut self.initialize_monster_innate_damage_amount[monster_index as usize]
       }
+      CombatChoiceLineageIdentity::InitializeMonsterHitpoints { monster_index } => {
+        &mut self.initialize_monster_hitpoints[monster_index as usize]
+      }
+      CombatChoiceLineageIdentity::InitializeMonsterPowerAmount { monster_index } => {
+        &mut self.initialize_monster_power_amount[monster_index as usize]
+      }
       CombatChoiceLineageIdentity::GainBlockRandomMonster { turn, target } => {
         self.gain_block_random_monster[target as usize].get_mut(turn)
       }
+      CombatChoiceLineageIdentity::LightningTarget { turn, target } => {
+        self.lightning_target[target as usize].get_mut(turn)
+      }
+      CombatChoiceLineageIdentity::SpawnMonster { turn, slot } => {
+        self.spawn_monster[slot as usize].get_mut(turn)
+      }
       CombatChoiceLineageIdentity::Uncategorized => &mut self.uncategorized,
     }
   }
@@ -1,3 +1,4 @@
+use arrayvec::ArrayVec;
 use derivative::Derivative;
 use serde::{Deserialize, Serialize};
 //use rand::{Rng, SeedableRng};
@@ -12,15 +13,130 @@ use smallvec::alloc::fmt::Formatter;
 use std::fmt;
 use std::fmt::Display;
 
-/*
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Debug)]
+pub enum CardPile {
+  Hand,
+  DrawPile,
+  DiscardPile,
+  ExhaustPile,
+  /// Cards generated for the player to pick one of, which aren't in any real pile yet.
+  Offered,
+}
+
+/// Which cards an `UpgradeCardsAction` upgrades.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Debug)]
+pub enum UpgradeCardsFilter {
+  /// Every card that can normally be upgraded.
+  Upgradable,
+  /// Every copy of this card that isn't upgraded yet, even status cards like Burn.
+  Id(CardId),
+}
+
+impl UpgradeCardsFilter {
+  pub fn matches(self, card: &SingleCard) -> bool {
+    match self {
+      UpgradeCardsFilter::Upgradable => card.can_upgrade(),
+      UpgradeCardsFilter::Id(card_id) => card.card_info.id == card_id && card.upgrades == 0,
+    }
+  }
+}
+
+/// Why the player is being asked to pick a card mid-action. This determines both which pile
+/// the card is picked from, and what happens to it afterwards.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Debug)]
 pub enum CardChoiceType {
   ExhaustCard,
+  UpgradeCard,
   HandTopdeck,
   DiscardTopdeck,
-  TutorSkill,
-  TutorAttack,
+  DuplicateAttackOrPower {
+    copies: i32,
+  },
+  DiscardCard,
+  HandTopdeckFree,
+  RetainCard {
+    remaining: i32,
+  },
+  DiscardToHand,
+  DrawPileToHand,
+  RecycleCard,
+  /// Discard any of the top `cards` cards of the draw pile, one at a time.
+  ScryDiscard {
+    cards: i32,
+  },
+  DiscardToHandRetained,
+  DrawPilePlayTwice,
+  DiscardToHandFree,
+  /// Exhaust any number of cards from the hand, one at a time.
+  ExhaustAnyCards,
+  /// Discard any number of cards from the hand, one at a time, then draw as many.
+  GambleDiscard {
+    discarded: i32,
+  },
+  /// Pick one of the offered cards, and add `copies` of it to the hand, free for this turn.
+  Discovery {
+    copies: i32,
+  },
+  /// Pick one of the offered special cards, like Stance Potion's Wrath and Calm, and do what it
+  /// says.
+  ChooseOne,
+}
+
+impl CardChoiceType {
+  pub fn pile(self) -> CardPile {
+    match self {
+      CardChoiceType::ExhaustCard => CardPile::Hand,
+      CardChoiceType::UpgradeCard => CardPile::Hand,
+      CardChoiceType::HandTopdeck => CardPile::Hand,
+      CardChoiceType::DiscardTopdeck => CardPile::DiscardPile,
+      CardChoiceType::DuplicateAttackOrPower { .. } => CardPile::Hand,
+      CardChoiceType::DiscardCard => CardPile::Hand,
+      CardChoiceType::HandTopdeckFree => CardPile::Hand,
+      CardChoiceType::RetainCard { .. } => CardPile::Hand,
+      CardChoiceType::DiscardToHand => CardPile::DiscardPile,
+      CardChoiceType::DrawPileToHand => CardPile::DrawPile,
+      CardChoiceType::RecycleCard => CardPile::Hand,
+      CardChoiceType::ScryDiscard { .. } => CardPile::DrawPile,
+      CardChoiceType::DiscardToHandRetained => CardPile::DiscardPile,
+      CardChoiceType::DrawPilePlayTwice => CardPile::DrawPile,
+      CardChoiceType::DiscardToHandFree => CardPile::DiscardPile,
+      CardChoiceType::ExhaustAnyCards => CardPile::Hand,
+      CardChoiceType::GambleDiscard { .. } => CardPile::Hand,
+      CardChoiceType::Discovery { .. } => CardPile::Offered,
+      CardChoiceType::ChooseOne => CardPile::Offered,
+    }
+  }
+  /// The cards the choice is made from, before checking which ones are eligible.
+  pub fn candidates(self, state: &CombatState) -> Box<dyn Iterator<Item = &SingleCard> + '_> {
+    match self {
+      CardChoiceType::ScryDiscard { cards } => {
+        Box::new(state.draw_pile_cards().take(cards as usize))
+      }
+      _ => state.card_pile(self.pile()),
+    }
+  }
+  pub fn eligible(self, card: &SingleCard) -> bool {
+    match self {
+      CardChoiceType::UpgradeCard => card.can_upgrade(),
+      CardChoiceType::DuplicateAttackOrPower { .. } => {
+        matches!(card.card_info.card_type, CardType::Attack | CardType::Power)
+      }
+      // ethereal cards are exhausted at end of turn whether they're retained or not
+      CardChoiceType::RetainCard { .. } => !card.retain && !card.retains() && !card.ethereal(),
+      _ => true,
+    }
+  }
+  /// Whether the player may pick no card at all ("up to" selections).
+  pub fn optional(self) -> bool {
+    matches!(
+      self,
+      CardChoiceType::RetainCard { .. }
+        | CardChoiceType::ScryDiscard { .. }
+        | CardChoiceType::ExhaustAnyCards
+        | CardChoiceType::GambleDiscard { .. }
+    )
+  }
 }
-*/
 
 pub const HARD_ACTION_LIMIT: i32 = 10000;
 
@@ -82,6 +198,9 @@ impl DamageInfoNoPowers {
         owner,
         damage = at_damage_give(damage, self.damage_type)
       );
+      if owner == CreatureIndex::Player && self.damage_type == DamageType::Normal {
+        damage *= state.player.stance.damage_give_multiplier();
+      }
     }
     DamageInfoOwnerPowers {
       owner: self.owner,
@@ -99,6 +218,15 @@ impl DamageInfoNoPowers {
       .apply_owner_powers(state)
       .apply_target_powers(state, target)
   }
+  pub fn apply_all_powers_to_monsters(
+    &self,
+    state: &CombatState,
+  ) -> ArrayVec<DamageInfoAllPowers, MAX_MONSTERS> {
+    let info = self.apply_owner_powers(state);
+    (0..state.monsters.len())
+      .map(|index| info.apply_target_powers(state, CreatureIndex::Monster(index)))
+      .collect()
+  }
   pub fn ignore_powers(&self) -> DamageInfoAllPowers {
     DamageInfoAllPowers {
       owner: self.owner,
@@ -115,6 +243,9 @@ impl DamageInfoOwnerPowers {
     target: CreatureIndex,
   ) -> DamageInfoAllPowers {
     let mut damage = self.intermediate.0;
+    if target == CreatureIndex::Player && self.damage_type == DamageType::Normal {
+      damage *= state.player.stance.damage_receive_multiplier();
+    }
     power_hook!(
       state,
       target,
@@ -142,7 +273,6 @@ impl DamageInfoOwnerPowers {
 pub enum PowerType {
   Buff,
   Debuff,
-  Relic,
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize, Debug, Derivative)]
@@ -313,8 +443,23 @@ impl<'a, Seed: MaybeSeedView<CombatState>> Runner for StandardRunner<'a, Seed> {
   }
   fn apply_choice(&mut self, choice: &Choice) {
     assert!(self.state().fresh_subaction_queue.is_empty());
-    assert!(self.state().stale_subaction_stack.is_empty());
-    assert!(self.state().actions.is_empty());
+    if let Some(pending) = self.state().pending_choice() {
+      let resolves_pending = match choice {
+        DynAction::SelectCard(select) => select.choice_type == pending.choice_type,
+        DynAction::SkipCardChoice(skip) => skip.choice_type == pending.choice_type,
+        _ => false,
+      };
+      assert!(
+        resolves_pending,
+        "{:?} doesn't resolve the pending {:?}",
+        choice, pending
+      );
+      // the choice resolves the pending action, so it replaces it
+      self.state.stale_subaction_stack.pop();
+    } else {
+      assert!(self.state().stale_subaction_stack.is_empty());
+      assert!(self.state().actions.is_empty());
+    }
     if let Some(hooks) = &mut self.hooks {
       hooks.on_choice(&self.state, choice);
     }
@@ -356,26 +501,104 @@ impl Creature {
 }
 
 impl CombatState {
+  pub fn has_relic(&self, relic_id: RelicId) -> bool {
+    self.relics.iter().any(|relic| relic.relic_id == relic_id)
+  }
+  pub fn living_minions(&self) -> usize {
+    self
+      .monsters
+      .iter()
+      .filter(|monster| !monster.gone && monster.creature.has_power(PowerId::Minion))
+      .count()
+  }
+  /// Where a newly spawned monster goes: the place of a minion that's gone, or a new place at the
+  /// end if there's room.
+  pub fn next_spawn_slot(&self) -> Option<usize> {
+    self
+      .monsters
+      .iter()
+      .position(|monster| monster.gone && monster.creature.has_power(PowerId::Minion))
+      .or_else(|| Some(self.monsters.len()).filter(|&len| len < MAX_MONSTERS))
+  }
   pub fn combat_over(&self) -> bool {
     self.player.creature.hitpoints <= 0
-      || self.monsters.iter().all(|monster| monster.gone)
+      || self.player.escaped
+      || self
+        .monsters
+        .iter()
+        .all(|monster| monster.gone && !monster.half_dead)
       || self.num_actions >= HARD_ACTION_LIMIT
   }
   pub fn choice_next(&self) -> bool {
-    (!self.combat_over()) && self.stale_subaction_stack.is_empty()
+    (!self.combat_over())
+      && (self.stale_subaction_stack.is_empty() || self.pending_choice().is_some())
+  }
+
+  /// The action that's waiting for the player to make a mid-action choice, if any.
+  pub fn pending_choice(&self) -> Option<&ChooseCardAction> {
+    match self.stale_subaction_stack.last() {
+      Some(DynAction::ChooseCardAction(action)) => Some(action),
+      _ => None,
+    }
+  }
+
+  pub fn card_pile(&self, pile: CardPile) -> Box<dyn Iterator<Item = &SingleCard> + '_> {
+    match pile {
+      CardPile::Hand => Box::new(self.hand.iter()),
+      CardPile::DrawPile => Box::new(self.draw_pile_cards()),
+      CardPile::DiscardPile => Box::new(self.discard_pile.iter()),
+      CardPile::ExhaustPile => Box::new(self.exhaust_pile.iter()),
+      CardPile::Offered => Box::new(self.offered_cards.iter()),
+    }
+  }
+
+  pub fn draw_pile_len(&self) -> usize {
+    self.draw_pile_top.len() + self.draw_pile.len()
+  }
+
+  /// All cards in the draw pile, starting with the ones known to be on top.
+  pub fn draw_pile_cards(&self) -> impl Iterator<Item = &SingleCard> {
+    self.draw_pile_top.iter().rev().chain(&self.draw_pile)
+  }
+
+  pub fn put_card_on_top_of_draw_pile(&mut self, card: SingleCard) {
+    self.draw_pile_top.push(card);
+  }
+
+  pub fn remove_from_draw_pile(&mut self, card: &SingleCard) -> Option<SingleCard> {
+    if let Some(index) = self.draw_pile_top.iter().rposition(|c| c == card) {
+      Some(self.draw_pile_top.remove(index))
+    } else if let Some(index) = self.draw_pile.iter().position(|c| c == card) {
+      Some(self.draw_pile.remove(index))
+    } else {
+      None
+    }
+  }
+
+  /// Move the discard pile into the (empty) draw pile. The new draw pile is entirely unknown order.
+  pub fn reshuffle_discard_pile(&mut self) {
+    debug_assert_eq!(self.draw_pile_len(), 0);
+    self.num_reshuffles += 1;
+    // I considered sorting to prevent some rare kinds of draw order manipulation,
+    // but it turned out to cause about a 4% slowdown in playouts, which seems troublesome
+    //self.discard_pile.sort();
+    std::mem::swap(&mut self.draw_pile, &mut self.discard_pile);
   }
 
   pub fn card_playable(&self, card: &SingleCard) -> bool {
     assert!(X_COST == -1);
     assert!(UNPLAYABLE == -2);
     card.cost >= -1
-      && self.player.energy >= card.cost
+      && self.player.energy >= card.cost_in_practice(self)
       && card.card_info.id.playable(self)
       && !(card.card_info.card_type == CardType::Attack
         && self.player.creature.has_power(PowerId::Entangled))
   }
 
   pub fn legal_choices(&self) -> Vec<Choice> {
+    if let Some(pending) = self.pending_choice() {
+      return pending.legal_choices(self);
+    }
     let mut result = Vec::with_capacity(10);
     result.push(EndTurn.into());
     for (index, card) in self.hand.iter().enumerate() {
@@ -385,6 +608,7 @@ impl CombatState {
         && self.card_playable(card)
       {
         if card.card_info.has_target {
+          // this also rules out half-dead monsters
           for (monster_index, monster) in self.monsters.iter().enumerate() {
             if !monster.gone {
               result.push(
@@ -505,6 +729,12 @@ impl Display for Choice {
       Choice::EndTurn(_) => {
         write!(f, "EndTurn")
       }
+      Choice::SelectCard(SelectCard { card, choice_type }) => {
+        write!(f, "{:?} {}", choice_type, card)
+      }
+      Choice::SkipCardChoice(SkipCardChoice { choice_type }) => {
+        write!(f, "{:?} (none)", choice_type)
+      }
       _ => {
         write!(f, "<invalid Choice: {:?}>", self)
       }
@@ -525,3 +755,293 @@ impl<'a> Display for DisplayChoices<'a> {
     write!(f, "[{}]", list)
   }
 }
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::seed_system::TrivialSeed;
+  use rand::SeedableRng;
+  use rand_pcg::Pcg64Mcg;
+
+  /// The player's first turn against `monsters`, with `hand` in hand and 3 energy. Everything
+  /// else is empty, so tests can add whatever they're about.
+  pub fn combat(hand: &[CardId], monsters: &[MonsterId]) -> CombatState {
+    CombatState {
+      hand: hand.iter().map(|&id| SingleCard::create(id)).collect(),
+      player: Player {
+        creature: Creature {
+          hitpoints: 70,
+          max_hitpoints: 80,
+          ..Default::default()
+        },
+        energy: 3,
+        ..Default::default()
+      },
+      monsters: monsters
+        .iter()
+        .map(|&monster_id| Monster {
+          monster_id,
+          creature: Creature {
+            hitpoints: 40,
+            max_hitpoints: 40,
+            ..Default::default()
+          },
+          ..Default::default()
+        })
+        .collect(),
+      turn_number: 1,
+      ..Default::default()
+    }
+  }
+
+  pub fn runner(state: &mut CombatState) -> StandardRunner<TrivialSeed> {
+    StandardRunner::new(state, TrivialSeed::new(Pcg64Mcg::seed_from_u64(0)))
+  }
+
+  /// Makes `choice` and runs everything it queues, up to the next choice.
+  pub fn choose(state: &mut CombatState, choice: impl Into<Choice>) {
+    runner(state).apply_choice(&choice.into());
+  }
+
+  /// Queues `action` at the bottom and runs until the next choice.
+  pub fn run(state: &mut CombatState, action: impl Action) {
+    let mut runner = runner(state);
+    runner.action_bottom(action);
+    runner.run_until_unable();
+  }
+
+  /// Plays the first card in hand with this id.
+  pub fn play(state: &mut CombatState, card_id: CardId, target: usize) {
+    let card = state
+      .hand
+      .iter()
+      .find(|card| card.card_info.id == card_id)
+      .unwrap_or_else(|| panic!("there's no {:?} in hand", card_id))
+      .clone();
+    choose(state, PlayCard { card, target });
+  }
+
+  pub fn cards(ids: &[CardId]) -> Vec<SingleCard> {
+    ids.iter().map(|&id| SingleCard::create(id)).collect()
+  }
+
+  #[test]
+  fn known_top_cards_are_drawn_first() {
+    use CardId::*;
+    let mut state = combat(&[], &[MonsterId::Cultist]);
+    state.draw_pile = cards(&[StrikeR, StrikeR, DefendR]);
+    state.put_card_on_top_of_draw_pile(SingleCard::create(Bash));
+    state.put_card_on_top_of_draw_pile(SingleCard::create(Anger));
+    assert_eq!(state.draw_pile_len(), 5);
+    let order: Vec<CardId> = state
+      .draw_pile_cards()
+      .map(|card| card.card_info.id)
+      .collect();
+    assert_eq!(order, [Anger, Bash, StrikeR, StrikeR, DefendR]);
+
+    assert_eq!(
+      DrawCardRandom.determinism(&state),
+      Determinism::Deterministic
+    );
+    run(&mut state, DrawCards(2));
+    assert_eq!(state.hand.as_slice(), cards(&[Anger, Bash]).as_slice());
+    assert!(state.draw_pile_top.is_empty());
+
+    // after that, any of the rest could be next
+    match DrawCardRandom.determinism(&state) {
+      Determinism::Random(distribution) => {
+        assert_eq!(distribution.0.len(), 3);
+        assert!(distribution.0.iter().all(|&(weight, _)| weight == 1.0));
+      }
+      other => panic!("drawing from an unknown pile is {:?}", other),
+    }
+  }
+
+  #[test]
+  fn shuffled_in_cards_land_anywhere() {
+    use CardId::*;
+    let mut state = combat(&[], &[MonsterId::Cultist]);
+    let wound = ShuffleNewCardIntoDrawPile(SingleCard::create(Wound));
+    // with nothing known, it just joins the unknown pile
+    assert_eq!(wound.determinism(&state), Determinism::Deterministic);
+
+    state.draw_pile = cards(&[StrikeR, StrikeR, DefendR]);
+    state.put_card_on_top_of_draw_pile(SingleCard::create(Bash));
+    state.put_card_on_top_of_draw_pile(SingleCard::create(Anger));
+    // it can go under either of the known cards, or anywhere among the 3 unknown ones
+    match wound.determinism(&state) {
+      Determinism::Random(distribution) => {
+        assert_eq!(distribution.0.as_slice(), &[(1.0, 0), (1.0, 1), (4.0, 2)])
+      }
+      other => panic!("shuffling into a known pile is {:?}", other),
+    }
+
+    let mut under_anger = state.clone();
+    wound.execute_random(&mut runner(&mut under_anger), 1);
+    let order: Vec<CardId> = under_anger
+      .draw_pile_cards()
+      .map(|card| card.card_info.id)
+      .collect();
+    assert_eq!(order, [Anger, Wound, Bash, StrikeR, StrikeR, DefendR]);
+
+    wound.execute_random(&mut runner(&mut state), 2);
+    assert_eq!(state.draw_pile_top, cards(&[Bash, Anger]));
+    assert_eq!(state.draw_pile.len(), 4);
+  }
+
+  #[test]
+  fn drawing_from_an_empty_pile_reshuffles() {
+    use CardId::*;
+    let mut state = combat(&[], &[MonsterId::Cultist]);
+    state.discard_pile = cards(&[StrikeR, DefendR]);
+    run(&mut state, DrawCards(1));
+    assert_eq!(state.num_reshuffles, 1);
+    assert_eq!(state.hand.len(), 1);
+    assert_eq!(state.draw_pile_len(), 1);
+    assert!(state.discard_pile.is_empty());
+  }
+
+  #[test]
+  fn cards_can_be_taken_from_anywhere_in_the_draw_pile() {
+    use CardId::*;
+    let mut state = combat(&[], &[MonsterId::Cultist]);
+    state.draw_pile = cards(&[StrikeR, DefendR]);
+    state.put_card_on_top_of_draw_pile(SingleCard::create(Bash));
+    assert_eq!(
+      state.remove_from_draw_pile(&SingleCard::create(DefendR)),
+      Some(SingleCard::create(DefendR))
+    );
+    assert_eq!(
+      state.remove_from_draw_pile(&SingleCard::create(Bash)),
+      Some(SingleCard::create(Bash))
+    );
+    assert_eq!(state.remove_from_draw_pile(&SingleCard::create(Bash)), None);
+    assert_eq!(state.draw_pile_len(), 1);
+  }
+
+  #[test]
+  fn card_damage_uses_the_powers_when_its_played() {
+    let mut state = combat(&[CardId::Bash, CardId::TwinStrike], &[MonsterId::JawWorm]);
+    play(&mut state, CardId::Bash, 0);
+    assert_eq!(state.monsters[0].creature.hitpoints, 32);
+    assert_eq!(
+      state.monsters[0].creature.power_amount(PowerId::Vulnerable),
+      2
+    );
+    // both hits of Twin Strike are against the Vulnerable that Bash applied
+    play(&mut state, CardId::TwinStrike, 0);
+    assert_eq!(state.monsters[0].creature.hitpoints, 32 - 7 - 7);
+  }
+
+  #[test]
+  fn monster_damage_uses_the_powers_when_it_resolves() {
+    use crate::simulation_state::monsters::exordium::{JawWormIntent, SlaverRedIntent};
+    use crate::simulation_state::monsters::Intent;
+    let mut state = combat(&[], &[MonsterId::SlaverRed, MonsterId::JawWorm]);
+    state.monsters[0].move_history = vec![SlaverRedIntent::Scrape.id()];
+    state.monsters[1].move_history = vec![JawWormIntent::Chomp.id()];
+    assert_eq!(state.total_monster_attack_intent_damage(), 8 + 11);
+
+    choose(&mut state, EndTurn);
+    // Scrape resolves completely, making the player Vulnerable, before Chomp hits
+    assert_eq!(state.player.creature.hitpoints, 70 - 8 - 16);
+  }
+
+  #[test]
+  fn a_card_choice_waits_for_the_player() {
+    use CardId::*;
+    let mut state = combat(&[Armaments, StrikeR, DefendR], &[MonsterId::JawWorm]);
+    play(&mut state, Armaments, 0);
+    assert_eq!(
+      state.pending_choice(),
+      Some(&ChooseCardAction {
+        choice_type: CardChoiceType::UpgradeCard
+      })
+    );
+    assert!(state.choice_next());
+    assert_eq!(state.legal_choices().len(), 2);
+
+    choose(
+      &mut state,
+      SelectCard {
+        card: SingleCard::create(StrikeR),
+        choice_type: CardChoiceType::UpgradeCard,
+      },
+    );
+    assert_eq!(state.pending_choice(), None);
+    assert!(state.stale_subaction_stack.is_empty());
+    assert_eq!(state.player.creature.block, 5);
+    let strike = state
+      .hand
+      .iter()
+      .find(|card| card.card_info.id == StrikeR)
+      .unwrap();
+    assert_eq!(strike.upgrades, 1);
+  }
+
+  #[test]
+  fn an_optional_card_choice_can_be_skipped() {
+    use CardId::*;
+    let mut state = combat(&[StrikeR, DefendR], &[MonsterId::JawWorm]);
+    let choice_type = CardChoiceType::ExhaustAnyCards;
+    run(&mut state, ChooseCardAction { choice_type });
+    assert!(state.pending_choice().is_some());
+    assert!(state
+      .legal_choices()
+      .contains(&SkipCardChoice { choice_type }.into()));
+
+    choose(&mut state, SkipCardChoice { choice_type });
+    assert_eq!(state.pending_choice(), None);
+    assert_eq!(state.hand.len(), 2);
+    assert!(state.exhaust_pile.is_empty());
+  }
+
+  #[test]
+  #[should_panic(expected = "doesn't resolve the pending")]
+  fn a_choice_of_the_wrong_type_is_rejected() {
+    use CardId::*;
+    let mut state = combat(&[Armaments, StrikeR, DefendR], &[MonsterId::JawWorm]);
+    play(&mut state, Armaments, 0);
+    choose(
+      &mut state,
+      SelectCard {
+        card: SingleCard::create(StrikeR),
+        choice_type: CardChoiceType::ExhaustCard,
+      },
+    );
+  }
+
+  #[test]
+  fn intangible_caps_damage_before_block_and_buffer_sees_what_gets_through() {
+    let mut state = combat(&[], &[MonsterId::JawWorm]);
+    for &power_id in &[PowerId::IntangiblePlayer, PowerId::Buffer] {
+      run(
+        &mut state,
+        ApplyPowerAction {
+          source: CreatureIndex::Player,
+          target: CreatureIndex::Player,
+          power_id,
+          amount: 1,
+        },
+      );
+    }
+    let hit = DamageAction {
+      target: CreatureIndex::Player,
+      info: DamageInfoNoPowers::new(Some(CreatureIndex::Monster(0)), 10, DamageType::Normal)
+        .ignore_powers(),
+    };
+    // capped to 1 before block, so block takes all of it and Buffer isn't used
+    state.player.creature.block = 5;
+    run(&mut state, hit.clone());
+    assert_eq!(state.player.creature.block, 4);
+    assert_eq!(state.player.creature.hitpoints, 70);
+    assert_eq!(state.player.creature.power_amount(PowerId::Buffer), 1);
+
+    state.player.creature.block = 0;
+    run(&mut state, hit.clone());
+    assert_eq!(state.player.creature.hitpoints, 70);
+    assert!(!state.player.creature.has_power(PowerId::Buffer));
+    run(&mut state, hit);
+    assert_eq!(state.player.creature.hitpoints, 69);
+  }
+}
@@ -9,14 +9,26 @@ use std::hash::{Hash, Hasher};
 use crate::actions::*;
 use crate::communication_mod_state as communication;
 use crate::simulation::*;
+use crate::simulation_state::powers::PowerBehavior;
 
 pub mod cards;
+pub mod fidelity;
+pub mod import_report;
 pub mod monsters;
+pub mod orbs;
 pub mod powers;
+pub mod relics;
+pub mod stances;
 
 pub use cards::CardId;
+pub use fidelity::{Fidelity, FidelityAudit};
+pub use import_report::{ImportFidelity, ImportReport};
+pub use monsters::unknown::{ObservedIntent, UnknownMonsterInfo, UnknownMonsterModel};
 pub use monsters::MonsterId;
+pub use orbs::{Orb, OrbType, MAX_ORB_SLOTS};
 pub use powers::PowerId;
+pub use relics::{Relic, RelicId};
+pub use stances::Stance;
 use std::cmp::Ordering;
 
 pub const MAX_MONSTERS: usize = 7;
@@ -45,6 +57,10 @@ pub struct CombatState {
     Hash(hash_with = "hash_cards_unordered")
   )]
   pub draw_pile: Vec<SingleCard>,
+  /// Cards known to be on top of the draw pile, because an effect put them there.
+  /// The last one is the top card; everything in `draw_pile` is below them, in unknown order.
+  #[serde(default)]
+  pub draw_pile_top: Vec<SingleCard>,
   #[derivative(
     PartialEq(compare_with = "compare_cards_unordered"),
     Hash(hash_with = "hash_cards_unordered")
@@ -65,13 +81,44 @@ pub struct CombatState {
     Hash(hash_with = "hash_cards_unordered")
   )]
   pub limbo: Vec<SingleCard>,
+  /// The cards offered by an effect like Attack Potion, while the player picks one.
+  #[serde(default)]
+  #[derivative(
+    PartialEq(compare_with = "compare_cards_unordered"),
+    Hash(hash_with = "hash_cards_unordered")
+  )]
+  pub offered_cards: Vec<SingleCard>,
   pub card_in_play: Option<SingleCard>,
+  /// Whose card and potion pools random cards and potions are generated from.
+  #[serde(default)]
+  pub character: Character,
   pub potion_slots: usize,
   pub potions: Vec<&'static CardInfo>,
+  #[serde(default)]
+  pub relics: Vec<Relic>,
   pub player: Player,
   pub monsters: ArrayVec<Monster, MAX_MONSTERS>,
   pub turn_number: i32,
   pub turn_has_ended: bool,
+  /// Set by Vault: the monsters don't take their next turn, so the player takes another one.
+  #[serde(default)]
+  pub skip_monster_turn: bool,
+  #[serde(default)]
+  pub cards_discarded_this_turn: i32,
+  #[serde(default)]
+  pub attacks_played_this_turn: i32,
+  #[serde(default)]
+  pub cards_played_this_turn: i32,
+  #[serde(default)]
+  pub lightning_channeled_this_combat: i32,
+  #[serde(default)]
+  pub frost_channeled_this_combat: i32,
+  #[serde(default)]
+  pub mantra_gained_this_combat: i32,
+  /// The type of the card played before the one currently being played (Crush Joints, Follow-Up,
+  /// Sanctity, Sash Whip).
+  #[serde(default)]
+  pub last_card_type_played: Option<CardType>,
 
   pub fresh_subaction_queue: Vec<DynAction>,
   pub stale_subaction_stack: Vec<DynAction>,
@@ -93,6 +140,15 @@ pub struct SingleCard {
   pub cost: i32,
   pub upgrades: i32,
   pub card_info: &'static CardInfo,
+  /// Stays in hand at the end of this turn (Well-Laid Plans).
+  #[serde(default)]
+  pub retain: bool,
+  /// Costs 0 until the end of this turn (Bullet Time).
+  #[serde(default)]
+  pub free_this_turn: bool,
+  /// Costs 0 the next time it's played (Setup).
+  #[serde(default)]
+  pub free_to_play_once: bool,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
@@ -114,16 +170,49 @@ pub enum Rarity {
   Special,
 }
 
+#[derive(
+  Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug, Derivative,
+)]
+#[derivative(Default)]
+pub enum Character {
+  #[derivative(Default)]
+  Ironclad,
+  Silent,
+  Defect,
+  Watcher,
+}
+
+impl Character {
+  pub fn from_communication_mod(class: &str) -> Option<Character> {
+    match class {
+      "IRONCLAD" => Some(Character::Ironclad),
+      "THE_SILENT" => Some(Character::Silent),
+      "DEFECT" => Some(Character::Defect),
+      "WATCHER" => Some(Character::Watcher),
+      _ => None,
+    }
+  }
+}
+
 #[derive(Clone, Eq)]
 pub struct CardInfo {
   pub id: CardId,
   pub card_type: CardType,
   pub rarity: Rarity,
+  pub character: Option<Character>,
   pub normal_cost: i32,
   pub upgraded_cost: i32,
   pub ethereal: bool,
   pub has_target: bool,
   pub exhausts: bool,
+  pub upgrade_removes_exhaust: bool,
+  pub upgrade_removes_ethereal: bool,
+  pub retains: bool,
+  pub upgrade_adds_retain: bool,
+  pub innate: bool,
+  pub upgrade_adds_innate: bool,
+  pub shuffles_back_into_draw_pile: bool,
+  pub fidelity: Fidelity,
 }
 
 impl PartialEq for CardInfo {
@@ -175,11 +264,20 @@ impl CardInfo {
       id: CardId::Injury,
       card_type: CardType::Curse,
       rarity: Rarity::Special,
+      character: None,
       normal_cost: UNPLAYABLE,
       upgraded_cost: -3,
       ethereal: false,
       has_target: false,
       exhausts: false,
+      upgrade_removes_exhaust: false,
+      upgrade_removes_ethereal: false,
+      retains: false,
+      upgrade_adds_retain: false,
+      innate: false,
+      upgrade_adds_innate: false,
+      shuffles_back_into_draw_pile: false,
+      fidelity: Fidelity::Complete,
     }
   }
 }
@@ -201,6 +299,16 @@ pub struct Creature {
 pub struct Player {
   pub creature: Creature,
   pub energy: i32,
+  /// Channeled orbs, starting with the leftmost one, which is the next to be evoked.
+  #[serde(default)]
+  pub orbs: ArrayVec<Orb, MAX_ORB_SLOTS>,
+  #[serde(default)]
+  pub orb_slots: usize,
+  #[serde(default)]
+  pub stance: Stance,
+  /// Whether the player used a Smoke Bomb, which ends the combat without winning it.
+  #[serde(default)]
+  pub escaped: bool,
 }
 
 pub type IntentId = i32;
@@ -214,6 +322,13 @@ pub struct Monster {
   pub creature: Creature,
   pub move_history: Vec<IntentId>,
   pub gone: bool,
+  /// Darklings and the Awakened One can fall without dying. Half-dead monsters are also `gone`,
+  /// so nothing can target or damage them, but they still take their turns.
+  #[serde(default)]
+  pub half_dead: bool,
+  /// Only for `UnknownMonster`.
+  #[serde(default)]
+  pub unknown_monster: Option<UnknownMonsterInfo>,
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
@@ -241,48 +356,86 @@ impl Default for Power {
   }
 }
 
-/*#[derive(Clone, Serialize, Deserialize, Debug)]
-pub struct Relic {
-  name: String,
-  id: String,
-  counter: i32,
-}*/
-
 impl CombatState {
+  /// Also returns everything the conversion didn't recognize or had to guess, so callers can
+  /// decide how far to trust the result. Monsters we don't simulate follow
+  /// `unknown_monster_model`.
   pub fn from_communication_mod(
     observed: &communication::GameState,
     previous: Option<&CombatState>,
-  ) -> Option<CombatState> {
+    unknown_monster_model: UnknownMonsterModel,
+  ) -> Option<(CombatState, ImportReport)> {
     let combat = observed.combat_state.as_ref()?;
+    let mut report = ImportReport::check(observed);
+    if Character::from_communication_mod(&observed.class).is_none() {
+      report.assume(format!(
+        "unknown class {:?} is {:?}",
+        observed.class,
+        Character::default()
+      ));
+    }
+    if let Some(stance) = &combat.player.stance {
+      if Stance::from_communication_mod(stance).is_none() {
+        report.assume(format!("unknown stance {:?} is Neutral", stance));
+      }
+    }
+    if observed
+      .relics
+      .iter()
+      .any(|relic| RelicId::from(&*relic.id) == RelicId::CentennialPuzzle)
+    {
+      report.assume("Centennial Puzzle hasn't triggered yet");
+    }
     let mut draw_pile: Vec<SingleCard> = combat.draw_pile.iter().map(From::from).collect();
     // explicitly sort, partly to make sure my AI doesn't accidentally cheat
     draw_pile.sort();
 
     let mut result = CombatState {
       draw_pile,
+      draw_pile_top: Vec::new(),
       discard_pile: combat.discard_pile.iter().map(From::from).collect(),
       exhaust_pile: combat.exhaust_pile.iter().map(From::from).collect(),
       hand: combat.hand.iter().map(From::from).collect(),
       limbo: combat.limbo.iter().map(From::from).collect(),
+      offered_cards: Vec::new(),
       card_in_play: combat.card_in_play.as_ref().map(From::from),
+      character: Character::from_communication_mod(&observed.class).unwrap_or_default(),
       potion_slots: observed.potions.len(),
+      // empty slots show up as "Potion Slot", and unknown potions would turn into Injury
       potions: observed
         .potions
         .iter()
-        .filter(|p| &p.id != "Potion Slot")
         .map(|p| <&CardInfo>::from(CardId::from(&*p.id)))
+        .filter(|info| info.card_type == CardType::Potion)
+        .collect(),
+      relics: observed
+        .relics
+        .iter()
+        .filter_map(Relic::from_communication_mod)
         .collect(),
       fresh_subaction_queue: Vec::new(),
       stale_subaction_stack: Vec::new(),
       actions: VecDeque::new(),
       card_queue: VecDeque::new(),
-      player: Player::from_communication_mod(&combat.player, &observed.relics),
+      player: Player::from_communication_mod(&combat.player),
       turn_number: combat.turn,
       turn_has_ended: false,
+      skip_monster_turn: false,
+      cards_discarded_this_turn: combat.cards_discarded_this_turn,
+      // CommunicationMod doesn't report these; they only matter for Finisher, FTL, Echo Form,
+      // Thunder Strike, Blizzard, Brilliance and the cards that check the previous card played
+      attacks_played_this_turn: 0,
+      cards_played_this_turn: 0,
+      lightning_channeled_this_combat: 0,
+      frost_channeled_this_combat: 0,
+      mantra_gained_this_combat: 0,
+      last_card_type_played: None,
       monsters: combat
         .monsters
         .iter()
-        .map(|monster| Monster::from_communication_mod(monster, observed.ascension_level))
+        .map(|monster| {
+          Monster::from_communication_mod(monster, observed.ascension_level, unknown_monster_model)
+        })
         .collect(),
       num_reshuffles: 0,
       num_actions: 0,
@@ -301,6 +454,12 @@ impl CombatState {
               );
             }
           }*/
+        if let (Some(earlier), Some(info)) =
+          (&monster.unknown_monster, &mut new_version.unknown_monster)
+        {
+          info.model = earlier.model;
+          info.remember(earlier);
+        }
         if new_version.move_history.len() == 3 && monster.move_history.len() > 3 {
           if new_version.move_history[..] == monster.move_history[monster.move_history.len() - 3..]
           {
@@ -322,6 +481,7 @@ impl CombatState {
           if amount == 0 {
             // if we forgot, just make an assumption I guess?
             amount = 50;
+            report.assume("The Guardian's Mode Shift threshold is 50");
           }
           new_version.creature.powers.push(Power {
             power_id: PowerId::ModeShiftDamageThreshold,
@@ -338,6 +498,7 @@ impl CombatState {
           if amount == 0 {
             // if we forgot, just make an assumption I guess?
             amount = 50;
+            report.assume("The Guardian's Mode Shift threshold is 50");
           }
           new_version.creature.powers.push(Power {
             power_id: PowerId::ModeShiftDamageThreshold,
@@ -347,7 +508,7 @@ impl CombatState {
         }
       }
     }
-    Some(result)
+    Some((result, report))
   }
 }
 
@@ -358,6 +519,9 @@ impl From<&communication::Card> for SingleCard {
       cost: card.cost,
       upgrades: card.upgrades,
       card_info: <&CardInfo>::from(CardId::from(&*card.id)),
+      retain: false,
+      free_this_turn: false,
+      free_to_play_once: false,
     }
   }
 }
@@ -375,43 +539,49 @@ impl From<&communication::Power> for Power {
   }
 }
 
-impl From<&communication::Relic> for Power {
-  fn from(relic: &communication::Relic) -> Power {
-    Power {
-      power_id: PowerId::from(&*relic.id),
-      amount: relic.counter,
-      damage: 0,
-      card: None,
-      misc: 0,
-      just_applied: false,
-    }
-  }
-}
-
 impl Player {
-  fn from_communication_mod(
-    player: &communication::Player,
-    relics: &[communication::Relic],
-  ) -> Player {
+  fn from_communication_mod(player: &communication::Player) -> Player {
+    let mut powers: Vec<Power> = player.powers.iter().map(From::from).collect();
+    // same order ApplyPowerAction keeps them in
+    powers.sort_by_key(|power| power.power_id.priority());
     Player {
       energy: player.energy,
       creature: Creature {
         hitpoints: player.current_hp,
         max_hitpoints: player.max_hp,
         block: player.block,
-        powers: relics
-          .iter()
-          .map(Power::from)
-          .filter(|p| p.power_id != PowerId::Unknown)
-          .chain(player.powers.iter().map(From::from))
-          .collect(),
+        powers,
       },
+      orbs: player
+        .orbs
+        .iter()
+        .filter_map(|orb| {
+          let orb_type = OrbType::from_communication_mod(&orb.id)?;
+          Some(Orb {
+            orb_type,
+            // for Dark orbs, this is the damage they've stored up
+            evoke_amount: orb.evoke_amount,
+          })
+        })
+        .collect(),
+      // empty slots are reported as orbs too
+      orb_slots: player.orbs.len(),
+      stance: player
+        .stance
+        .as_deref()
+        .and_then(Stance::from_communication_mod)
+        .unwrap_or_default(),
+      escaped: false,
     }
   }
 }
 
 impl Monster {
-  fn from_communication_mod(monster: &communication::Monster, ascension: i32) -> Monster {
+  fn from_communication_mod(
+    monster: &communication::Monster,
+    ascension: i32,
+    unknown_monster_model: UnknownMonsterModel,
+  ) -> Monster {
     let monster_id = MonsterId::from(&*monster.id);
     let mut move_history = vec![monster_id
       .intent_from_communication_mod(monster.move_id)
@@ -437,6 +607,14 @@ impl Monster {
     } else {
       None
     };
+    let unknown_monster = if monster_id == MonsterId::UnknownMonster {
+      Some(UnknownMonsterInfo::new(
+        ObservedIntent::from_communication_mod(monster),
+        unknown_monster_model,
+      ))
+    } else {
+      None
+    };
     Monster {
       monster_id,
       ascension,
@@ -448,7 +626,9 @@ impl Monster {
         block: monster.block,
         powers: monster.powers.iter().map(From::from).collect(),
       },
-      gone: monster.is_gone,
+      gone: monster.is_gone || monster.half_dead,
+      half_dead: monster.half_dead,
+      unknown_monster,
     }
   }
 }
@@ -469,19 +649,57 @@ impl SingleCard {
       cost: card_info.normal_cost,
       upgrades: 0,
       card_info,
+      retain: false,
+      free_this_turn: false,
+      free_to_play_once: false,
     }
   }
 
+  pub fn ethereal(&self) -> bool {
+    self.card_info.ethereal && !(self.upgrades > 0 && self.card_info.upgrade_removes_ethereal)
+  }
+
+  /// Whether the card itself has Retain, as opposed to being retained by an effect this turn.
+  pub fn retains(&self) -> bool {
+    self.card_info.retains || (self.upgrades > 0 && self.card_info.upgrade_adds_retain)
+  }
+
+  pub fn innate(&self) -> bool {
+    self.card_info.innate || (self.upgrades > 0 && self.card_info.upgrade_adds_innate)
+  }
+
+  pub fn can_upgrade(&self) -> bool {
+    self.upgrades == 0 && !matches!(self.card_info.card_type, CardType::Status | CardType::Curse)
+  }
+
   pub fn upgrade(&mut self) {
     if self.upgrades == 0 {
       self.upgrades = 1;
+      if self.cost == self.card_info.normal_cost {
+        self.cost = self.card_info.upgraded_cost;
+      }
+    }
+  }
+
+  /// The cost, unless an effect makes the card free for now.
+  pub fn cost_for_turn(&self) -> i32 {
+    if self.cost > 0 && (self.free_this_turn || self.free_to_play_once) {
+      0
+    } else {
+      self.cost
     }
   }
 
   pub fn cost_in_practice(&self, state: &CombatState) -> i32 {
-    match self.cost {
+    match self.cost_for_turn() {
       X_COST => state.player.energy,
-      0..=999 => self.cost,
+      // Swivel
+      _ if self.card_info.card_type == CardType::Attack
+        && state.player.creature.has_power(PowerId::FreeAttack) =>
+      {
+        0
+      }
+      cost @ 0..=999 => cost,
       _ => 0,
     }
   }
@@ -489,13 +707,32 @@ impl SingleCard {
 
 impl Display for Player {
   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-    write!(f, "({}) {}", self.energy, self.creature)
+    write!(f, "({}) {}", self.energy, self.creature)?;
+    if self.orb_slots > 0 {
+      write!(f, " [")?;
+      for index in 0..self.orb_slots {
+        if index > 0 {
+          write!(f, ", ")?;
+        }
+        match self.orbs.get(index) {
+          Some(orb) => write!(f, "{}", orb)?,
+          None => write!(f, "_")?,
+        }
+      }
+      write!(f, "]")?;
+    }
+    if self.stance != Stance::Neutral {
+      write!(f, " {}", self.stance)?;
+    }
+    Ok(())
   }
 }
 
 impl Display for Monster {
   fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-    if self.gone {
+    if self.half_dead {
+      write!(f, "({:?} half-dead)", self.monster_id)
+    } else if self.gone {
       write!(f, "({:?})", self.monster_id)
     } else {
       write!(
@@ -553,8 +790,8 @@ impl Display for SingleCard {
     if self.misc != 0 {
       write!(f, "?{}", self.misc)?;
     }
-    if self.cost != self.start_combat_cost() {
-      write!(f, "({})", self.cost)?;
+    if self.cost_for_turn() != self.start_combat_cost() {
+      write!(f, "({})", self.cost_for_turn())?;
     }
     Ok(())
   }
@@ -568,11 +805,20 @@ impl Display for CombatState {
       write!(f, "{}\n", monster)?;
     }
     write!(f, "\nPlayer: {}", self.player)?;
+    if !self.relics.is_empty() {
+      write!(f, "\nRelics: ")?;
+      for relic in &self.relics {
+        write!(f, "{}, ", relic)?;
+      }
+    }
     write!(f, "\nHand: ")?;
     for card in &self.hand {
       write!(f, "{}, ", card)?;
     }
     write!(f, "\nDraw: ")?;
+    for card in self.draw_pile_top.iter().rev() {
+      write!(f, "{} (top), ", card)?;
+    }
     for card in &self.draw_pile {
       write!(f, "{}, ", card)?;
     }
@@ -590,6 +836,12 @@ impl Display for CombatState {
         write!(f, "{}, ", card)?;
       }
     }
+    if !self.offered_cards.is_empty() {
+      write!(f, "\nOffered: ")?;
+      for card in &self.offered_cards {
+        write!(f, "{}, ", card)?;
+      }
+    }
     if let Some(card) = &self.card_in_play {
       write!(f, "\nCard in play: {}", card)?;
     }
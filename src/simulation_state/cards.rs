@@ -10,6 +10,7 @@ use crate::simulation_state::*;
 
 use self::CardType::{Attack, Curse, Potion, Power, Skill, Status};
 use self::Rarity::{Basic, Common, Rare, Special, Uncommon};
+use crate::simulation_state::fidelity::Fidelity::{Missing, Partial};
 
 pub trait CardSpecies: Sized + Copy + Into<CardId> + CardBehavior {
   const INFO: CardInfo;
@@ -25,6 +26,26 @@ pub trait CardBehavior: Sized {
   fn potion_value(self, state: &CombatState) -> f64 {
     12.0
   }
+  // called before the drawn card is put into the hand
+  #[allow(unused)]
+  fn on_draw(self, runner: &mut impl Runner, card: &mut SingleCard) {}
+  // only for discards caused by effects, not the end-of-turn discard
+  #[allow(unused)]
+  fn on_manual_discard(self, runner: &mut impl Runner, card: &SingleCard) {}
+  #[allow(unused)]
+  fn on_other_card_discarded(self, card: &mut SingleCard) {}
+  // called at the end of the turn, for cards that stay in hand because of Retain
+  #[allow(unused)]
+  fn on_retained(self, card: &mut SingleCard) {}
+  // called for each card in the discard pile when the player's stance changes
+  #[allow(unused)]
+  fn on_stance_change(self, runner: &mut impl Runner, card: &SingleCard) {}
+  // called for each card in the discard pile after the player scries
+  #[allow(unused)]
+  fn on_scry(self, runner: &mut impl Runner, card: &SingleCard) {}
+  // called when the card is picked from the options a ChooseOneAction offers
+  #[allow(unused)]
+  fn on_chosen(self, runner: &mut impl Runner, card: &SingleCard) {}
 }
 
 pub trait CardBehaviorContext {
@@ -33,8 +54,9 @@ pub trait CardBehaviorContext {
   fn target_creature_index(&self) -> CreatureIndex {
     CreatureIndex::Monster(self.target())
   }
+  // Like the actual game, card damage is calculated when the card is played, and all hits use
+  // that value even if powers change before they resolve.
   fn attack_target(&mut self, base_damage: i32) {
-    // hack: this is actually NOT where powers are applied to card/monster damage in the actual code
     let info =
       DamageInfoNoPowers::new(Some(CreatureIndex::Player), base_damage, DamageType::Normal)
         .apply_all_powers(self.state(), self.target_creature_index());
@@ -44,17 +66,15 @@ pub trait CardBehaviorContext {
     });
   }
   fn attack_monsters(&mut self, base_damage: i32) {
-    // hack: this is actually NOT where powers are applied to card/monster damage in the actual code
-    let info =
+    let infos =
       DamageInfoNoPowers::new(Some(CreatureIndex::Player), base_damage, DamageType::Normal)
-        .apply_owner_powers(self.state());
-    self.action(DamageAllEnemiesAction { info });
+        .apply_all_powers_to_monsters(self.state());
+    self.action(DamageAllEnemiesAction { infos });
   }
   fn attack_random_monster(&mut self, base_damage: i32) {
-    // hack: this is actually NOT where powers are applied to card/monster damage in the actual code
+    // the exception: the target isn't known yet, so the damage is calculated when it resolves
     let info =
-      DamageInfoNoPowers::new(Some(CreatureIndex::Player), base_damage, DamageType::Normal)
-        .apply_owner_powers(self.state());
+      DamageInfoNoPowers::new(Some(CreatureIndex::Player), base_damage, DamageType::Normal);
     self.action(AttackDamageRandomEnemyAction { info });
   }
   fn power_monsters(&mut self, power_id: PowerId, amount: i32) {
@@ -83,22 +103,44 @@ pub trait CardBehaviorContext {
       amount,
     });
   }
-  fn block(&mut self, amount: i32) {
+  fn block_amount(&self, amount: i32) -> i32 {
     let mut amount = amount as f64;
     power_hook!(
       self.state(),
       CreatureIndex::Player,
       amount = modify_block(amount)
     );
+    amount as i32
+  }
+  fn block(&mut self, amount: i32) {
     self.action(GainBlockAction {
       creature_index: CreatureIndex::Player,
-      amount: amount as i32,
+      amount: self.block_amount(amount),
     });
   }
+  fn choose_card(&mut self, choice_type: CardChoiceType) {
+    self.action(ChooseCardAction { choice_type });
+  }
   fn draw_cards(&mut self, amount: i32) {
     self.action(DrawCards(amount));
   }
+  fn discard_cards(&mut self, amount: i32) {
+    for _ in 0..amount {
+      self.choose_card(CardChoiceType::DiscardCard);
+    }
+  }
+  fn channel_orb(&mut self, orb_type: OrbType) {
+    self.action(ChannelOrbAction(Orb::new(orb_type)));
+  }
+  fn change_stance(&mut self, stance: Stance) {
+    self.action(ChangeStanceAction(stance));
+  }
+  fn scry(&mut self, amount: i32) {
+    self.action(ScryAction(amount));
+  }
   fn state(&self) -> &CombatState;
+  /// The energy available when the card was played, which is what X-cost cards use.
+  fn energy_on_use(&self) -> i32;
   fn card(&self) -> &SingleCard {
     self.state().card_in_play.as_ref().unwrap()
   }
@@ -113,7 +155,7 @@ pub trait CardBehaviorContext {
     }
   }
   fn potency(&self, base: i32) -> i32 {
-    if self.state().player.creature.has_power(PowerId::SacredBark) {
+    if self.state().has_relic(RelicId::SacredBark) {
       base * 2
     } else {
       base
@@ -124,6 +166,7 @@ pub trait CardBehaviorContext {
 pub struct PlayCardContext<'a, R: Runner> {
   pub runner: &'a mut R,
   pub target: usize,
+  pub energy_on_use: i32,
 }
 
 impl<'a, R: Runner> CardBehaviorContext for PlayCardContext<'a, R> {
@@ -136,6 +179,9 @@ impl<'a, R: Runner> CardBehaviorContext for PlayCardContext<'a, R> {
   fn state(&self) -> &CombatState {
     self.runner.state()
   }
+  fn energy_on_use(&self) -> i32 {
+    self.energy_on_use
+  }
 }
 
 pub struct ConsiderCardContext<'a, F> {
@@ -158,6 +204,9 @@ impl<'a, F: ConsiderAction> CardBehaviorContext for ConsiderCardContext<'a, F> {
   fn card(&self) -> &SingleCard {
     self.card
   }
+  fn energy_on_use(&self) -> i32 {
+    self.state.player.energy
+  }
 }
 
 pub fn consider_card_actions(
@@ -176,7 +225,7 @@ pub fn consider_card_actions(
 }
 
 macro_rules! cards {
-  ($([$id: expr, $Variant: ident, $card_type: expr, $rarity: expr, $cost: expr, $has_target: expr, {$($type_info: tt)*}],)*) => {
+  ($([$id: expr, $Variant: ident, $card_type: expr, $rarity: expr, $character: expr, $cost: expr, $has_target: expr, {$($type_info: tt)*}],)*) => {
     #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Enum, Debug)]
     pub enum CardId {
       $($Variant,)*
@@ -191,6 +240,7 @@ macro_rules! cards {
           id: CardId::$Variant,
           card_type: $card_type,
           rarity: $rarity,
+          character: $character,
           normal_cost: $cost,
           has_target: $has_target,
           $($type_info)*
@@ -208,15 +258,25 @@ macro_rules! cards {
     }
 )*
 
-    impl From<& str> for CardId {
-      fn from (source: & str)->CardId {
+    impl CardId {
+      pub const ALL: &'static [CardId] = &[$(CardId::$Variant,)*];
+    }
+
+    impl CardId {
+      pub fn from_communication_mod(source: & str)->Option<CardId> {
         match source {
-          $($id => CardId::$Variant,)*
-          _ => CardId::Injury,
+          $($id => Some(CardId::$Variant),)*
+          _ => None,
         }
       }
     }
 
+    impl From<& str> for CardId {
+      fn from (source: & str)->CardId {
+        CardId::from_communication_mod(source).unwrap_or(CardId::Injury)
+      }
+    }
+
     impl From <CardId> for &'static CardInfo {
       fn from (source: CardId)->&'static CardInfo {
         match source {
@@ -241,6 +301,41 @@ macro_rules! cards {
           $(CardId::$Variant => $Variant.potion_value(state),)*
         }
       }
+      fn on_draw(self, runner: &mut impl Runner, card: &mut SingleCard) {
+        match self {
+          $(CardId::$Variant => $Variant.on_draw(runner, card),)*
+        }
+      }
+      fn on_manual_discard(self, runner: &mut impl Runner, card: &SingleCard) {
+        match self {
+          $(CardId::$Variant => $Variant.on_manual_discard(runner, card),)*
+        }
+      }
+      fn on_other_card_discarded(self, card: &mut SingleCard) {
+        match self {
+          $(CardId::$Variant => $Variant.on_other_card_discarded(card),)*
+        }
+      }
+      fn on_retained(self, card: &mut SingleCard) {
+        match self {
+          $(CardId::$Variant => $Variant.on_retained(card),)*
+        }
+      }
+      fn on_stance_change(self, runner: &mut impl Runner, card: &SingleCard) {
+        match self {
+          $(CardId::$Variant => $Variant.on_stance_change(runner, card),)*
+        }
+      }
+      fn on_scry(self, runner: &mut impl Runner, card: &SingleCard) {
+        match self {
+          $(CardId::$Variant => $Variant.on_scry(runner, card),)*
+        }
+      }
+      fn on_chosen(self, runner: &mut impl Runner, card: &SingleCard) {
+        match self {
+          $(CardId::$Variant => $Variant.on_chosen(runner, card),)*
+        }
+      }
     }
   }
 }
@@ -248,95 +343,457 @@ macro_rules! cards {
 pub const HAS_TARGET: bool = true;
 pub const NO_TARGET: bool = false;
 
+// the game's card colors, which are the characters whose pools the cards are in
+pub const RED: Option<Character> = Some(Character::Ironclad);
+pub const GREEN: Option<Character> = Some(Character::Silent);
+pub const BLUE: Option<Character> = Some(Character::Defect);
+pub const PURPLE: Option<Character> = Some(Character::Watcher);
+pub const COLORLESS: Option<Character> = None;
+
 cards! {
-  ["Strike_R", StrikeR, Attack, Basic, 1, HAS_TARGET, {}],
-  ["Bash", Bash, Attack, Basic, 2, HAS_TARGET, {}],
-  ["Defend_R", DefendR, Skill, Basic, 1, NO_TARGET, {}],
-
-  ["Anger", Anger, Attack, Common, 0, HAS_TARGET, {}],
-  ["Armaments", Armaments, Skill, Common, 1, NO_TARGET, {}],
-  ["Body Slam", BodySlam, Attack, Common, 1, HAS_TARGET, {upgraded_cost: 0,}],
-  ["Clash", Clash, Attack, Common, 0, HAS_TARGET, {}],
-  ["Cleave", Cleave, Attack, Common, 1, NO_TARGET, {}],
-  ["Clothesline", Clothesline, Attack, Common, 2, HAS_TARGET, {}],
-  ["Flex", Flex, Skill, Common, 0, NO_TARGET, {}],
-  ["Havoc", Havoc, Skill, Common, 1, NO_TARGET, {upgraded_cost: 0,}],
-  ["Headbutt", Headbutt, Attack, Common, 1, HAS_TARGET, {}],
-  ["Heavy Blade", HeavyBlade, Attack, Common, 2, HAS_TARGET, {}],
-  ["Iron Wave", IronWave, Attack, Common, 1, HAS_TARGET, {}],
-  ["Perfected Strike", PerfectedStrike, Attack, Common, 2, HAS_TARGET, {}],
-  ["Pommel Strike", PommelStrike, Attack, Common, 1, HAS_TARGET, {}],
-  ["Shrug It Off", ShrugItOff, Skill, Common, 1, NO_TARGET, {}],
-  ["Sword Boomerang", SwordBoomerang, Attack, Common, 1, NO_TARGET, {}],
-  ["Thunderclap", Thunderclap, Attack, Common, 1, NO_TARGET, {}],
-  ["True Grit", TrueGrit, Skill, Common, 1, NO_TARGET, {}],
-  ["Twin Strike", TwinStrike, Attack, Common, 1, HAS_TARGET, {}],
-  ["Warcry", Warcry, Skill, Common, 0, NO_TARGET, {}],
-  ["Wild Strike", WildStrike, Attack, Common, 1, HAS_TARGET, {}],
-
-  ["Battle Trance", BattleTrance, Skill, Uncommon, 0, NO_TARGET, {}],
-  ["Blood for Blood", BloodForBlood, Attack, Uncommon, 4, HAS_TARGET, {upgraded_cost: 3,}],
-  ["Bloodletting", Bloodletting, Skill, Uncommon, 0, NO_TARGET, {}],
-  ["Burning Pact", BurningPact, Skill, Uncommon, 1, NO_TARGET, {}],
-  ["Carnage", Carnage, Attack, Uncommon, 2, HAS_TARGET, {ethereal: true,}],
-  ["Combust", Combust, Power, Uncommon, 1, NO_TARGET, {}],
-  ["Corruption", Corruption, Power, Uncommon, 3, NO_TARGET, {upgraded_cost: 2,}],
-  ["Disarm", Disarm, Skill, Uncommon, 1, HAS_TARGET, {exhausts: true,}],
-  ["Dropkick", Dropkick, Attack, Uncommon, 1, HAS_TARGET, {}],
-  ["Dual Wield", DualWield, Skill, Uncommon, 1, NO_TARGET, {}],
-  ["Entrench", Entrench, Skill, Uncommon, 2, NO_TARGET, {upgraded_cost: 1,}],
-  ["Evolve", Evolve, Power, Uncommon, 1, NO_TARGET, {}],
-  ["Feel No Pain", FeelNoPain, Power, Uncommon, 1, NO_TARGET, {}],
-  ["Fire Breathing", FireBreathing, Power, Uncommon, 1, NO_TARGET, {upgraded_cost: 0,}],
-  ["Flame Barrier", FlameBarrier, Skill, Uncommon, 2, NO_TARGET, {}],
-  ["Ghostly Armor", GhostlyArmor, Skill, Uncommon, 1, NO_TARGET, {ethereal: true,}],
-  ["Hemokinesis", Hemokinesis, Attack, Uncommon, 1, HAS_TARGET, {}],
-  ["Infernal Blade", InfernalBlade, Skill, Uncommon, 1, NO_TARGET, {}],
-  ["Inflame", Inflame, Power, Uncommon, 1, NO_TARGET, {}],
-  ["Intimidate", Intimidate, Skill, Uncommon, 0, NO_TARGET, {exhausts: true,}],
-  ["Metallicize", Metallicize, Power, Uncommon, 1, NO_TARGET, {}],
-  ["Power Through", PowerThrough, Skill, Uncommon, 1, NO_TARGET, {}],
-  ["Pummel", Pummel, Attack, Uncommon, 1, HAS_TARGET, {exhausts: true,}],
-  ["Rage", Rage, Skill, Uncommon, 0, NO_TARGET, {}],
-  ["Rampage", Rampage, Attack, Uncommon, 1, HAS_TARGET, {}],
-  ["Reckless Charge", RecklessCharge, Attack, Uncommon, 0, HAS_TARGET, {}],
-  ["Rupture", Rupture, Power, Uncommon, 1, NO_TARGET, {upgraded_cost: 0,}],
-  ["Searing Blow", SearingBlow, Attack, Uncommon, 2, HAS_TARGET, {}],
-  ["Second Wind", SecondWind, Skill, Uncommon, 1, NO_TARGET, {}],
-  ["Seeing Red", SeeingRed, Skill, Uncommon, 1, NO_TARGET, {exhausts: true,}],
-  ["Sentinel", Sentinel, Skill, Uncommon, 1, NO_TARGET, {}],
-  ["Sever Soul", SeverSoul, Attack, Uncommon, 2, HAS_TARGET, {}],
-  ["Shockwave", Shockwave, Skill, Uncommon, 2, NO_TARGET, {}],
-  ["Spot Weakness", SpotWeakness, Skill, Uncommon, 1, HAS_TARGET, {}],
-  ["Uppercut", Uppercut, Attack, Uncommon, 2, HAS_TARGET, {}],
-  ["Whirlwind", Whirlwind, Attack, Uncommon, X_COST, HAS_TARGET, {}],
-
-  ["Barricade", Barricade, Power, Rare, 3, NO_TARGET, {upgraded_cost: 2,}],
-  ["Berserk", Berserk, Power, Rare, 0, NO_TARGET, {}],
-  ["Bludgeon", Bludgeon, Attack, Rare, 3, HAS_TARGET, {}],
-  ["Brutality", Brutality, Power, Rare, 0, NO_TARGET, {}],
-  ["Dark Embrace", DarkEmbrace, Power, Rare, 2, NO_TARGET, {upgraded_cost: 1,}],
-  ["Demon Form", DemonForm, Power, Rare, 3, NO_TARGET, {}],
-  ["Double Tap", DoubleTap, Skill, Rare, 1, NO_TARGET, {}],
-  ["Fiend Fire", FiendFire, Attack, Rare, 2, HAS_TARGET, {exhausts: true,}],
-  ["Immolate", Immolate, Attack, Rare, 2, NO_TARGET, {}],
-  ["Impervious", Impervious, Skill, Rare, 2, NO_TARGET, {exhausts: true,}],
-  ["Juggernaut", Juggernaut, Power, Rare, 2, NO_TARGET, {}],
-  ["Reaper", Reaper, Attack, Rare, 2, NO_TARGET, {exhausts: true,}],
-
-  ["J.A.X.", Jax, Skill, Special, 1, NO_TARGET, {}],
-
-  ["Injury", Injury, Curse, Special, UNPLAYABLE, NO_TARGET, {}],
-  ["AscendersBane", AscendersBane, Curse, Special, UNPLAYABLE, NO_TARGET, {ethereal: true,}],
-  ["Dazed", Dazed, Status, Special, UNPLAYABLE, NO_TARGET, {ethereal: true,}],
-  ["Slimed", Slimed, Status, Special, 1, NO_TARGET, {exhausts: true,}],
-  ["Burn", Burn, Status, Special, UNPLAYABLE, NO_TARGET, {}],
-  ["Wound", Wound, Status, Special, UNPLAYABLE, NO_TARGET, {}],
-
-  ["Block Potion", BlockPotion, Potion, Special, 0, NO_TARGET, {}],
-  ["BloodPotion", BloodPotion, Potion, Special, 0, NO_TARGET, {}],
-  ["Energy Potion", EnergyPotion, Potion, Special, 0, NO_TARGET, {}],
-  ["Explosive Potion", ExplosivePotion, Potion, Special, 0, NO_TARGET, {}],
+  ["Strike_R", StrikeR, Attack, Basic, RED, 1, HAS_TARGET, {}],
+  ["Bash", Bash, Attack, Basic, RED, 2, HAS_TARGET, {}],
+  ["Defend_R", DefendR, Skill, Basic, RED, 1, NO_TARGET, {}],
+
+  ["Anger", Anger, Attack, Common, RED, 0, HAS_TARGET, {}],
+  ["Armaments", Armaments, Skill, Common, RED, 1, NO_TARGET, {}],
+  ["Body Slam", BodySlam, Attack, Common, RED, 1, HAS_TARGET, {upgraded_cost: 0,}],
+  ["Clash", Clash, Attack, Common, RED, 0, HAS_TARGET, {}],
+  ["Cleave", Cleave, Attack, Common, RED, 1, NO_TARGET, {}],
+  ["Clothesline", Clothesline, Attack, Common, RED, 2, HAS_TARGET, {}],
+  ["Flex", Flex, Skill, Common, RED, 0, NO_TARGET, {}],
+  ["Havoc", Havoc, Skill, Common, RED, 1, NO_TARGET, {upgraded_cost: 0,}],
+  ["Headbutt", Headbutt, Attack, Common, RED, 1, HAS_TARGET, {}],
+  ["Heavy Blade", HeavyBlade, Attack, Common, RED, 2, HAS_TARGET, {}],
+  ["Iron Wave", IronWave, Attack, Common, RED, 1, HAS_TARGET, {}],
+  ["Perfected Strike", PerfectedStrike, Attack, Common, RED, 2, HAS_TARGET, {fidelity: Missing("does nothing"),}],
+  ["Pommel Strike", PommelStrike, Attack, Common, RED, 1, HAS_TARGET, {}],
+  ["Shrug It Off", ShrugItOff, Skill, Common, RED, 1, NO_TARGET, {}],
+  ["Sword Boomerang", SwordBoomerang, Attack, Common, RED, 1, NO_TARGET, {}],
+  ["Thunderclap", Thunderclap, Attack, Common, RED, 1, NO_TARGET, {}],
+  ["True Grit", TrueGrit, Skill, Common, RED, 1, NO_TARGET, {}],
+  ["Twin Strike", TwinStrike, Attack, Common, RED, 1, HAS_TARGET, {}],
+  ["Warcry", Warcry, Skill, Common, RED, 0, NO_TARGET, {exhausts: true,}],
+  ["Wild Strike", WildStrike, Attack, Common, RED, 1, HAS_TARGET, {}],
+
+  ["Battle Trance", BattleTrance, Skill, Uncommon, RED, 0, NO_TARGET, {}],
+  ["Blood for Blood", BloodForBlood, Attack, Uncommon, RED, 4, HAS_TARGET, {upgraded_cost: 3, fidelity: Partial("cost doesn't drop when losing hitpoints"),}],
+  ["Bloodletting", Bloodletting, Skill, Uncommon, RED, 0, NO_TARGET, {fidelity: Missing("does nothing"),}],
+  ["Burning Pact", BurningPact, Skill, Uncommon, RED, 1, NO_TARGET, {}],
+  ["Carnage", Carnage, Attack, Uncommon, RED, 2, HAS_TARGET, {ethereal: true,}],
+  ["Combust", Combust, Power, Uncommon, RED, 1, NO_TARGET, {fidelity: Missing("its power does nothing"),}],
+  ["Corruption", Corruption, Power, Uncommon, RED, 3, NO_TARGET, {upgraded_cost: 2, fidelity: Missing("its power does nothing"),}],
+  ["Disarm", Disarm, Skill, Uncommon, RED, 1, HAS_TARGET, {exhausts: true,}],
+  ["Dropkick", Dropkick, Attack, Uncommon, RED, 1, HAS_TARGET, {fidelity: Partial("no energy or draw against Vulnerable"),}],
+  ["Dual Wield", DualWield, Skill, Uncommon, RED, 1, NO_TARGET, {}],
+  ["Entrench", Entrench, Skill, Uncommon, RED, 2, NO_TARGET, {upgraded_cost: 1, fidelity: Missing("does nothing"),}],
+  ["Evolve", Evolve, Power, Uncommon, RED, 1, NO_TARGET, {}],
+  ["Feel No Pain", FeelNoPain, Power, Uncommon, RED, 1, NO_TARGET, {}],
+  ["Fire Breathing", FireBreathing, Power, Uncommon, RED, 1, NO_TARGET, {upgraded_cost: 0, fidelity: Missing("its power does nothing"),}],
+  ["Flame Barrier", FlameBarrier, Skill, Uncommon, RED, 2, NO_TARGET, {}],
+  ["Ghostly Armor", GhostlyArmor, Skill, Uncommon, RED, 1, NO_TARGET, {ethereal: true,}],
+  ["Hemokinesis", Hemokinesis, Attack, Uncommon, RED, 1, HAS_TARGET, {}],
+  ["Infernal Blade", InfernalBlade, Skill, Uncommon, RED, 1, NO_TARGET, {fidelity: Missing("does nothing"),}],
+  ["Inflame", Inflame, Power, Uncommon, RED, 1, NO_TARGET, {}],
+  ["Intimidate", Intimidate, Skill, Uncommon, RED, 0, NO_TARGET, {exhausts: true,}],
+  ["Metallicize", Metallicize, Power, Uncommon, RED, 1, NO_TARGET, {}],
+  ["Power Through", PowerThrough, Skill, Uncommon, RED, 1, NO_TARGET, {fidelity: Partial("no Wounds"),}],
+  ["Pummel", Pummel, Attack, Uncommon, RED, 1, HAS_TARGET, {exhausts: true,}],
+  ["Rage", Rage, Skill, Uncommon, RED, 0, NO_TARGET, {}],
+  ["Rampage", Rampage, Attack, Uncommon, RED, 1, HAS_TARGET, {fidelity: Partial("damage doesn't grow"),}],
+  ["Reckless Charge", RecklessCharge, Attack, Uncommon, RED, 0, HAS_TARGET, {}],
+  ["Rupture", Rupture, Power, Uncommon, RED, 1, NO_TARGET, {upgraded_cost: 0, fidelity: Missing("does nothing"),}],
+  ["Searing Blow", SearingBlow, Attack, Uncommon, RED, 2, HAS_TARGET, {fidelity: Partial("no scaling past the first upgrade"),}],
+  ["Second Wind", SecondWind, Skill, Uncommon, RED, 1, NO_TARGET, {}],
+  ["Seeing Red", SeeingRed, Skill, Uncommon, RED, 1, NO_TARGET, {exhausts: true, fidelity: Missing("does nothing"),}],
+  ["Sentinel", Sentinel, Skill, Uncommon, RED, 1, NO_TARGET, {fidelity: Partial("no energy when exhausted"),}],
+  ["Sever Soul", SeverSoul, Attack, Uncommon, RED, 2, HAS_TARGET, {fidelity: Partial("doesn't exhaust non-attacks"),}],
+  ["Shockwave", Shockwave, Skill, Uncommon, RED, 2, NO_TARGET, {}],
+  ["Spot Weakness", SpotWeakness, Skill, Uncommon, RED, 1, HAS_TARGET, {}],
+  ["Uppercut", Uppercut, Attack, Uncommon, RED, 2, HAS_TARGET, {}],
+  ["Whirlwind", Whirlwind, Attack, Uncommon, RED, X_COST, HAS_TARGET, {fidelity: Missing("does nothing"),}],
+
+  ["Barricade", Barricade, Power, Rare, RED, 3, NO_TARGET, {upgraded_cost: 2,}],
+  ["Berserk", Berserk, Power, Rare, RED, 0, NO_TARGET, {}],
+  ["Bludgeon", Bludgeon, Attack, Rare, RED, 3, HAS_TARGET, {}],
+  ["Brutality", Brutality, Power, Rare, RED, 0, NO_TARGET, {upgrade_adds_innate: true, fidelity: Missing("its power does nothing"),}],
+  ["Dark Embrace", DarkEmbrace, Power, Rare, RED, 2, NO_TARGET, {upgraded_cost: 1,}],
+  ["Demon Form", DemonForm, Power, Rare, RED, 3, NO_TARGET, {}],
+  ["Double Tap", DoubleTap, Skill, Rare, RED, 1, NO_TARGET, {fidelity: Missing("its power does nothing"),}],
+  ["Fiend Fire", FiendFire, Attack, Rare, RED, 2, HAS_TARGET, {exhausts: true,}],
+  ["Immolate", Immolate, Attack, Rare, RED, 2, NO_TARGET, {}],
+  ["Impervious", Impervious, Skill, Rare, RED, 2, NO_TARGET, {exhausts: true,}],
+  ["Juggernaut", Juggernaut, Power, Rare, RED, 2, NO_TARGET, {fidelity: Missing("its power does nothing"),}],
+  ["Reaper", Reaper, Attack, Rare, RED, 2, NO_TARGET, {exhausts: true,}],
+
+  ["Strike_G", StrikeG, Attack, Basic, GREEN, 1, HAS_TARGET, {}],
+  ["Defend_G", DefendG, Skill, Basic, GREEN, 1, NO_TARGET, {}],
+  ["Neutralize", Neutralize, Attack, Basic, GREEN, 0, HAS_TARGET, {}],
+  ["Survivor", Survivor, Skill, Basic, GREEN, 1, NO_TARGET, {}],
+
+  ["Acrobatics", Acrobatics, Skill, Common, GREEN, 1, NO_TARGET, {}],
+  ["Backflip", Backflip, Skill, Common, GREEN, 1, NO_TARGET, {}],
+  ["Bane", Bane, Attack, Common, GREEN, 1, HAS_TARGET, {}],
+  ["Blade Dance", BladeDance, Skill, Common, GREEN, 1, NO_TARGET, {}],
+  ["Cloak And Dagger", CloakAndDagger, Skill, Common, GREEN, 1, NO_TARGET, {}],
+  ["Dagger Spray", DaggerSpray, Attack, Common, GREEN, 1, NO_TARGET, {}],
+  ["Dagger Throw", DaggerThrow, Attack, Common, GREEN, 1, HAS_TARGET, {}],
+  ["Deadly Poison", DeadlyPoison, Skill, Common, GREEN, 1, HAS_TARGET, {}],
+  ["Deflect", Deflect, Skill, Common, GREEN, 0, NO_TARGET, {}],
+  ["Dodge and Roll", DodgeAndRoll, Skill, Common, GREEN, 1, NO_TARGET, {}],
+  ["Flying Knee", FlyingKnee, Attack, Common, GREEN, 1, HAS_TARGET, {}],
+  ["Outmaneuver", Outmaneuver, Skill, Common, GREEN, 1, NO_TARGET, {}],
+  ["Piercing Wail", PiercingWail, Skill, Common, GREEN, 1, NO_TARGET, {exhausts: true,}],
+  ["Poisoned Stab", PoisonedStab, Attack, Common, GREEN, 1, HAS_TARGET, {}],
+  ["Prepared", Prepared, Skill, Common, GREEN, 0, NO_TARGET, {}],
+  ["Quick Slash", QuickSlash, Attack, Common, GREEN, 1, HAS_TARGET, {}],
+  ["Slice", Slice, Attack, Common, GREEN, 0, HAS_TARGET, {}],
+  ["Underhanded Strike", SneakyStrike, Attack, Common, GREEN, 2, HAS_TARGET, {}],
+  ["Sucker Punch", SuckerPunch, Attack, Common, GREEN, 1, HAS_TARGET, {}],
+
+  ["Accuracy", Accuracy, Power, Uncommon, GREEN, 1, NO_TARGET, {}],
+  ["All Out Attack", AllOutAttack, Attack, Uncommon, GREEN, 1, NO_TARGET, {}],
+  ["Backstab", Backstab, Attack, Uncommon, GREEN, 0, HAS_TARGET, {exhausts: true, innate: true,}],
+  ["Blur", Blur, Skill, Uncommon, GREEN, 1, NO_TARGET, {}],
+  ["Bouncing Flask", BouncingFlask, Skill, Uncommon, GREEN, 2, NO_TARGET, {}],
+  ["Calculated Gamble", CalculatedGamble, Skill, Uncommon, GREEN, 0, NO_TARGET, {exhausts: true, upgrade_removes_exhaust: true,}],
+  ["Caltrops", Caltrops, Power, Uncommon, GREEN, 1, NO_TARGET, {}],
+  ["Catalyst", Catalyst, Skill, Uncommon, GREEN, 1, HAS_TARGET, {exhausts: true,}],
+  ["Choke", Choke, Attack, Uncommon, GREEN, 2, HAS_TARGET, {}],
+  ["Concentrate", Concentrate, Skill, Uncommon, GREEN, 0, NO_TARGET, {}],
+  ["Crippling Poison", CripplingCloud, Skill, Uncommon, GREEN, 2, NO_TARGET, {exhausts: true,}],
+  ["Dash", Dash, Attack, Uncommon, GREEN, 2, HAS_TARGET, {}],
+  ["Distraction", Distraction, Skill, Uncommon, GREEN, 1, NO_TARGET, {upgraded_cost: 0, exhausts: true, fidelity: Missing("doesn't create a random skill"),}],
+  ["Endless Agony", EndlessAgony, Attack, Uncommon, GREEN, 0, HAS_TARGET, {exhausts: true,}],
+  ["Escape Plan", EscapePlan, Skill, Uncommon, GREEN, 0, NO_TARGET, {}],
+  ["Eviscerate", Eviscerate, Attack, Uncommon, GREEN, 3, HAS_TARGET, {}],
+  ["Expertise", Expertise, Skill, Uncommon, GREEN, 1, NO_TARGET, {}],
+  ["Finisher", Finisher, Attack, Uncommon, GREEN, 1, HAS_TARGET, {}],
+  ["Flechettes", Flechettes, Attack, Uncommon, GREEN, 1, HAS_TARGET, {}],
+  ["Footwork", Footwork, Power, Uncommon, GREEN, 1, NO_TARGET, {}],
+  ["Heel Hook", HeelHook, Attack, Uncommon, GREEN, 1, HAS_TARGET, {}],
+  ["Infinite Blades", InfiniteBlades, Power, Uncommon, GREEN, 1, NO_TARGET, {upgrade_adds_innate: true,}],
+  ["Leg Sweep", LegSweep, Skill, Uncommon, GREEN, 2, HAS_TARGET, {}],
+  ["Masterful Stab", MasterfulStab, Attack, Uncommon, GREEN, 0, HAS_TARGET, {fidelity: Partial("cost doesn't grow when losing hitpoints"),}],
+  ["Noxious Fumes", NoxiousFumes, Power, Uncommon, GREEN, 1, NO_TARGET, {}],
+  ["Predator", Predator, Attack, Uncommon, GREEN, 2, HAS_TARGET, {}],
+  ["Reflex", Reflex, Skill, Uncommon, GREEN, UNPLAYABLE, NO_TARGET, {}],
+  ["Riddle With Holes", RiddleWithHoles, Attack, Uncommon, GREEN, 2, HAS_TARGET, {}],
+  ["Setup", Setup, Skill, Uncommon, GREEN, 1, NO_TARGET, {upgraded_cost: 0,}],
+  ["Skewer", Skewer, Attack, Uncommon, GREEN, X_COST, HAS_TARGET, {}],
+  ["Tactician", Tactician, Skill, Uncommon, GREEN, UNPLAYABLE, NO_TARGET, {}],
+  ["Terror", Terror, Skill, Uncommon, GREEN, 1, HAS_TARGET, {upgraded_cost: 0, exhausts: true,}],
+  ["Well Laid Plans", WellLaidPlans, Power, Uncommon, GREEN, 1, NO_TARGET, {}],
+
+  ["A Thousand Cuts", AThousandCuts, Power, Rare, GREEN, 2, NO_TARGET, {}],
+  ["Adrenaline", Adrenaline, Skill, Rare, GREEN, 0, NO_TARGET, {exhausts: true,}],
+  ["After Image", AfterImage, Power, Rare, GREEN, 1, NO_TARGET, {upgrade_adds_innate: true,}],
+  ["Venomology", Alchemize, Skill, Rare, GREEN, 1, NO_TARGET, {upgraded_cost: 0, exhausts: true,}],
+  ["Bullet Time", BulletTime, Skill, Rare, GREEN, 3, NO_TARGET, {upgraded_cost: 2,}],
+  ["Burst", Burst, Skill, Rare, GREEN, 1, NO_TARGET, {}],
+  ["Corpse Explosion", CorpseExplosion, Skill, Rare, GREEN, 2, HAS_TARGET, {}],
+  ["Die Die Die", DieDieDie, Attack, Rare, GREEN, 1, NO_TARGET, {exhausts: true,}],
+  ["Doppelganger", Doppelganger, Skill, Rare, GREEN, X_COST, NO_TARGET, {exhausts: true,}],
+  ["Envenom", Envenom, Power, Rare, GREEN, 2, NO_TARGET, {upgraded_cost: 1,}],
+  ["Glass Knife", GlassKnife, Attack, Rare, GREEN, 1, HAS_TARGET, {fidelity: Partial("damage doesn't decrease"),}],
+  ["Grand Finale", GrandFinale, Attack, Rare, GREEN, 0, NO_TARGET, {}],
+  ["Malaise", Malaise, Skill, Rare, GREEN, X_COST, HAS_TARGET, {exhausts: true,}],
+  ["Night Terror", Nightmare, Skill, Rare, GREEN, 3, NO_TARGET, {upgraded_cost: 2, exhausts: true, fidelity: Missing("does nothing"),}],
+  ["Phantasmal Killer", PhantasmalKiller, Skill, Rare, GREEN, 1, NO_TARGET, {upgraded_cost: 0,}],
+  ["Storm of Steel", StormOfSteel, Skill, Rare, GREEN, 1, NO_TARGET, {}],
+  ["Tools of the Trade", ToolsOfTheTrade, Power, Rare, GREEN, 1, NO_TARGET, {upgraded_cost: 0,}],
+  ["Unload", Unload, Attack, Rare, GREEN, 1, HAS_TARGET, {}],
+  ["Wraith Form v2", WraithForm, Power, Rare, GREEN, 3, NO_TARGET, {}],
+
+  ["Strike_B", StrikeB, Attack, Basic, BLUE, 1, HAS_TARGET, {}],
+  ["Defend_B", DefendB, Skill, Basic, BLUE, 1, NO_TARGET, {}],
+  ["Zap", Zap, Skill, Basic, BLUE, 1, NO_TARGET, {upgraded_cost: 0,}],
+  ["Dualcast", Dualcast, Skill, Basic, BLUE, 1, NO_TARGET, {upgraded_cost: 0,}],
+
+  ["Ball Lightning", BallLightning, Attack, Common, BLUE, 1, HAS_TARGET, {}],
+  ["Barrage", Barrage, Attack, Common, BLUE, 1, HAS_TARGET, {}],
+  ["Beam Cell", BeamCell, Attack, Common, BLUE, 0, HAS_TARGET, {}],
+  ["Gash", Claw, Attack, Common, BLUE, 0, HAS_TARGET, {fidelity: Partial("doesn't increase the damage of other Claws"),}],
+  ["Cold Snap", ColdSnap, Attack, Common, BLUE, 1, HAS_TARGET, {}],
+  ["Compile Driver", CompileDriver, Attack, Common, BLUE, 1, HAS_TARGET, {}],
+  ["Conserve Battery", ConserveBattery, Skill, Common, BLUE, 1, NO_TARGET, {}],
+  ["Coolheaded", Coolheaded, Skill, Common, BLUE, 1, NO_TARGET, {}],
+  ["Go for the Eyes", GoForTheEyes, Attack, Common, BLUE, 0, HAS_TARGET, {}],
+  ["Hologram", Hologram, Skill, Common, BLUE, 1, NO_TARGET, {exhausts: true, upgrade_removes_exhaust: true,}],
+  ["Leap", Leap, Skill, Common, BLUE, 1, NO_TARGET, {}],
+  ["Rebound", Rebound, Attack, Common, BLUE, 1, HAS_TARGET, {}],
+  ["Redo", Recursion, Skill, Common, BLUE, 1, NO_TARGET, {upgraded_cost: 0,}],
+  ["Stack", Stack, Skill, Common, BLUE, 1, NO_TARGET, {}],
+  ["Steam", SteamBarrier, Skill, Common, BLUE, 0, NO_TARGET, {fidelity: Partial("block doesn't decrease"),}],
+  ["Streamline", Streamline, Attack, Common, BLUE, 2, HAS_TARGET, {fidelity: Partial("cost doesn't decrease"),}],
+  ["Sweeping Beam", SweepingBeam, Attack, Common, BLUE, 1, NO_TARGET, {}],
+  ["Turbo", Turbo, Skill, Common, BLUE, 0, NO_TARGET, {}],
+
+  ["Aggregate", Aggregate, Skill, Uncommon, BLUE, 1, NO_TARGET, {}],
+  ["Auto Shields", AutoShields, Skill, Uncommon, BLUE, 1, NO_TARGET, {}],
+  ["Blizzard", Blizzard, Attack, Uncommon, BLUE, 1, NO_TARGET, {}],
+  ["BootSequence", BootSequence, Skill, Uncommon, BLUE, 0, NO_TARGET, {exhausts: true, innate: true,}],
+  ["Lockon", Bullseye, Attack, Uncommon, BLUE, 2, HAS_TARGET, {}],
+  ["Capacitor", Capacitor, Power, Uncommon, BLUE, 1, NO_TARGET, {}],
+  ["Chaos", Chaos, Skill, Uncommon, BLUE, 1, NO_TARGET, {}],
+  ["Chill", Chill, Skill, Uncommon, BLUE, 0, NO_TARGET, {exhausts: true, upgrade_adds_innate: true,}],
+  ["Consume", Consume, Skill, Uncommon, BLUE, 2, NO_TARGET, {}],
+  ["Darkness", Darkness, Skill, Uncommon, BLUE, 1, NO_TARGET, {}],
+  ["Defragment", Defragment, Power, Uncommon, BLUE, 1, NO_TARGET, {}],
+  ["Doom and Gloom", DoomAndGloom, Attack, Uncommon, BLUE, 2, NO_TARGET, {}],
+  ["Double Energy", DoubleEnergy, Skill, Uncommon, BLUE, 1, NO_TARGET, {upgraded_cost: 0, exhausts: true,}],
+  ["Undo", Equilibrium, Skill, Uncommon, BLUE, 2, NO_TARGET, {}],
+  ["Force Field", ForceField, Skill, Uncommon, BLUE, 4, NO_TARGET, {fidelity: Partial("cost doesn't decrease"),}],
+  ["FTL", Ftl, Attack, Uncommon, BLUE, 0, HAS_TARGET, {}],
+  ["Fusion", Fusion, Skill, Uncommon, BLUE, 2, NO_TARGET, {upgraded_cost: 1,}],
+  ["Genetic Algorithm", GeneticAlgorithm, Skill, Uncommon, BLUE, 1, NO_TARGET, {exhausts: true,}],
+  ["Glacier", Glacier, Skill, Uncommon, BLUE, 2, NO_TARGET, {}],
+  ["Heatsinks", Heatsinks, Power, Uncommon, BLUE, 1, NO_TARGET, {}],
+  ["Hello World", HelloWorld, Power, Uncommon, BLUE, 1, NO_TARGET, {upgrade_adds_innate: true, fidelity: Missing("its power does nothing"),}],
+  ["Loop", Loop, Power, Uncommon, BLUE, 1, NO_TARGET, {}],
+  ["Melter", Melter, Attack, Uncommon, BLUE, 1, HAS_TARGET, {}],
+  ["Steam Power", Overclock, Skill, Uncommon, BLUE, 0, NO_TARGET, {}],
+  ["Recycle", Recycle, Skill, Uncommon, BLUE, 1, NO_TARGET, {upgraded_cost: 0,}],
+  ["Reinforced Body", ReinforcedBody, Skill, Uncommon, BLUE, X_COST, NO_TARGET, {}],
+  ["Reprogram", Reprogram, Skill, Uncommon, BLUE, 1, NO_TARGET, {}],
+  ["Rip and Tear", RipAndTear, Attack, Uncommon, BLUE, 1, NO_TARGET, {}],
+  ["Scrape", Scrape, Attack, Uncommon, BLUE, 1, HAS_TARGET, {fidelity: Partial("keeps drawn cards that don't cost 0"),}],
+  ["Self Repair", SelfRepair, Power, Uncommon, BLUE, 1, NO_TARGET, {}],
+  ["Skim", Skim, Skill, Uncommon, BLUE, 1, NO_TARGET, {}],
+  ["Static Discharge", StaticDischarge, Power, Uncommon, BLUE, 1, NO_TARGET, {}],
+  ["Storm", Storm, Power, Uncommon, BLUE, 1, NO_TARGET, {upgrade_adds_innate: true,}],
+  ["Sunder", Sunder, Attack, Uncommon, BLUE, 3, HAS_TARGET, {fidelity: Partial("no energy on kill"),}],
+  ["Tempest", Tempest, Skill, Uncommon, BLUE, X_COST, NO_TARGET, {exhausts: true,}],
+  ["White Noise", WhiteNoise, Skill, Uncommon, BLUE, 1, NO_TARGET, {upgraded_cost: 0, exhausts: true, fidelity: Missing("doesn't create a random power"),}],
+
+  ["All For One", AllForOne, Attack, Rare, BLUE, 2, HAS_TARGET, {}],
+  ["Amplify", Amplify, Skill, Rare, BLUE, 1, NO_TARGET, {}],
+  ["Biased Cognition", BiasedCognition, Power, Rare, BLUE, 1, NO_TARGET, {}],
+  ["Buffer", Buffer, Power, Rare, BLUE, 2, NO_TARGET, {}],
+  ["Core Surge", CoreSurge, Attack, Rare, BLUE, 1, HAS_TARGET, {exhausts: true,}],
+  ["Creative AI", CreativeAI, Power, Rare, BLUE, 3, NO_TARGET, {upgraded_cost: 2, fidelity: Missing("its power does nothing"),}],
+  ["Echo Form", EchoForm, Power, Rare, BLUE, 3, NO_TARGET, {ethereal: true, upgrade_removes_ethereal: true,}],
+  ["Electrodynamics", Electrodynamics, Power, Rare, BLUE, 2, NO_TARGET, {}],
+  ["Fission", Fission, Skill, Rare, BLUE, 0, NO_TARGET, {exhausts: true,}],
+  ["Hyperbeam", Hyperbeam, Attack, Rare, BLUE, 2, NO_TARGET, {}],
+  ["Machine Learning", MachineLearning, Power, Rare, BLUE, 1, NO_TARGET, {upgrade_adds_innate: true,}],
+  ["Meteor Strike", MeteorStrike, Attack, Rare, BLUE, 5, HAS_TARGET, {}],
+  ["Multi-Cast", MultiCast, Skill, Rare, BLUE, X_COST, NO_TARGET, {}],
+  ["Rainbow", Rainbow, Skill, Rare, BLUE, 2, NO_TARGET, {exhausts: true, upgrade_removes_exhaust: true,}],
+  ["Reboot", Reboot, Skill, Rare, BLUE, 0, NO_TARGET, {exhausts: true,}],
+  ["Seek", Seek, Skill, Rare, BLUE, 0, NO_TARGET, {exhausts: true,}],
+  ["Thunder Strike", ThunderStrike, Attack, Rare, BLUE, 3, NO_TARGET, {}],
+
+  ["Strike_P", StrikeP, Attack, Basic, PURPLE, 1, HAS_TARGET, {}],
+  ["Defend_P", DefendP, Skill, Basic, PURPLE, 1, NO_TARGET, {}],
+  ["Eruption", Eruption, Attack, Basic, PURPLE, 2, HAS_TARGET, {upgraded_cost: 1,}],
+  ["Vigilance", Vigilance, Skill, Basic, PURPLE, 2, NO_TARGET, {}],
+
+  ["BowlingBash", BowlingBash, Attack, Common, PURPLE, 1, HAS_TARGET, {}],
+  ["Consecrate", Consecrate, Attack, Common, PURPLE, 0, NO_TARGET, {}],
+  ["Crescendo", Crescendo, Skill, Common, PURPLE, 1, NO_TARGET, {upgraded_cost: 0, exhausts: true, retains: true,}],
+  ["CrushJoints", CrushJoints, Attack, Common, PURPLE, 1, HAS_TARGET, {}],
+  ["CutThroughFate", CutThroughFate, Attack, Common, PURPLE, 1, HAS_TARGET, {}],
+  ["EmptyBody", EmptyBody, Skill, Common, PURPLE, 1, NO_TARGET, {}],
+  ["EmptyFist", EmptyFist, Attack, Common, PURPLE, 1, HAS_TARGET, {}],
+  ["Evaluate", Evaluate, Skill, Common, PURPLE, 1, NO_TARGET, {}],
+  ["FlurryOfBlows", FlurryOfBlows, Attack, Common, PURPLE, 0, HAS_TARGET, {}],
+  ["FlyingSleeves", FlyingSleeves, Attack, Common, PURPLE, 1, HAS_TARGET, {retains: true,}],
+  ["FollowUp", FollowUp, Attack, Common, PURPLE, 1, HAS_TARGET, {}],
+  ["Halt", Halt, Skill, Common, PURPLE, 0, NO_TARGET, {}],
+  ["JustLucky", JustLucky, Attack, Common, PURPLE, 0, HAS_TARGET, {}],
+  ["PathToVictory", PressurePoints, Skill, Common, PURPLE, 1, HAS_TARGET, {}],
+  ["Prostrate", Prostrate, Skill, Common, PURPLE, 0, NO_TARGET, {}],
+  ["Protect", Protect, Skill, Common, PURPLE, 2, NO_TARGET, {retains: true,}],
+  ["SashWhip", SashWhip, Attack, Common, PURPLE, 1, HAS_TARGET, {}],
+  ["ClearTheMind", Tranquility, Skill, Common, PURPLE, 1, NO_TARGET, {upgraded_cost: 0, exhausts: true, retains: true,}],
+  ["ThirdEye", ThirdEye, Skill, Common, PURPLE, 1, NO_TARGET, {}],
+
+  ["BattleHymn", BattleHymn, Power, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["CarveReality", CarveReality, Attack, Uncommon, PURPLE, 1, HAS_TARGET, {}],
+  ["Collect", Collect, Skill, Uncommon, PURPLE, X_COST, NO_TARGET, {exhausts: true,}],
+  ["Conclude", Conclude, Attack, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["DeceiveReality", DeceiveReality, Skill, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["EmptyMind", EmptyMind, Skill, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["Fasting2", Fasting, Power, Uncommon, PURPLE, 2, NO_TARGET, {}],
+  ["FearNoEvil", FearNoEvil, Attack, Uncommon, PURPLE, 1, HAS_TARGET, {}],
+  ["ForeignInfluence", ForeignInfluence, Skill, Uncommon, PURPLE, 0, NO_TARGET, {exhausts: true, fidelity: Missing("doesn't offer any attacks"),}],
+  ["Wireheading", Foresight, Power, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["Indignation", Indignation, Skill, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["InnerPeace", InnerPeace, Skill, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["LikeWater", LikeWater, Power, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["Meditate", Meditate, Skill, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["MentalFortress", MentalFortress, Power, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["Nirvana", Nirvana, Power, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["Perseverance", Perseverance, Skill, Uncommon, PURPLE, 1, NO_TARGET, {retains: true,}],
+  ["Pray", Pray, Skill, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["ReachHeaven", ReachHeaven, Attack, Uncommon, PURPLE, 2, HAS_TARGET, {}],
+  ["Adaptation", Rushdown, Power, Uncommon, PURPLE, 1, NO_TARGET, {upgraded_cost: 0,}],
+  ["Sanctity", Sanctity, Skill, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["SandsOfTime", SandsOfTime, Attack, Uncommon, PURPLE, 4, HAS_TARGET, {retains: true,}],
+  ["SignatureMove", SignatureMove, Attack, Uncommon, PURPLE, 2, HAS_TARGET, {}],
+  ["Study", Study, Power, Uncommon, PURPLE, 2, NO_TARGET, {upgraded_cost: 1,}],
+  ["Swivel", Swivel, Skill, Uncommon, PURPLE, 2, NO_TARGET, {}],
+  ["TalkToTheHand", TalkToTheHand, Attack, Uncommon, PURPLE, 1, HAS_TARGET, {exhausts: true,}],
+  ["Tantrum", Tantrum, Attack, Uncommon, PURPLE, 1, HAS_TARGET, {shuffles_back_into_draw_pile: true,}],
+  ["Vengeance", SimmeringFury, Skill, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["WaveOfTheHand", WaveOfTheHand, Skill, Uncommon, PURPLE, 1, NO_TARGET, {}],
+  ["Weave", Weave, Attack, Uncommon, PURPLE, 0, HAS_TARGET, {}],
+  ["WheelKick", WheelKick, Attack, Uncommon, PURPLE, 2, HAS_TARGET, {}],
+  ["WindmillStrike", WindmillStrike, Attack, Uncommon, PURPLE, 2, HAS_TARGET, {retains: true,}],
+  ["Worship", Worship, Skill, Uncommon, PURPLE, 2, NO_TARGET, {upgrade_adds_retain: true,}],
+  ["WreathOfFlame", WreathOfFlame, Skill, Uncommon, PURPLE, 1, NO_TARGET, {}],
+
+  ["Alpha", Alpha, Skill, Rare, PURPLE, 1, NO_TARGET, {exhausts: true, upgrade_adds_innate: true,}],
+  ["Blasphemy", Blasphemy, Skill, Rare, PURPLE, 1, NO_TARGET, {exhausts: true, upgrade_adds_retain: true,}],
+  ["Brilliance", Brilliance, Attack, Rare, PURPLE, 1, HAS_TARGET, {}],
+  ["ConjureBlade", ConjureBlade, Skill, Rare, PURPLE, X_COST, NO_TARGET, {exhausts: true,}],
+  ["DeusExMachina", DeusExMachina, Skill, Rare, PURPLE, UNPLAYABLE, NO_TARGET, {exhausts: true,}],
+  ["DevaForm", DevaForm, Power, Rare, PURPLE, 3, NO_TARGET, {ethereal: true, upgrade_removes_ethereal: true,}],
+  ["Devotion", Devotion, Power, Rare, PURPLE, 1, NO_TARGET, {}],
+  ["Establishment", Establishment, Power, Rare, PURPLE, 1, NO_TARGET, {upgrade_adds_innate: true,}],
+  ["Judgement", Judgement, Skill, Rare, PURPLE, 1, HAS_TARGET, {}],
+  ["LessonLearned", LessonLearned, Attack, Rare, PURPLE, 2, HAS_TARGET, {exhausts: true, fidelity: Partial("doesn't upgrade a card in the deck when it kills"),}],
+  ["MasterReality", MasterReality, Power, Rare, PURPLE, 1, NO_TARGET, {upgraded_cost: 0, fidelity: Missing("its power does nothing"),}],
+  ["Omniscience", Omniscience, Skill, Rare, PURPLE, 4, NO_TARGET, {upgraded_cost: 3, exhausts: true,}],
+  ["Ragnarok", Ragnarok, Attack, Rare, PURPLE, 3, NO_TARGET, {}],
+  ["Scrawl", Scrawl, Skill, Rare, PURPLE, 1, NO_TARGET, {upgraded_cost: 0, exhausts: true,}],
+  ["SpiritShield", SpiritShield, Skill, Rare, PURPLE, 2, NO_TARGET, {}],
+  ["Vault", Vault, Skill, Rare, PURPLE, 3, NO_TARGET, {upgraded_cost: 2, exhausts: true,}],
+  ["Wallop", Wallop, Attack, Rare, PURPLE, 2, HAS_TARGET, {}],
+  ["Wish", Wish, Skill, Rare, PURPLE, 3, NO_TARGET, {exhausts: true,}],
+
+  ["J.A.X.", Jax, Skill, Special, COLORLESS, 1, NO_TARGET, {}],
+  ["Shiv", Shiv, Attack, Special, COLORLESS, 0, HAS_TARGET, {exhausts: true,}],
+  ["Miracle", Miracle, Skill, Special, COLORLESS, 0, NO_TARGET, {exhausts: true, retains: true,}],
+  ["Insight", Insight, Skill, Special, COLORLESS, 0, NO_TARGET, {exhausts: true, retains: true,}],
+  ["Smite", Smite, Attack, Special, COLORLESS, 1, HAS_TARGET, {exhausts: true, retains: true,}],
+  ["Safety", Safety, Skill, Special, COLORLESS, 1, NO_TARGET, {exhausts: true, retains: true,}],
+  ["ThroughViolence", ThroughViolence, Attack, Special, COLORLESS, 0, HAS_TARGET, {exhausts: true, retains: true,}],
+  ["Beta", Beta, Skill, Special, COLORLESS, 2, NO_TARGET, {upgraded_cost: 1, exhausts: true,}],
+  ["Omega", Omega, Power, Special, COLORLESS, 3, NO_TARGET, {}],
+  ["Expunger", Expunger, Attack, Special, COLORLESS, 1, HAS_TARGET, {}],
+  ["Wrath", ChooseWrath, Power, Special, COLORLESS, UNPLAYABLE, NO_TARGET, {}],
+  ["Calm", ChooseCalm, Power, Special, COLORLESS, UNPLAYABLE, NO_TARGET, {}],
+  ["BecomeAlmighty", BecomeAlmighty, Power, Special, COLORLESS, UNPLAYABLE, NO_TARGET, {}],
+  ["FameAndFortune", FameAndFortune, Power, Special, COLORLESS, UNPLAYABLE, NO_TARGET, {}],
+  ["LiveForever", LiveForever, Power, Special, COLORLESS, UNPLAYABLE, NO_TARGET, {}],
+
+  ["Apotheosis", Apotheosis, Skill, Rare, COLORLESS, 2, NO_TARGET, {upgraded_cost: 1, exhausts: true,}],
+
+  ["Injury", Injury, Curse, Special, COLORLESS, UNPLAYABLE, NO_TARGET, {}],
+  ["AscendersBane", AscendersBane, Curse, Special, COLORLESS, UNPLAYABLE, NO_TARGET, {ethereal: true,}],
+  ["Dazed", Dazed, Status, Special, COLORLESS, UNPLAYABLE, NO_TARGET, {ethereal: true,}],
+  ["Slimed", Slimed, Status, Special, COLORLESS, 1, NO_TARGET, {exhausts: true,}],
+  ["Burn", Burn, Status, Special, COLORLESS, UNPLAYABLE, NO_TARGET, {}],
+  ["Wound", Wound, Status, Special, COLORLESS, UNPLAYABLE, NO_TARGET, {}],
+  ["Void", VoidCard, Status, Special, COLORLESS, UNPLAYABLE, NO_TARGET, {ethereal: true,}],
+
+  ["Ancient Potion", AncientPotion, Potion, Uncommon, COLORLESS, 0, NO_TARGET, {}],
+  ["AttackPotion", AttackPotion, Potion, Common, COLORLESS, 0, NO_TARGET, {}],
+  ["Block Potion", BlockPotion, Potion, Common, COLORLESS, 0, NO_TARGET, {}],
+  ["BloodPotion", BloodPotion, Potion, Common, RED, 0, NO_TARGET, {}],
+  ["BottledMiracle", BottledMiracle, Potion, Common, PURPLE, 0, NO_TARGET, {}],
+  ["ColorlessPotion", ColorlessPotion, Potion, Common, COLORLESS, 0, NO_TARGET, {fidelity: Missing("can't be used"),}],
+  ["CultistPotion", CultistPotion, Potion, Rare, COLORLESS, 0, NO_TARGET, {}],
+  ["CunningPotion", CunningPotion, Potion, Uncommon, GREEN, 0, NO_TARGET, {}],
+  ["Dexterity Potion", DexterityPotion, Potion, Common, COLORLESS, 0, NO_TARGET, {}],
+  ["DistilledChaos", DistilledChaos, Potion, Uncommon, COLORLESS, 0, NO_TARGET, {}],
+  ["DuplicationPotion", DuplicationPotion, Potion, Uncommon, COLORLESS, 0, NO_TARGET, {}],
+  ["ElixirPotion", Elixir, Potion, Uncommon, RED, 0, NO_TARGET, {}],
+  ["Energy Potion", EnergyPotion, Potion, Common, COLORLESS, 0, NO_TARGET, {}],
+  ["EntropicBrew", EntropicBrew, Potion, Rare, COLORLESS, 0, NO_TARGET, {}],
+  ["EssenceOfDarkness", EssenceOfDarkness, Potion, Rare, BLUE, 0, NO_TARGET, {}],
+  ["EssenceOfSteel", EssenceOfSteel, Potion, Uncommon, COLORLESS, 0, NO_TARGET, {}],
+  ["Explosive Potion", ExplosivePotion, Potion, Common, COLORLESS, 0, NO_TARGET, {}],
+  ["FairyPotion", FairyPotion, Potion, Rare, COLORLESS, 0, NO_TARGET, {}],
+  ["FearPotion", FearPotion, Potion, Common, COLORLESS, 0, HAS_TARGET, {}],
+  ["Fire Potion", FirePotion, Potion, Common, COLORLESS, 0, HAS_TARGET, {}],
+  ["FocusPotion", FocusPotion, Potion, Common, BLUE, 0, NO_TARGET, {}],
+  ["Fruit Juice", FruitJuice, Potion, Rare, COLORLESS, 0, NO_TARGET, {}],
+  ["GamblersBrew", GamblersBrew, Potion, Uncommon, COLORLESS, 0, NO_TARGET, {}],
+  ["GhostInAJar", GhostInAJar, Potion, Rare, GREEN, 0, NO_TARGET, {}],
+  ["HeartOfIron", HeartOfIron, Potion, Rare, RED, 0, NO_TARGET, {}],
+  ["LiquidBronze", LiquidBronze, Potion, Uncommon, COLORLESS, 0, NO_TARGET, {}],
+  ["LiquidMemories", LiquidMemories, Potion, Uncommon, COLORLESS, 0, NO_TARGET, {}],
+  ["Poison Potion", PoisonPotion, Potion, Common, GREEN, 0, HAS_TARGET, {}],
+  ["PotionOfCapacity", PotionOfCapacity, Potion, Uncommon, BLUE, 0, NO_TARGET, {}],
+  ["PowerPotion", PowerPotion, Potion, Common, COLORLESS, 0, NO_TARGET, {}],
+  ["Regen Potion", RegenPotion, Potion, Uncommon, COLORLESS, 0, NO_TARGET, {}],
+  ["SkillPotion", SkillPotion, Potion, Common, COLORLESS, 0, NO_TARGET, {}],
+  ["SmokeBomb", SmokeBomb, Potion, Rare, COLORLESS, 0, NO_TARGET, {}],
+  ["SneckoOil", SneckoOil, Potion, Rare, COLORLESS, 0, NO_TARGET, {fidelity: Partial("doesn't randomize costs"),}],
+  ["SpeedPotion", SpeedPotion, Potion, Common, COLORLESS, 0, NO_TARGET, {}],
+  ["StancePotion", StancePotion, Potion, Uncommon, PURPLE, 0, NO_TARGET, {}],
+  ["SteroidPotion", SteroidPotion, Potion, Common, COLORLESS, 0, NO_TARGET, {}],
+  ["Strength Potion", StrengthPotion, Potion, Common, COLORLESS, 0, NO_TARGET, {}],
+  ["Swift Potion", SwiftPotion, Potion, Common, COLORLESS, 0, NO_TARGET, {}],
+  ["Weak Potion", WeakPotion, Potion, Common, COLORLESS, 0, HAS_TARGET, {}],
+  ["BlessingOfTheForge", BlessingOfTheForge, Potion, Common, COLORLESS, 0, NO_TARGET, {}],
+  ["Ambrosia", Ambrosia, Potion, Rare, PURPLE, 0, NO_TARGET, {}],
+}
+
+impl CardId {
+  /// The character whose pool this card or potion belongs to, or None if it isn't in any
+  /// character's pool.
+  pub fn character(self) -> Option<Character> {
+    <&CardInfo>::from(self).character
+  }
+  /// Cards with the HEALING tag, which random card generation in combat never picks.
+  pub fn heals(self) -> bool {
+    matches!(
+      self,
+      CardId::Reaper
+        | CardId::SelfRepair
+        | CardId::LessonLearned
+        | CardId::Wish
+        | CardId::Alchemize
+    )
+  }
+}
+
+/// The cards a random card of `card_type` is picked from in combat (Attack Potion, Skill Potion,
+/// Power Potion), each equally likely.
+pub fn combat_card_pool(character: Character, card_type: CardType) -> Vec<CardId> {
+  CardId::ALL
+    .iter()
+    .copied()
+    .filter(|&id| {
+      let info = <&CardInfo>::from(id);
+      id.character() == Some(character)
+        && info.card_type == card_type
+        && matches!(info.rarity, Common | Uncommon | Rare)
+        && !id.heals()
+    })
+    .collect()
+}
+
+/// The potions a random potion is picked from in combat (Alchemize, Entropic Brew), with their
+/// weights: the rarity is picked first (65% common, 25% uncommon, 10% rare), then a potion of that
+/// rarity uniformly.
+pub fn random_potion_distribution(character: Character) -> Vec<(f64, CardId)> {
+  let potions: Vec<&CardInfo> = CardId::ALL
+    .iter()
+    .map(|&id| <&CardInfo>::from(id))
+    .filter(|info| {
+      info.card_type == Potion
+        && info.id.character().map_or(true, |c| c == character)
+        // the in-combat version rerolls Fruit Juice
+        && info.id != CardId::FruitJuice
+    })
+    .collect();
+  let rarity_weight = |rarity: Rarity| match rarity {
+    Common => 0.65,
+    Uncommon => 0.25,
+    _ => 0.10,
+  };
+  potions
+    .iter()
+    .map(|info| {
+      let count = potions.iter().filter(|p| p.rarity == info.rarity).count();
+      (rarity_weight(info.rarity) / count as f64, info.id)
+    })
+    .collect()
 }
 
 impl CardBehavior for StrikeR {
@@ -368,9 +825,14 @@ impl CardBehavior for Anger {
 impl CardBehavior for Armaments {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
     context.block(5);
-    context.action(ArmamentsAction {
-      upgraded: context.upgraded(),
-    });
+    if context.upgraded() {
+      context.action(UpgradeCardsAction::new(
+        UpgradeCardsFilter::Upgradable,
+        &[CardPile::Hand],
+      ));
+    } else {
+      context.choose_card(CardChoiceType::UpgradeCard);
+    }
   }
 }
 
@@ -408,20 +870,20 @@ impl CardBehavior for Clothesline {
 impl CardBehavior for Flex {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
     context.power_self(PowerId::Strength, context.with_upgrade(4, 2));
-    //TODO: strength down
+    context.power_self(PowerId::LoseStrength, context.with_upgrade(4, 2));
   }
 }
 
 impl CardBehavior for Havoc {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
-    //TODO
+    context.action(PlayTopCardAction);
   }
 }
 
 impl CardBehavior for Headbutt {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
     context.attack_target(context.with_upgrade(12, 9));
-    //TODO: retrieve card
+    context.choose_card(CardChoiceType::DiscardTopdeck);
   }
 }
 
@@ -482,7 +944,13 @@ impl CardBehavior for Thunderclap {
 impl CardBehavior for TrueGrit {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
     context.block(context.with_upgrade(9, 7));
-    //TODO: exhaust
+    if context.upgraded() {
+      context.choose_card(CardChoiceType::ExhaustCard);
+    } else {
+      context.action(ChooseRandomCardAction {
+        choice_type: CardChoiceType::ExhaustCard,
+      });
+    }
   }
 }
 
@@ -496,14 +964,15 @@ impl CardBehavior for TwinStrike {
 
 impl CardBehavior for Warcry {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
-    //TODO
+    context.draw_cards(context.with_upgrade(2, 1));
+    context.choose_card(CardChoiceType::HandTopdeck);
   }
 }
 
 impl CardBehavior for WildStrike {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
     context.attack_target(context.with_upgrade(17, 12));
-    //TODO: wound
+    context.action(ShuffleNewCardIntoDrawPile(SingleCard::create(CardId::Wound)));
   }
 }
 
@@ -529,7 +998,8 @@ impl CardBehavior for Bloodletting {
 
 impl CardBehavior for BurningPact {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
-    //TODO
+    context.choose_card(CardChoiceType::ExhaustCard);
+    context.draw_cards(context.with_upgrade(3, 2));
   }
 }
 
@@ -566,7 +1036,9 @@ impl CardBehavior for Dropkick {
 
 impl CardBehavior for DualWield {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
-    //TODO
+    context.choose_card(CardChoiceType::DuplicateAttackOrPower {
+      copies: context.with_upgrade(2, 1),
+    });
   }
 }
 
@@ -677,7 +1149,7 @@ impl CardBehavior for Rampage {
 impl CardBehavior for RecklessCharge {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
     context.attack_target(context.with_upgrade(10, 7));
-    // TODO other effect
+    context.action(ShuffleNewCardIntoDrawPile(SingleCard::create(CardId::Dazed)));
   }
 }
 
@@ -696,7 +1168,9 @@ impl CardBehavior for SearingBlow {
 
 impl CardBehavior for SecondWind {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
-    //TODO
+    context.action(SecondWindAction {
+      block_per_card: context.block_amount(context.with_upgrade(7, 5)),
+    });
   }
 }
 
@@ -829,72 +1303,2504 @@ impl CardBehavior for Juggernaut {
 
 impl CardBehavior for Reaper {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
-    let info = DamageInfoNoPowers::new(
+    let infos = DamageInfoNoPowers::new(
       Some(CreatureIndex::Player),
       context.with_upgrade(5, 4),
       DamageType::Normal,
     )
-    .apply_owner_powers(context.state());
-    context.action(VampireDamageAllEnemiesAction { info });
+    .apply_all_powers_to_monsters(context.state());
+    context.action(VampireDamageAllEnemiesAction { infos });
   }
 }
 
-impl CardBehavior for Jax {
+impl CardBehavior for StrikeG {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
-    context.action(DamageAction {
-      target: CreatureIndex::Player,
-      info: DamageInfoNoPowers::new(Some(CreatureIndex::Player), 3, DamageType::HitpointLoss)
-        .ignore_powers(),
-    });
-    context.power_self(PowerId::Strength, context.with_upgrade(3, 2));
+    context.attack_target(context.with_upgrade(9, 6));
   }
 }
 
-impl CardBehavior for Injury {}
-impl CardBehavior for AscendersBane {}
-impl CardBehavior for Dazed {}
-impl CardBehavior for Slimed {}
-impl CardBehavior for Burn {}
-impl CardBehavior for Wound {}
+impl CardBehavior for DefendG {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(8, 5));
+  }
+}
 
-impl CardBehavior for BlockPotion {
+impl CardBehavior for Neutralize {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
-    context.block(context.potency(12));
+    context.attack_target(context.with_upgrade(4, 3));
+    context.power_target(PowerId::Weak, context.with_upgrade(2, 1));
   }
-  fn potion_value(self, _state: &CombatState) -> f64 {
-    11.5
+}
+
+impl CardBehavior for Survivor {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(11, 8));
+    context.discard_cards(1);
   }
 }
 
-impl CardBehavior for BloodPotion {
+impl CardBehavior for Acrobatics {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
-    context.action(HealAction {
+    context.draw_cards(context.with_upgrade(4, 3));
+    context.discard_cards(1);
+  }
+}
+
+impl CardBehavior for Backflip {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(8, 5));
+    context.draw_cards(2);
+  }
+}
+
+impl CardBehavior for Bane {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let damage = context.with_upgrade(10, 7);
+    context.attack_target(damage);
+    let poisoned = context.state().monsters[context.target()]
+      .creature
+      .has_power(PowerId::Poison);
+    if poisoned {
+      context.attack_target(damage);
+    }
+  }
+}
+
+impl CardBehavior for BladeDance {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.with_upgrade(4, 3) {
+      context.action(AddNewCardToHand(SingleCard::create(CardId::Shiv)));
+    }
+  }
+}
+
+impl CardBehavior for CloakAndDagger {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(6);
+    for _ in 0..context.with_upgrade(2, 1) {
+      context.action(AddNewCardToHand(SingleCard::create(CardId::Shiv)));
+    }
+  }
+}
+
+impl CardBehavior for DaggerSpray {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..2 {
+      context.attack_monsters(context.with_upgrade(6, 4));
+    }
+  }
+}
+
+impl CardBehavior for DaggerThrow {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(12, 9));
+    context.draw_cards(1);
+    context.discard_cards(1);
+  }
+}
+
+impl CardBehavior for DeadlyPoison {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_target(PowerId::Poison, context.with_upgrade(7, 5));
+  }
+}
+
+impl CardBehavior for Deflect {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(7, 4));
+  }
+}
+
+impl CardBehavior for DodgeAndRoll {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let amount = context.block_amount(context.with_upgrade(6, 4));
+    context.action(GainBlockAction {
       creature_index: CreatureIndex::Player,
-      amount: ((context.state().player.creature.max_hitpoints * context.potency(20)) as f64 / 100.0)
-        as i32,
+      amount,
     });
+    context.power_self(PowerId::NextTurnBlock, amount);
   }
-  fn potion_value(self, state: &CombatState) -> f64 {
-    // Holding a blood potion is only worth slightly more than the hitpoints you get from using it:
-    ((state.player.creature.max_hitpoints * 20) as f64 / 100.0).floor() + 1.0
+}
+
+impl CardBehavior for FlyingKnee {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(11, 8));
+    context.power_self(PowerId::Energized, 1);
   }
 }
 
-impl CardBehavior for ExplosivePotion {
+impl CardBehavior for Outmaneuver {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
-    let info = DamageInfoNoPowers::new(None, context.potency(10), DamageType::Normal);
-    context.action(DamageAllEnemiesActionIgnoringPowers { info });
+    context.power_self(PowerId::Energized, context.with_upgrade(3, 2));
   }
-  fn potion_value(self, _state: &CombatState) -> f64 {
-    16.0
+}
+
+impl CardBehavior for PiercingWail {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let amount = context.with_upgrade(8, 6);
+    for index in 0..context.state().monsters.len() {
+      if context.state().monsters[index].gone {
+        continue;
+      }
+      let target = CreatureIndex::Monster(index);
+      // if Artifact blocks the strength loss, there's nothing to restore later
+      let has_artifact = context
+        .state()
+        .get_creature(target)
+        .has_power(PowerId::Artifact);
+      context.action(ApplyPowerAction {
+        source: CreatureIndex::Player,
+        target,
+        power_id: PowerId::Strength,
+        amount: -amount,
+      });
+      if !has_artifact {
+        context.action(ApplyPowerAction {
+          source: CreatureIndex::Player,
+          target,
+          power_id: PowerId::Shackled,
+          amount,
+        });
+      }
+    }
   }
 }
 
-impl CardBehavior for EnergyPotion {
+impl CardBehavior for PoisonedStab {
   fn behavior(self, context: &mut impl CardBehaviorContext) {
-    context.action(GainEnergyAction(context.potency(2)));
+    context.attack_target(context.with_upgrade(8, 6));
+    context.power_target(PowerId::Poison, context.with_upgrade(4, 3));
   }
-  fn potion_value(self, _state: &CombatState) -> f64 {
-    10.0
+}
+
+impl CardBehavior for Prepared {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let amount = context.with_upgrade(2, 1);
+    context.draw_cards(amount);
+    context.discard_cards(amount);
+  }
+}
+
+impl CardBehavior for QuickSlash {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(12, 8));
+    context.draw_cards(1);
+  }
+}
+
+impl CardBehavior for Slice {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(9, 6));
+  }
+}
+
+impl CardBehavior for SneakyStrike {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(16, 12));
+    if context.state().cards_discarded_this_turn > 0 {
+      context.action(GainEnergyAction(2));
+    }
+  }
+}
+
+impl CardBehavior for SuckerPunch {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(9, 7));
+    context.power_target(PowerId::Weak, context.with_upgrade(2, 1));
+  }
+}
+
+impl CardBehavior for Accuracy {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Accuracy, context.with_upgrade(6, 4));
+  }
+}
+
+impl CardBehavior for AllOutAttack {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_monsters(context.with_upgrade(14, 10));
+    context.action(ChooseRandomCardAction {
+      choice_type: CardChoiceType::DiscardCard,
+    });
+  }
+}
+
+impl CardBehavior for Backstab {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(15, 11));
+  }
+}
+
+impl CardBehavior for Blur {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(8, 5));
+    context.power_self(PowerId::Blur, 1);
+  }
+}
+
+impl CardBehavior for BouncingFlask {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.with_upgrade(4, 3) {
+      context.action(ApplyPowerRandomEnemyAction {
+        power_id: PowerId::Poison,
+        amount: 3,
+      });
+    }
+  }
+}
+
+impl CardBehavior for CalculatedGamble {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(CalculatedGambleAction);
+  }
+}
+
+impl CardBehavior for Caltrops {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Thorns, context.with_upgrade(5, 3));
+  }
+}
+
+impl CardBehavior for Catalyst {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(CatalystAction {
+      target: context.target_creature_index(),
+      multiplier: context.with_upgrade(3, 2),
+    });
+  }
+}
+
+impl CardBehavior for Choke {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(12);
+    context.power_target(PowerId::Choked, context.with_upgrade(5, 3));
+  }
+}
+
+impl CardBehavior for Concentrate {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.discard_cards(context.with_upgrade(2, 3));
+    context.action(GainEnergyAction(2));
+  }
+}
+
+impl CardBehavior for CripplingCloud {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_monsters(PowerId::Poison, context.with_upgrade(7, 4));
+    context.power_monsters(PowerId::Weak, 2);
+  }
+}
+
+impl CardBehavior for Dash {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(13, 10));
+    context.attack_target(context.with_upgrade(13, 10));
+  }
+}
+
+impl CardBehavior for Distraction {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    //TODO: random skill
+  }
+}
+
+impl CardBehavior for EndlessAgony {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(6, 4));
+  }
+  fn on_draw(self, runner: &mut impl Runner, card: &mut SingleCard) {
+    runner.action_bottom(AddNewCardToHand(card.clone()));
+  }
+}
+
+impl CardBehavior for EscapePlan {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(EscapePlanAction {
+      block: context.block_amount(context.with_upgrade(5, 3)),
+    });
+  }
+}
+
+impl CardBehavior for Eviscerate {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..3 {
+      context.attack_target(context.with_upgrade(9, 7));
+    }
+  }
+  fn on_draw(self, runner: &mut impl Runner, card: &mut SingleCard) {
+    let discarded = runner.state().cards_discarded_this_turn;
+    card.cost = std::cmp::max(0, card.start_combat_cost() - discarded);
+  }
+  fn on_other_card_discarded(self, card: &mut SingleCard) {
+    if card.cost > 0 {
+      card.cost -= 1;
+    }
+  }
+}
+
+impl CardBehavior for Expertise {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(ExpertiseAction(context.with_upgrade(7, 6)));
+  }
+}
+
+impl CardBehavior for Finisher {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    // not counting this one
+    let attacks = context.state().attacks_played_this_turn - 1;
+    for _ in 0..attacks {
+      context.attack_target(context.with_upgrade(8, 6));
+    }
+  }
+}
+
+impl CardBehavior for Flechettes {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let skills = context
+      .state()
+      .hand
+      .iter()
+      .filter(|card| card.card_info.card_type == Skill)
+      .count();
+    for _ in 0..skills {
+      context.attack_target(context.with_upgrade(6, 4));
+    }
+  }
+}
+
+impl CardBehavior for Footwork {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Dexterity, context.with_upgrade(3, 2));
+  }
+}
+
+impl CardBehavior for HeelHook {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(8, 5));
+    let weak = context.state().monsters[context.target()]
+      .creature
+      .has_power(PowerId::Weak);
+    if weak {
+      context.action(GainEnergyAction(1));
+      context.draw_cards(1);
+    }
+  }
+}
+
+impl CardBehavior for InfiniteBlades {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::InfiniteBlades, 1);
+  }
+}
+
+impl CardBehavior for LegSweep {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_target(PowerId::Weak, context.with_upgrade(3, 2));
+    context.block(context.with_upgrade(14, 11));
+  }
+}
+
+impl CardBehavior for MasterfulStab {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(16, 12));
+    // TODO: cost increase when losing hitpoints
+  }
+}
+
+impl CardBehavior for NoxiousFumes {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::NoxiousFumes, context.with_upgrade(3, 2));
+  }
+}
+
+impl CardBehavior for Predator {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(20, 15));
+    context.power_self(PowerId::DrawCardNextTurn, 2);
+  }
+}
+
+impl CardBehavior for Reflex {
+  fn on_manual_discard(self, runner: &mut impl Runner, card: &SingleCard) {
+    let amount = if card.upgrades > 0 { 3 } else { 2 };
+    runner.action_bottom(DrawCards(amount));
+  }
+}
+
+impl CardBehavior for RiddleWithHoles {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..5 {
+      context.attack_target(context.with_upgrade(4, 3));
+    }
+  }
+}
+
+impl CardBehavior for Setup {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.choose_card(CardChoiceType::HandTopdeckFree);
+  }
+}
+
+impl CardBehavior for Skewer {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.energy_on_use() {
+      context.attack_target(context.with_upgrade(10, 7));
+    }
+  }
+}
+
+impl CardBehavior for Tactician {
+  fn on_manual_discard(self, runner: &mut impl Runner, card: &SingleCard) {
+    let amount = if card.upgrades > 0 { 2 } else { 1 };
+    runner.action_bottom(GainEnergyAction(amount));
+  }
+}
+
+impl CardBehavior for Terror {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_target(PowerId::Vulnerable, 99);
+  }
+}
+
+impl CardBehavior for WellLaidPlans {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::RetainCards, context.with_upgrade(2, 1));
+  }
+}
+
+impl CardBehavior for AThousandCuts {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::ThousandCuts, context.with_upgrade(2, 1));
+  }
+}
+
+impl CardBehavior for Adrenaline {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(GainEnergyAction(context.with_upgrade(2, 1)));
+    context.draw_cards(2);
+  }
+}
+
+impl CardBehavior for AfterImage {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::AfterImage, 1);
+  }
+}
+
+impl CardBehavior for Alchemize {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(ObtainRandomPotionAction);
+  }
+}
+
+impl CardBehavior for BulletTime {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(BulletTimeAction);
+    context.power_self(PowerId::NoDraw, -1);
+  }
+}
+
+impl CardBehavior for Burst {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Burst, context.with_upgrade(2, 1));
+  }
+}
+
+impl CardBehavior for CorpseExplosion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_target(PowerId::Poison, context.with_upgrade(9, 6));
+    context.power_target(PowerId::CorpseExplosion, 1);
+  }
+}
+
+impl CardBehavior for DieDieDie {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_monsters(context.with_upgrade(17, 13));
+  }
+}
+
+impl CardBehavior for Doppelganger {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let amount = context.energy_on_use() + context.with_upgrade(1, 0);
+    if amount > 0 {
+      context.power_self(PowerId::DrawCardNextTurn, amount);
+      context.power_self(PowerId::Energized, amount);
+    }
+  }
+}
+
+impl CardBehavior for Envenom {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Envenom, 1);
+  }
+}
+
+impl CardBehavior for GlassKnife {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..2 {
+      context.attack_target(context.with_upgrade(12, 8));
+    }
+    // TODO: damage decrease
+  }
+}
+
+impl CardBehavior for GrandFinale {
+  fn playable(self, state: &CombatState) -> bool {
+    state.draw_pile_len() == 0
+  }
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_monsters(context.with_upgrade(60, 50));
+  }
+}
+
+impl CardBehavior for Malaise {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let amount = context.energy_on_use() + context.with_upgrade(1, 0);
+    if amount > 0 {
+      context.power_target(PowerId::Strength, -amount);
+      context.power_target(PowerId::Weak, amount);
+    }
+  }
+}
+
+impl CardBehavior for Nightmare {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    //TODO
+  }
+}
+
+impl CardBehavior for PhantasmalKiller {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Phantasmal, 1);
+  }
+}
+
+impl CardBehavior for StormOfSteel {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(StormOfSteelAction {
+      upgraded: context.upgraded(),
+    });
+  }
+}
+
+impl CardBehavior for ToolsOfTheTrade {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::ToolsOfTheTrade, 1);
+  }
+}
+
+impl CardBehavior for Unload {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(18, 14));
+    context.action(UnloadAction);
+  }
+}
+
+impl CardBehavior for WraithForm {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::IntangiblePlayer, context.with_upgrade(3, 2));
+    context.power_self(PowerId::WraithForm, -1);
+  }
+}
+
+impl CardBehavior for StrikeB {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(9, 6));
+  }
+}
+
+impl CardBehavior for DefendB {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(8, 5));
+  }
+}
+
+impl CardBehavior for Zap {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.channel_orb(OrbType::Lightning);
+  }
+}
+
+impl CardBehavior for Dualcast {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(EvokeWithoutRemovingOrbAction);
+    context.action(EvokeOrbAction);
+  }
+}
+
+impl CardBehavior for BallLightning {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(10, 7));
+    context.channel_orb(OrbType::Lightning);
+  }
+}
+
+impl CardBehavior for Barrage {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.state().player.orbs.len() {
+      context.attack_target(context.with_upgrade(6, 4));
+    }
+  }
+}
+
+impl CardBehavior for BeamCell {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(4, 3));
+    context.power_target(PowerId::Vulnerable, context.with_upgrade(2, 1));
+  }
+}
+
+impl CardBehavior for Claw {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(5, 3));
+    // TODO: increase the damage of all Claws
+  }
+}
+
+impl CardBehavior for ColdSnap {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(9, 6));
+    context.channel_orb(OrbType::Frost);
+  }
+}
+
+impl CardBehavior for CompileDriver {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(10, 7));
+    let mut orb_types: Vec<OrbType> = context
+      .state()
+      .player
+      .orbs
+      .iter()
+      .map(|orb| orb.orb_type)
+      .collect();
+    orb_types.sort();
+    orb_types.dedup();
+    context.draw_cards(orb_types.len() as i32);
+  }
+}
+
+impl CardBehavior for ConserveBattery {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(10, 7));
+    context.power_self(PowerId::Energized, 1);
+  }
+}
+
+impl CardBehavior for Coolheaded {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.channel_orb(OrbType::Frost);
+    context.draw_cards(context.with_upgrade(2, 1));
+  }
+}
+
+impl CardBehavior for GoForTheEyes {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(4, 3));
+    if context.state().monster_is_attacking(context.target()) {
+      context.power_target(PowerId::Weak, context.with_upgrade(2, 1));
+    }
+  }
+}
+
+impl CardBehavior for Hologram {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(5, 3));
+    context.choose_card(CardChoiceType::DiscardToHand);
+  }
+}
+
+impl CardBehavior for Leap {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(12, 9));
+  }
+}
+
+impl CardBehavior for Rebound {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(12, 9));
+    context.power_self(PowerId::Rebound, 1);
+  }
+}
+
+impl CardBehavior for Recursion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(RedoAction);
+  }
+}
+
+impl CardBehavior for Stack {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let amount = context.state().discard_pile.len() as i32 + context.with_upgrade(3, 0);
+    context.block(amount);
+  }
+}
+
+impl CardBehavior for SteamBarrier {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(8, 6));
+    // TODO: block decrease
+  }
+}
+
+impl CardBehavior for Streamline {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(20, 15));
+    // TODO: cost decrease
+  }
+}
+
+impl CardBehavior for SweepingBeam {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_monsters(context.with_upgrade(9, 6));
+    context.draw_cards(1);
+  }
+}
+
+impl CardBehavior for Turbo {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(GainEnergyAction(context.with_upgrade(3, 2)));
+    context.action(DiscardNewCard(SingleCard::create(CardId::VoidCard)));
+  }
+}
+
+impl CardBehavior for Aggregate {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let divisor = context.with_upgrade(3, 4);
+    context.action(GainEnergyAction(
+      context.state().draw_pile_len() as i32 / divisor,
+    ));
+  }
+}
+
+impl CardBehavior for AutoShields {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    if context.state().player.creature.block == 0 {
+      context.block(context.with_upgrade(15, 11));
+    }
+  }
+}
+
+impl CardBehavior for Blizzard {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let frost = context.state().frost_channeled_this_combat;
+    context.attack_monsters(frost * context.with_upgrade(3, 2));
+  }
+}
+
+impl CardBehavior for BootSequence {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(13, 10));
+  }
+}
+
+impl CardBehavior for Bullseye {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(11, 8));
+    context.power_target(PowerId::LockOn, context.with_upgrade(3, 2));
+  }
+}
+
+impl CardBehavior for Capacitor {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(IncreaseMaxOrbAction(context.with_upgrade(3, 2)));
+  }
+}
+
+impl CardBehavior for Chaos {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.with_upgrade(2, 1) {
+      context.action(ChannelRandomOrbAction);
+    }
+  }
+}
+
+impl CardBehavior for Chill {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let monsters = context.state().monsters.iter().filter(|m| !m.gone).count();
+    for _ in 0..monsters {
+      context.channel_orb(OrbType::Frost);
+    }
+  }
+}
+
+impl CardBehavior for Consume {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Focus, context.with_upgrade(3, 2));
+    context.action(DecreaseMaxOrbAction(1));
+  }
+}
+
+impl CardBehavior for Darkness {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.channel_orb(OrbType::Dark);
+    if context.upgraded() {
+      context.action(DarkImpulseAction);
+    }
+  }
+}
+
+impl CardBehavior for Defragment {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Focus, context.with_upgrade(2, 1));
+  }
+}
+
+impl CardBehavior for DoomAndGloom {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_monsters(context.with_upgrade(14, 10));
+    context.channel_orb(OrbType::Dark);
+  }
+}
+
+impl CardBehavior for DoubleEnergy {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(GainEnergyAction(context.state().player.energy));
+  }
+}
+
+impl CardBehavior for Equilibrium {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(16, 13));
+    context.power_self(PowerId::Equilibrium, 1);
+  }
+}
+
+impl CardBehavior for ForceField {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(16, 12));
+    // TODO: cost decrease
+  }
+}
+
+impl CardBehavior for Ftl {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(6, 5));
+    // the count includes this card
+    if context.state().cards_played_this_turn <= context.with_upgrade(4, 3) {
+      context.draw_cards(1);
+    }
+  }
+}
+
+impl CardBehavior for Fusion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.channel_orb(OrbType::Plasma);
+  }
+}
+
+impl CardBehavior for GeneticAlgorithm {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.card().misc);
+    // TODO: the permanent increase only matters after combat
+  }
+}
+
+impl CardBehavior for Glacier {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(10, 7));
+    context.channel_orb(OrbType::Frost);
+    context.channel_orb(OrbType::Frost);
+  }
+}
+
+impl CardBehavior for Heatsinks {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Heatsink, context.with_upgrade(2, 1));
+  }
+}
+
+impl CardBehavior for HelloWorld {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::HelloWorld, 1);
+  }
+}
+
+impl CardBehavior for Loop {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Loop, context.with_upgrade(2, 1));
+  }
+}
+
+impl CardBehavior for Melter {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(RemoveAllBlockAction {
+      target: context.target_creature_index(),
+    });
+    context.attack_target(context.with_upgrade(14, 10));
+  }
+}
+
+impl CardBehavior for Overclock {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.draw_cards(context.with_upgrade(3, 2));
+    context.action(DiscardNewCard(SingleCard::create(CardId::Burn)));
+  }
+}
+
+impl CardBehavior for Recycle {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.choose_card(CardChoiceType::RecycleCard);
+  }
+}
+
+impl CardBehavior for ReinforcedBody {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.energy_on_use() {
+      context.block(context.with_upgrade(9, 7));
+    }
+  }
+}
+
+impl CardBehavior for Reprogram {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let amount = context.with_upgrade(2, 1);
+    context.power_self(PowerId::Focus, -amount);
+    context.power_self(PowerId::Strength, amount);
+    context.power_self(PowerId::Dexterity, amount);
+  }
+}
+
+impl CardBehavior for RipAndTear {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..2 {
+      context.attack_random_monster(context.with_upgrade(9, 7));
+    }
+  }
+}
+
+impl CardBehavior for Scrape {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(10, 7));
+    context.draw_cards(context.with_upgrade(5, 4));
+    // TODO: discard the drawn cards that don't cost 0
+  }
+}
+
+impl CardBehavior for SelfRepair {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Repair, context.with_upgrade(10, 7));
+  }
+}
+
+impl CardBehavior for Skim {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.draw_cards(context.with_upgrade(4, 3));
+  }
+}
+
+impl CardBehavior for StaticDischarge {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::StaticDischarge, context.with_upgrade(2, 1));
+  }
+}
+
+impl CardBehavior for Storm {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Storm, 1);
+  }
+}
+
+impl CardBehavior for Sunder {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(32, 24));
+    // TODO: energy if this kills
+  }
+}
+
+impl CardBehavior for Tempest {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.energy_on_use() + context.with_upgrade(1, 0) {
+      context.channel_orb(OrbType::Lightning);
+    }
+  }
+}
+
+impl CardBehavior for WhiteNoise {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    // TODO: a random power card
+  }
+}
+
+impl CardBehavior for AllForOne {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(14, 10));
+    context.action(AllForOneAction);
+  }
+}
+
+impl CardBehavior for Amplify {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Amplify, context.with_upgrade(2, 1));
+  }
+}
+
+impl CardBehavior for BiasedCognition {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Focus, context.with_upgrade(5, 4));
+    context.power_self(PowerId::BiasedCognition, 1);
+  }
+}
+
+impl CardBehavior for Buffer {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Buffer, context.with_upgrade(2, 1));
+  }
+}
+
+impl CardBehavior for CoreSurge {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(15, 11));
+    context.power_self(PowerId::Artifact, 1);
+  }
+}
+
+impl CardBehavior for CreativeAI {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::CreativeAI, 1);
+  }
+}
+
+impl CardBehavior for EchoForm {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::EchoForm, 1);
+  }
+}
+
+impl CardBehavior for Electrodynamics {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Electrodynamics, 1);
+    for _ in 0..context.with_upgrade(3, 2) {
+      context.channel_orb(OrbType::Lightning);
+    }
+  }
+}
+
+impl CardBehavior for Fission {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let count = context.state().player.orbs.len();
+    if context.upgraded() {
+      for _ in 0..count {
+        context.action(EvokeOrbAction);
+      }
+    } else {
+      context.action(RemoveAllOrbsAction);
+    }
+    context.action(GainEnergyAction(count as i32));
+    context.draw_cards(count as i32);
+  }
+}
+
+impl CardBehavior for Hyperbeam {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_monsters(context.with_upgrade(34, 26));
+    context.power_self(PowerId::Focus, -3);
+  }
+}
+
+impl CardBehavior for MachineLearning {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Draw, 1);
+  }
+}
+
+impl CardBehavior for MeteorStrike {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(30, 24));
+    for _ in 0..3 {
+      context.channel_orb(OrbType::Plasma);
+    }
+  }
+}
+
+impl CardBehavior for MultiCast {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let times = context.energy_on_use() + context.with_upgrade(1, 0);
+    if times > 0 {
+      for _ in 1..times {
+        context.action(EvokeWithoutRemovingOrbAction);
+      }
+      context.action(EvokeOrbAction);
+    }
+  }
+}
+
+impl CardBehavior for Rainbow {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.channel_orb(OrbType::Lightning);
+    context.channel_orb(OrbType::Frost);
+    context.channel_orb(OrbType::Dark);
+  }
+}
+
+impl CardBehavior for Reboot {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(ShuffleHandAndDiscardIntoDrawPile);
+    context.draw_cards(context.with_upgrade(6, 4));
+  }
+}
+
+impl CardBehavior for Seek {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.with_upgrade(2, 1) {
+      context.choose_card(CardChoiceType::DrawPileToHand);
+    }
+  }
+}
+
+impl CardBehavior for ThunderStrike {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.state().lightning_channeled_this_combat {
+      context.attack_random_monster(context.with_upgrade(9, 7));
+    }
+  }
+}
+
+impl CardBehavior for StrikeP {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(9, 6));
+  }
+}
+
+impl CardBehavior for DefendP {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(8, 5));
+  }
+}
+
+impl CardBehavior for Eruption {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(9);
+    context.change_stance(Stance::Wrath);
+  }
+}
+
+impl CardBehavior for Vigilance {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(12, 8));
+    context.change_stance(Stance::Calm);
+  }
+}
+
+impl CardBehavior for BowlingBash {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let count = context.state().monsters.iter().filter(|m| !m.gone).count();
+    for _ in 0..count {
+      context.attack_target(context.with_upgrade(10, 7));
+    }
+  }
+}
+
+impl CardBehavior for Consecrate {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_monsters(context.with_upgrade(8, 5));
+  }
+}
+
+impl CardBehavior for Crescendo {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.change_stance(Stance::Wrath);
+  }
+}
+
+impl CardBehavior for CrushJoints {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(10, 8));
+    if context.state().last_card_type_played == Some(Skill) {
+      context.power_target(PowerId::Vulnerable, context.with_upgrade(2, 1));
+    }
+  }
+}
+
+impl CardBehavior for CutThroughFate {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(9, 7));
+    context.scry(context.with_upgrade(3, 2));
+    context.draw_cards(1);
+  }
+}
+
+impl CardBehavior for EmptyBody {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(10, 7));
+    context.change_stance(Stance::Neutral);
+  }
+}
+
+impl CardBehavior for EmptyFist {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(14, 9));
+    context.change_stance(Stance::Neutral);
+  }
+}
+
+impl CardBehavior for Evaluate {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(10, 6));
+    context.action(ShuffleNewCardIntoDrawPile(SingleCard::create(
+      CardId::Insight,
+    )));
+  }
+}
+
+impl CardBehavior for FlurryOfBlows {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(6, 4));
+  }
+  fn on_stance_change(self, runner: &mut impl Runner, card: &SingleCard) {
+    return_from_discard_to_hand(runner.state_mut(), card);
+  }
+}
+
+impl CardBehavior for FlyingSleeves {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(6, 4));
+    context.attack_target(context.with_upgrade(6, 4));
+  }
+}
+
+impl CardBehavior for FollowUp {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(11, 7));
+    if context.state().last_card_type_played == Some(Attack) {
+      context.action(GainEnergyAction(1));
+    }
+  }
+}
+
+impl CardBehavior for Halt {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let mut amount = context.with_upgrade(4, 3);
+    if context.state().player.stance == Stance::Wrath {
+      amount += context.with_upgrade(14, 9);
+    }
+    context.block(amount);
+  }
+}
+
+impl CardBehavior for JustLucky {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.scry(context.with_upgrade(2, 1));
+    context.block(context.with_upgrade(3, 2));
+    context.attack_target(context.with_upgrade(4, 3));
+  }
+}
+
+impl CardBehavior for PressurePoints {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_target(PowerId::Mark, context.with_upgrade(11, 8));
+    context.action(TriggerMarksAction);
+  }
+}
+
+impl CardBehavior for Prostrate {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Mantra, context.with_upgrade(3, 2));
+    context.block(4);
+  }
+}
+
+impl CardBehavior for Protect {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(16, 12));
+  }
+}
+
+impl CardBehavior for SashWhip {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(10, 8));
+    if context.state().last_card_type_played == Some(Attack) {
+      context.power_target(PowerId::Weak, context.with_upgrade(2, 1));
+    }
+  }
+}
+
+impl CardBehavior for Tranquility {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.change_stance(Stance::Calm);
+  }
+}
+
+impl CardBehavior for ThirdEye {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(9, 7));
+    context.scry(context.with_upgrade(5, 3));
+  }
+}
+
+impl CardBehavior for BattleHymn {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::BattleHymn, 1);
+  }
+}
+
+impl CardBehavior for CarveReality {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(10, 6));
+    context.action(AddNewCardToHand(SingleCard::create(CardId::Smite)));
+  }
+}
+
+impl CardBehavior for Collect {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let amount = context.energy_on_use() + context.with_upgrade(1, 0);
+    if amount > 0 {
+      context.power_self(PowerId::Collect, amount);
+    }
+  }
+}
+
+impl CardBehavior for Conclude {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_monsters(context.with_upgrade(16, 12));
+    context.action(EndTurn);
+  }
+}
+
+impl CardBehavior for DeceiveReality {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(7, 4));
+    context.action(AddNewCardToHand(SingleCard::create(CardId::Safety)));
+  }
+}
+
+impl CardBehavior for EmptyMind {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.change_stance(Stance::Neutral);
+    context.draw_cards(context.with_upgrade(3, 2));
+  }
+}
+
+impl CardBehavior for Fasting {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Strength, context.with_upgrade(4, 3));
+    context.power_self(PowerId::Dexterity, context.with_upgrade(4, 3));
+    context.power_self(PowerId::EnergyDown, 1);
+  }
+}
+
+impl CardBehavior for FearNoEvil {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(11, 8));
+    if context.state().monster_is_attacking(context.target()) {
+      context.change_stance(Stance::Calm);
+    }
+  }
+}
+
+impl CardBehavior for ForeignInfluence {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    // TODO: choose one of 3 random attacks from any character
+  }
+}
+
+impl CardBehavior for Foresight {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Foresight, context.with_upgrade(4, 3));
+  }
+}
+
+impl CardBehavior for Indignation {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    if context.state().player.stance == Stance::Wrath {
+      context.power_monsters(PowerId::Vulnerable, context.with_upgrade(5, 3));
+    } else {
+      context.change_stance(Stance::Wrath);
+    }
+  }
+}
+
+impl CardBehavior for InnerPeace {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    if context.state().player.stance == Stance::Calm {
+      context.draw_cards(context.with_upgrade(4, 3));
+    } else {
+      context.change_stance(Stance::Calm);
+    }
+  }
+}
+
+impl CardBehavior for LikeWater {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::LikeWater, context.with_upgrade(7, 5));
+  }
+}
+
+impl CardBehavior for Meditate {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.with_upgrade(2, 1) {
+      context.choose_card(CardChoiceType::DiscardToHandRetained);
+    }
+    context.change_stance(Stance::Calm);
+    context.action(EndTurn);
+  }
+}
+
+impl CardBehavior for MentalFortress {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::MentalFortress, context.with_upgrade(6, 4));
+  }
+}
+
+impl CardBehavior for Nirvana {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Nirvana, context.with_upgrade(4, 3));
+  }
+}
+
+impl CardBehavior for Perseverance {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(7, 5) + context.card().misc);
+  }
+  fn on_retained(self, card: &mut SingleCard) {
+    card.misc += if card.upgrades > 0 { 3 } else { 2 };
+  }
+}
+
+impl CardBehavior for Pray {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Mantra, context.with_upgrade(4, 3));
+    context.action(ShuffleNewCardIntoDrawPile(SingleCard::create(
+      CardId::Insight,
+    )));
+  }
+}
+
+impl CardBehavior for ReachHeaven {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(15, 10));
+    context.action(ShuffleNewCardIntoDrawPile(SingleCard::create(
+      CardId::ThroughViolence,
+    )));
+  }
+}
+
+impl CardBehavior for Rushdown {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Rushdown, 2);
+  }
+}
+
+impl CardBehavior for Sanctity {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(9, 6));
+    if context.state().last_card_type_played == Some(Skill) {
+      context.draw_cards(2);
+    }
+  }
+}
+
+impl CardBehavior for SandsOfTime {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(26, 20));
+  }
+  fn on_retained(self, card: &mut SingleCard) {
+    if card.cost > 0 {
+      card.cost -= 1;
+    }
+  }
+}
+
+impl CardBehavior for SignatureMove {
+  fn playable(self, state: &CombatState) -> bool {
+    // no attacks in hand other than this one
+    state
+      .hand
+      .iter()
+      .filter(|card| card.card_info.card_type == Attack)
+      .count()
+      <= 1
+  }
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(40, 30));
+  }
+}
+
+impl CardBehavior for Study {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Study, 1);
+  }
+}
+
+impl CardBehavior for Swivel {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(11, 8));
+    context.power_self(PowerId::FreeAttack, 1);
+  }
+}
+
+impl CardBehavior for TalkToTheHand {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(7, 5));
+    context.power_target(PowerId::BlockReturn, context.with_upgrade(3, 2));
+  }
+}
+
+impl CardBehavior for Tantrum {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.with_upgrade(4, 3) {
+      context.attack_target(3);
+    }
+    context.change_stance(Stance::Wrath);
+  }
+}
+
+impl CardBehavior for SimmeringFury {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::WrathNextTurn, 1);
+    context.power_self(PowerId::DrawCardNextTurn, context.with_upgrade(3, 2));
+  }
+}
+
+impl CardBehavior for WaveOfTheHand {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::WaveOfTheHand, context.with_upgrade(2, 1));
+  }
+}
+
+impl CardBehavior for Weave {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(6, 4));
+  }
+  fn on_scry(self, runner: &mut impl Runner, card: &SingleCard) {
+    return_from_discard_to_hand(runner.state_mut(), card);
+  }
+}
+
+impl CardBehavior for WheelKick {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(20, 15));
+    context.draw_cards(2);
+  }
+}
+
+impl CardBehavior for WindmillStrike {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(10, 7) + context.card().misc);
+  }
+  fn on_retained(self, card: &mut SingleCard) {
+    card.misc += if card.upgrades > 0 { 5 } else { 4 };
+  }
+}
+
+impl CardBehavior for Worship {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Mantra, 5);
+  }
+}
+
+impl CardBehavior for WreathOfFlame {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Vigor, context.with_upgrade(8, 5));
+  }
+}
+
+impl CardBehavior for Alpha {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(ShuffleNewCardIntoDrawPile(SingleCard::create(CardId::Beta)));
+  }
+}
+
+impl CardBehavior for Blasphemy {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.change_stance(Stance::Divinity);
+    context.power_self(PowerId::EndTurnDeath, 0);
+  }
+}
+
+impl CardBehavior for Brilliance {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let mantra = context.state().mantra_gained_this_combat;
+    context.attack_target(context.with_upgrade(16, 12) + mantra);
+  }
+}
+
+impl CardBehavior for ConjureBlade {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let mut expunger = SingleCard::create(CardId::Expunger);
+    // the number of hits
+    expunger.misc = context.energy_on_use() + context.with_upgrade(1, 0);
+    context.action(ShuffleNewCardIntoDrawPile(expunger));
+  }
+}
+
+impl CardBehavior for DeusExMachina {
+  fn on_draw(self, runner: &mut impl Runner, card: &mut SingleCard) {
+    let count = if card.upgrades > 0 { 3 } else { 2 };
+    for _ in 0..count {
+      runner.action_top(AddNewCardToHand(SingleCard::create(CardId::Miracle)));
+    }
+    runner.action_top(ExhaustSpecificCardAction(card.clone()));
+  }
+}
+
+impl CardBehavior for DevaForm {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::DevaForm, 1);
+  }
+}
+
+impl CardBehavior for Devotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Devotion, context.with_upgrade(3, 2));
+  }
+}
+
+impl CardBehavior for Establishment {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Establishment, 1);
+  }
+}
+
+impl CardBehavior for Judgement {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(JudgementAction {
+      target: context.target_creature_index(),
+      threshold: context.with_upgrade(40, 30),
+    });
+  }
+}
+
+impl CardBehavior for LessonLearned {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(13, 10));
+  }
+}
+
+impl CardBehavior for MasterReality {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::MasterReality, 1);
+  }
+}
+
+impl CardBehavior for Omniscience {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.choose_card(CardChoiceType::DrawPilePlayTwice);
+  }
+}
+
+impl CardBehavior for Ragnarok {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.with_upgrade(6, 5) {
+      context.attack_random_monster(context.with_upgrade(6, 5));
+    }
+  }
+}
+
+impl CardBehavior for Scrawl {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(ExpertiseAction(10));
+  }
+}
+
+impl CardBehavior for SpiritShield {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let count = context.state().hand.len() as i32;
+    context.block(context.with_upgrade(4, 3) * count);
+  }
+}
+
+impl CardBehavior for Vault {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(SkipMonsterTurnAction);
+    context.action(EndTurn);
+  }
+}
+
+impl CardBehavior for Wallop {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let info = DamageInfoNoPowers::new(
+      Some(CreatureIndex::Player),
+      context.with_upgrade(12, 9),
+      DamageType::Normal,
+    )
+    .apply_all_powers(context.state(), context.target_creature_index());
+    context.action(WallopDamageAction {
+      target: context.target_creature_index(),
+      info,
+    });
+  }
+}
+
+impl CardBehavior for Wish {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(ChooseOneAction {
+      options: [
+        CardId::BecomeAlmighty,
+        CardId::FameAndFortune,
+        CardId::LiveForever,
+      ]
+      .iter()
+      .copied()
+      .collect(),
+      upgraded: context.upgraded(),
+    });
+  }
+}
+
+impl CardBehavior for Jax {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(DamageAction {
+      target: CreatureIndex::Player,
+      info: DamageInfoNoPowers::new(Some(CreatureIndex::Player), 3, DamageType::HitpointLoss)
+        .ignore_powers(),
+    });
+    context.power_self(PowerId::Strength, context.with_upgrade(3, 2));
+  }
+}
+
+impl CardBehavior for Shiv {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let accuracy = context
+      .state()
+      .player
+      .creature
+      .power_amount(PowerId::Accuracy);
+    context.attack_target(context.with_upgrade(6, 4) + accuracy);
+  }
+}
+
+impl CardBehavior for Miracle {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(GainEnergyAction(context.with_upgrade(2, 1)));
+  }
+}
+
+impl CardBehavior for Insight {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.draw_cards(context.with_upgrade(3, 2));
+  }
+}
+
+impl CardBehavior for Smite {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(16, 12));
+  }
+}
+
+impl CardBehavior for Safety {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.with_upgrade(16, 12));
+  }
+}
+
+impl CardBehavior for ThroughViolence {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.attack_target(context.with_upgrade(30, 20));
+  }
+}
+
+impl CardBehavior for Beta {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(ShuffleNewCardIntoDrawPile(SingleCard::create(
+      CardId::Omega,
+    )));
+  }
+}
+
+impl CardBehavior for Omega {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Omega, context.with_upgrade(60, 50));
+  }
+}
+
+impl CardBehavior for Expunger {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.card().misc {
+      context.attack_target(context.with_upgrade(15, 9));
+    }
+  }
+}
+
+impl CardBehavior for ChooseWrath {
+  fn on_chosen(self, runner: &mut impl Runner, _card: &SingleCard) {
+    runner.action_now(&ChangeStanceAction(Stance::Wrath));
+  }
+}
+
+impl CardBehavior for ChooseCalm {
+  fn on_chosen(self, runner: &mut impl Runner, _card: &SingleCard) {
+    runner.action_now(&ChangeStanceAction(Stance::Calm));
+  }
+}
+
+impl CardBehavior for BecomeAlmighty {
+  fn on_chosen(self, runner: &mut impl Runner, card: &SingleCard) {
+    runner.action_now(&ApplyPowerAction {
+      source: CreatureIndex::Player,
+      target: CreatureIndex::Player,
+      power_id: PowerId::Strength,
+      amount: if card.upgrades > 0 { 4 } else { 3 },
+    });
+  }
+}
+
+// gold isn't part of combat, so this does nothing here
+impl CardBehavior for FameAndFortune {}
+
+impl CardBehavior for LiveForever {
+  fn on_chosen(self, runner: &mut impl Runner, card: &SingleCard) {
+    runner.action_now(&ApplyPowerAction {
+      source: CreatureIndex::Player,
+      target: CreatureIndex::Player,
+      power_id: PowerId::PlatedArmor,
+      amount: if card.upgrades > 0 { 8 } else { 6 },
+    });
+  }
+}
+
+impl CardBehavior for Apotheosis {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(UpgradeCardsAction::new(
+      UpgradeCardsFilter::Upgradable,
+      &[
+        CardPile::Hand,
+        CardPile::DrawPile,
+        CardPile::DiscardPile,
+        CardPile::ExhaustPile,
+      ],
+    ));
+  }
+}
+
+impl CardBehavior for Injury {}
+impl CardBehavior for AscendersBane {}
+impl CardBehavior for Dazed {}
+impl CardBehavior for Slimed {}
+impl CardBehavior for Burn {}
+impl CardBehavior for Wound {}
+impl CardBehavior for VoidCard {
+  fn on_draw(self, runner: &mut impl Runner, card: &mut SingleCard) {
+    let energy = &mut runner.state_mut().player.energy;
+    *energy = std::cmp::max(0, *energy - 1);
+  }
+}
+
+impl CardBehavior for AncientPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Artifact, context.potency(1));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    8.0
+  }
+}
+
+impl CardBehavior for AttackPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(DiscoveryAction {
+      card_type: Attack,
+      copies: context.potency(1),
+    });
+  }
+}
+
+impl CardBehavior for BlockPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.block(context.potency(12));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    11.5
+  }
+}
+
+impl CardBehavior for BloodPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(HealAction {
+      creature_index: CreatureIndex::Player,
+      amount: ((context.state().player.creature.max_hitpoints * context.potency(20)) as f64 / 100.0)
+        as i32,
+    });
+  }
+  fn potion_value(self, state: &CombatState) -> f64 {
+    // Holding a blood potion is only worth slightly more than the hitpoints you get from using it:
+    ((state.player.creature.max_hitpoints * 20) as f64 / 100.0).floor() + 1.0
+  }
+}
+
+impl CardBehavior for BottledMiracle {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.potency(2) {
+      context.action(AddNewCardToHand(SingleCard::create(CardId::Miracle)));
+    }
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    10.0
+  }
+}
+
+impl CardBehavior for ColorlessPotion {
+  fn playable(self, state: &CombatState) -> bool {
+    // TODO: there are no colorless cards to pick from yet
+    false
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    8.0
+  }
+}
+
+impl CardBehavior for CultistPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Ritual, context.potency(1));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    14.0
+  }
+}
+
+impl CardBehavior for CunningPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.potency(3) {
+      let mut shiv = SingleCard::create(CardId::Shiv);
+      shiv.upgrade();
+      context.action(AddNewCardToHand(shiv));
+    }
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    14.0
+  }
+}
+
+impl CardBehavior for DexterityPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Dexterity, context.potency(2));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    10.0
+  }
+}
+
+impl CardBehavior for DistilledChaos {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.potency(3) {
+      context.action(PlayTopCardAction);
+    }
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    14.0
+  }
+}
+
+impl CardBehavior for DuplicationPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Duplication, context.potency(1));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    13.0
+  }
+}
+
+impl CardBehavior for Elixir {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.choose_card(CardChoiceType::ExhaustAnyCards);
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    6.0
+  }
+}
+
+impl CardBehavior for EnergyPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(GainEnergyAction(context.potency(2)));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    10.0
+  }
+}
+
+impl CardBehavior for EntropicBrew {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    // this potion's own slot is already empty
+    let state = context.state();
+    for _ in state.potions.len()..state.potion_slots {
+      context.action(ObtainRandomPotionAction);
+    }
+  }
+  fn potion_value(self, state: &CombatState) -> f64 {
+    // roughly the value of the potions it turns into
+    6.0 * state.potion_slots as f64
+  }
+}
+
+impl CardBehavior for EssenceOfDarkness {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.state().player.orb_slots as i32 * context.potency(1) {
+      context.channel_orb(OrbType::Dark);
+    }
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    16.0
+  }
+}
+
+impl CardBehavior for EssenceOfSteel {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::PlatedArmor, context.potency(4));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    12.0
+  }
+}
+
+impl CardBehavior for ExplosivePotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let info = DamageInfoNoPowers::new(None, context.potency(10), DamageType::Normal);
+    context.action(DamageAllEnemiesActionIgnoringPowers { info });
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    16.0
+  }
+}
+
+// can't be drunk; it revives you when you would die, which DamageAction takes care of
+impl CardBehavior for FairyPotion {
+  fn playable(self, state: &CombatState) -> bool {
+    false
+  }
+  fn potion_value(self, state: &CombatState) -> f64 {
+    // it's worth more than the hitpoints it restores, because it only triggers when you need it
+    ((state.player.creature.max_hitpoints * 30) as f64 / 100.0).floor() + 10.0
+  }
+}
+
+impl CardBehavior for FearPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_target(PowerId::Vulnerable, context.potency(3));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    10.0
+  }
+}
+
+impl CardBehavior for FirePotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    let info = DamageInfoNoPowers::new(None, context.potency(20), DamageType::Thorns)
+      .apply_all_powers(context.state(), context.target_creature_index());
+    context.action(DamageAction {
+      target: context.target_creature_index(),
+      info,
+    });
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    14.0
+  }
+}
+
+impl CardBehavior for FocusPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Focus, context.potency(2));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    12.0
+  }
+}
+
+impl CardBehavior for FruitJuice {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(IncreaseMaxHpAction(context.potency(5)));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    // it's worth the same whenever it's drunk, so there's no reason to hold on to it
+    5.0
+  }
+}
+
+impl CardBehavior for GamblersBrew {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.choose_card(CardChoiceType::GambleDiscard { discarded: 0 });
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    6.0
+  }
+}
+
+impl CardBehavior for GhostInAJar {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::IntangiblePlayer, context.potency(1));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    25.0
+  }
+}
+
+impl CardBehavior for HeartOfIron {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Metallicize, context.potency(6));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    16.0
+  }
+}
+
+impl CardBehavior for LiquidBronze {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Thorns, context.potency(3));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    10.0
+  }
+}
+
+impl CardBehavior for LiquidMemories {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    for _ in 0..context.potency(1) {
+      context.choose_card(CardChoiceType::DiscardToHandFree);
+    }
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    10.0
+  }
+}
+
+impl CardBehavior for PoisonPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_target(PowerId::Poison, context.potency(6));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    10.0
+  }
+}
+
+impl CardBehavior for PotionOfCapacity {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(IncreaseMaxOrbAction(context.potency(2)));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    8.0
+  }
+}
+
+impl CardBehavior for PowerPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(DiscoveryAction {
+      card_type: Power,
+      copies: context.potency(1),
+    });
+  }
+}
+
+impl CardBehavior for RegenPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Regeneration, context.potency(5));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    // it heals 15 over 5 turns, if the fight lasts that long
+    12.0
+  }
+}
+
+impl CardBehavior for SkillPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(DiscoveryAction {
+      card_type: Skill,
+      copies: context.potency(1),
+    });
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    10.0
+  }
+}
+
+impl CardBehavior for SmokeBomb {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(EscapeCombatAction);
+  }
+  fn playable(self, state: &CombatState) -> bool {
+    // there's no escaping bosses, or monsters that have the player surrounded
+    !state.monsters.iter().any(|monster| {
+      matches!(
+        monster.monster_id,
+        MonsterId::TheGuardian
+          | MonsterId::Hexaghost
+          | MonsterId::SlimeBoss
+          | MonsterId::BronzeAutomaton
+          | MonsterId::TheCollector
+          | MonsterId::TheChamp
+          | MonsterId::AwakenedOne
+          | MonsterId::TimeEater
+          | MonsterId::Donu
+          | MonsterId::Deca
+          | MonsterId::CorruptHeart
+      ) || monster.creature.has_power(PowerId::BackAttack)
+    })
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    10.0
+  }
+}
+
+impl CardBehavior for SneckoOil {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.draw_cards(context.potency(5));
+    // TODO: randomize the costs of the cards in hand
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    12.0
+  }
+}
+
+impl CardBehavior for SpeedPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Dexterity, context.potency(5));
+    context.power_self(PowerId::LoseDexterity, context.potency(5));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    9.0
+  }
+}
+
+impl CardBehavior for StancePotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(ChooseOneAction {
+      options: [CardId::ChooseWrath, CardId::ChooseCalm]
+        .iter()
+        .copied()
+        .collect(),
+      upgraded: false,
+    });
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    10.0
+  }
+}
+
+impl CardBehavior for SteroidPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Strength, context.potency(5));
+    context.power_self(PowerId::LoseStrength, context.potency(5));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    10.0
+  }
+}
+
+impl CardBehavior for StrengthPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_self(PowerId::Strength, context.potency(2));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    14.0
+  }
+}
+
+impl CardBehavior for SwiftPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.draw_cards(context.potency(3));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    10.0
+  }
+}
+
+impl CardBehavior for WeakPotion {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.power_target(PowerId::Weak, context.potency(3));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    9.0
+  }
+}
+
+impl CardBehavior for BlessingOfTheForge {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.action(UpgradeCardsAction::new(
+      UpgradeCardsFilter::Upgradable,
+      &[CardPile::Hand],
+    ));
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    7.0
+  }
+}
+
+impl CardBehavior for Ambrosia {
+  fn behavior(self, context: &mut impl CardBehaviorContext) {
+    context.change_stance(Stance::Divinity);
+  }
+  fn potion_value(self, _state: &CombatState) -> f64 {
+    16.0
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::ai_utils::CombatResult;
+  use crate::simulation::tests::*;
+  use crate::simulation_state::monsters::exordium::CultistIntent;
+  use crate::simulation_state::monsters::Intent;
+
+  /// A Cultist that spends the monster turn on Incantation, so ending the turn is harmless.
+  fn cultist(hand: &[CardId]) -> CombatState {
+    let mut state = combat(hand, &[MonsterId::Cultist]);
+    state.monsters[0].move_history = vec![CultistIntent::Incantation.id()];
+    state
+  }
+
+  #[test]
+  fn silent_cards_are_imported() {
+    for &name in &[
+      "Neutralize",
+      "Survivor",
+      "Blade Dance",
+      "Well Laid Plans",
+      "Shiv",
+    ] {
+      assert_ne!(CardId::from(name), CardId::Injury, "{}", name);
+    }
+  }
+
+  #[test]
+  fn accuracy_applies_to_new_shivs() {
+    let mut state = cultist(&[CardId::BladeDance]);
+    run(
+      &mut state,
+      ApplyPowerAction {
+        source: CreatureIndex::Player,
+        target: CreatureIndex::Player,
+        power_id: PowerId::Accuracy,
+        amount: 4,
+      },
+    );
+    play(&mut state, CardId::BladeDance, 0);
+    assert_eq!(
+      state.hand.as_slice(),
+      cards(&[CardId::Shiv, CardId::Shiv, CardId::Shiv]).as_slice()
+    );
+    play(&mut state, CardId::Shiv, 0);
+    assert_eq!(state.monsters[0].creature.hitpoints, 40 - 8);
+    // Shivs exhaust
+    assert_eq!(state.exhaust_pile, cards(&[CardId::Shiv]));
+  }
+
+  #[test]
+  fn poison_ticks_at_the_start_of_the_monster_turn() {
+    let mut state = cultist(&[CardId::DeadlyPoison]);
+    play(&mut state, CardId::DeadlyPoison, 0);
+    assert_eq!(state.monsters[0].creature.power_amount(PowerId::Poison), 5);
+    assert_eq!(state.monsters[0].creature.hitpoints, 40);
+    choose(&mut state, EndTurn);
+    assert_eq!(state.monsters[0].creature.hitpoints, 35);
+    assert_eq!(state.monsters[0].creature.power_amount(PowerId::Poison), 4);
+  }
+
+  #[test]
+  fn manual_discards_trigger_tactician() {
+    let mut state = cultist(&[CardId::Survivor, CardId::Tactician, CardId::StrikeG]);
+    play(&mut state, CardId::Survivor, 0);
+    assert_eq!(state.player.creature.block, 8);
+    let pending = state.pending_choice().expect("Survivor asks for a discard");
+    assert_eq!(pending.choice_type, CardChoiceType::DiscardCard);
+    choose(
+      &mut state,
+      SelectCard {
+        card: SingleCard::create(CardId::Tactician),
+        choice_type: CardChoiceType::DiscardCard,
+      },
+    );
+    assert_eq!(
+      state.discard_pile,
+      cards(&[CardId::Survivor, CardId::Tactician])
+    );
+    assert_eq!(state.player.energy, 3 - 1 + 1);
+  }
+
+  #[test]
+  fn well_laid_plans_retains_a_card() {
+    let mut state = cultist(&[CardId::WellLaidPlans, CardId::StrikeG, CardId::DefendG]);
+    play(&mut state, CardId::WellLaidPlans, 0);
+    choose(&mut state, EndTurn);
+    let choice_type = state
+      .pending_choice()
+      .expect("Well-Laid Plans asks what to retain")
+      .choice_type;
+    assert_eq!(choice_type, CardChoiceType::RetainCard { remaining: 1 });
+    choose(
+      &mut state,
+      SelectCard {
+        card: SingleCard::create(CardId::StrikeG),
+        choice_type: choice_type,
+      },
+    );
+    assert_eq!(state.turn_number, 2);
+    // Defend was discarded, then drawn again by the new turn along with the reshuffle
+    assert!(state
+      .hand
+      .iter()
+      .any(|card| card.card_info.id == CardId::StrikeG));
+    assert_eq!(state.hand.len(), 2);
+  }
+
+  #[test]
+  fn apotheosis_upgrades_every_pile() {
+    let mut state = combat(&[CardId::Apotheosis, CardId::Bash], &[MonsterId::Cultist]);
+    state.draw_pile = cards(&[CardId::StrikeR, CardId::Burn]);
+    state.discard_pile = cards(&[CardId::DefendR]);
+    state.exhaust_pile = cards(&[CardId::Anger]);
+    play(&mut state, CardId::Apotheosis, 0);
+    let upgraded = |cards: &[SingleCard]| cards.iter().all(|card| card.upgrades == 1);
+    assert!(upgraded(&state.hand));
+    assert!(upgraded(&state.discard_pile));
+    assert!(state
+      .draw_pile
+      .iter()
+      .all(|card| card.upgrades == (card.card_info.id == CardId::StrikeR) as i32));
+    // Apotheosis itself is exhausted after it upgrades everything
+    assert!(upgraded(&state.exhaust_pile[..1]));
+    assert_eq!(state.exhaust_pile[1].card_info.id, CardId::Apotheosis);
+  }
+
+  fn use_potion(state: &mut CombatState, potion: CardId) {
+    let potion_info = <&CardInfo>::from(potion);
+    state.potions.push(potion_info);
+    choose(
+      state,
+      UsePotion {
+        potion_info,
+        target: 0,
+      },
+    );
+  }
+
+  #[test]
+  fn stance_potion_offers_wrath_and_calm() {
+    let mut state = cultist(&[]);
+    use_potion(&mut state, CardId::StancePotion);
+    let pending = state
+      .pending_choice()
+      .expect("Stance Potion asks for a stance");
+    assert_eq!(pending.choice_type, CardChoiceType::ChooseOne);
+    assert_eq!(
+      state.offered_cards,
+      cards(&[CardId::ChooseWrath, CardId::ChooseCalm])
+    );
+    choose(
+      &mut state,
+      SelectCard {
+        card: SingleCard::create(CardId::ChooseCalm),
+        choice_type: CardChoiceType::ChooseOne,
+      },
+    );
+    assert_eq!(state.player.stance, Stance::Calm);
+    assert!(state.offered_cards.is_empty());
+    assert!(state.pending_choice().is_none());
+  }
+
+  #[test]
+  fn smoke_bomb_ends_the_combat_without_winning_it() {
+    let mut state = cultist(&[]);
+    state.potion_slots = 3;
+    assert!(CardId::SmokeBomb.playable(&state));
+    use_potion(&mut state, CardId::SmokeBomb);
+    assert!(state.player.escaped);
+    assert!(state.combat_over());
+    assert_eq!(state.monsters[0].creature.hitpoints, 40);
+
+    let mut won = cultist(&[]);
+    won.potion_slots = 3;
+    won.monsters[0].creature.hitpoints = 0;
+    won.monsters[0].gone = true;
+    assert!(CombatResult::new(&state).score < CombatResult::new(&won).score);
+
+    assert!(!CardId::SmokeBomb.playable(&combat(&[], &[MonsterId::Hexaghost])));
   }
 }
@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+use crate::simulation_state::*;
+
+/// How faithfully we simulate a card, power or monster. Everything declares this in the `cards!`,
+/// `powers!` and `monsters!` lists, defaulting to `Complete`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Fidelity {
+  /// It does nothing, or something else entirely. Says what's missing.
+  Missing(&'static str),
+  /// Some of its effects are missing or approximated. Says which.
+  Partial(&'static str),
+  Complete,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
+pub enum AuditedEntity {
+  Card(CardId),
+  Power(PowerId),
+  Monster(MonsterId),
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FidelityWarning {
+  pub entity: AuditedEntity,
+  pub fidelity: Fidelity,
+}
+
+/// The entities in a combat that we don't simulate faithfully, worst first.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct FidelityAudit {
+  pub warnings: Vec<FidelityWarning>,
+}
+
+impl AuditedEntity {
+  pub fn fidelity(self) -> Fidelity {
+    match self {
+      AuditedEntity::Card(card_id) => <&CardInfo>::from(card_id).fidelity,
+      AuditedEntity::Power(power_id) => power_id.fidelity(),
+      AuditedEntity::Monster(monster_id) => monster_id.fidelity(),
+    }
+  }
+}
+
+impl FidelityAudit {
+  fn check(&mut self, entity: AuditedEntity) {
+    let fidelity = entity.fidelity();
+    if fidelity != Fidelity::Complete
+      && !self.warnings.iter().any(|warning| warning.entity == entity)
+    {
+      self.warnings.push(FidelityWarning { entity, fidelity });
+    }
+  }
+  pub fn is_complete(&self) -> bool {
+    self.warnings.is_empty()
+  }
+  /// Whether something in the combat isn't simulated at all.
+  pub fn anything_missing(&self) -> bool {
+    self
+      .warnings
+      .iter()
+      .any(|warning| matches!(warning.fidelity, Fidelity::Missing(_)))
+  }
+}
+
+impl CombatState {
+  /// Everything in this combat, including cards that haven't been drawn yet and potions, that
+  /// the simulation doesn't model faithfully.
+  pub fn fidelity_audit(&self) -> FidelityAudit {
+    let mut audit = FidelityAudit::default();
+    let piles = [
+      &self.draw_pile,
+      &self.draw_pile_top,
+      &self.discard_pile,
+      &self.exhaust_pile,
+      &self.limbo,
+    ];
+    let cards = piles
+      .iter()
+      .flat_map(|pile| pile.iter())
+      .chain(&self.hand)
+      .chain(&self.card_in_play)
+      .map(|card| card.card_info.id)
+      .chain(self.potions.iter().map(|potion| potion.id));
+    for card_id in cards {
+      audit.check(AuditedEntity::Card(card_id));
+    }
+    for power in &self.player.creature.powers {
+      audit.check(AuditedEntity::Power(power.power_id));
+    }
+    for monster in &self.monsters {
+      if monster.gone && !monster.half_dead {
+        continue;
+      }
+      audit.check(AuditedEntity::Monster(monster.monster_id));
+      for power in &monster.creature.powers {
+        audit.check(AuditedEntity::Power(power.power_id));
+      }
+    }
+    audit.warnings.sort_by_key(|warning| warning.fidelity);
+    audit
+  }
+}
+
+impl Display for AuditedEntity {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      AuditedEntity::Card(card_id) => write!(f, "card {:?}", card_id),
+      AuditedEntity::Power(power_id) => write!(f, "power {:?}", power_id),
+      AuditedEntity::Monster(monster_id) => write!(f, "monster {:?}", monster_id),
+    }
+  }
+}
+
+impl Display for FidelityWarning {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self.fidelity {
+      Fidelity::Missing(what) => write!(f, "Not simulated: {} ({})", self.entity, what),
+      Fidelity::Partial(what) => write!(f, "Partly simulated: {} ({})", self.entity, what),
+      Fidelity::Complete => write!(f, "Simulated: {}", self.entity),
+    }
+  }
+}
+
+impl Display for FidelityAudit {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    for warning in &self.warnings {
+      writeln!(f, "{}", warning)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::simulation::tests::*;
+
+  fn power(power_id: PowerId) -> Power {
+    Power {
+      power_id,
+      amount: 1,
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn a_faithfully_simulated_combat_has_no_warnings() {
+    let mut state = combat(&[CardId::StrikeR, CardId::Bash], &[MonsterId::JawWorm]);
+    state.draw_pile = cards(&[CardId::DefendR]);
+    state.player.creature.powers.push(power(PowerId::Strength));
+    let audit = state.fidelity_audit();
+    assert!(audit.is_complete(), "{}", audit);
+  }
+
+  #[test]
+  fn the_audit_covers_every_pile_and_creature() {
+    let mut state = combat(
+      &[CardId::BloodForBlood],
+      &[MonsterId::UnknownMonster, MonsterId::JawWorm],
+    );
+    state.draw_pile = cards(&[CardId::Bloodletting]);
+    state.discard_pile = cards(&[CardId::BloodForBlood]);
+    state.player.creature.powers.push(power(PowerId::Combust));
+    state.monsters[1]
+      .creature
+      .powers
+      .push(power(PowerId::Juggernaut));
+    let entities: Vec<AuditedEntity> = state
+      .fidelity_audit()
+      .warnings
+      .iter()
+      .map(|warning| warning.entity)
+      .collect();
+    // missing comes before partial, and each entity is only listed once
+    assert_eq!(
+      entities,
+      [
+        AuditedEntity::Card(CardId::Bloodletting),
+        AuditedEntity::Power(PowerId::Combust),
+        AuditedEntity::Power(PowerId::Juggernaut),
+        AuditedEntity::Card(CardId::BloodForBlood),
+        AuditedEntity::Monster(MonsterId::UnknownMonster),
+      ]
+    );
+  }
+
+  #[test]
+  fn dead_monsters_dont_count() {
+    let mut state = combat(&[], &[MonsterId::UnknownMonster, MonsterId::JawWorm]);
+    state.monsters[0].gone = true;
+    assert!(state.fidelity_audit().is_complete());
+    state.monsters[0].half_dead = true;
+    assert!(!state.fidelity_audit().is_complete());
+  }
+
+  #[test]
+  fn warnings_say_what_is_missing() {
+    let warning = FidelityWarning {
+      entity: AuditedEntity::Card(CardId::Bloodletting),
+      fidelity: AuditedEntity::Card(CardId::Bloodletting).fidelity(),
+    };
+    assert_eq!(
+      warning.to_string(),
+      "Not simulated: card Bloodletting (does nothing)"
+    );
+  }
+
+  #[test]
+  fn only_missing_entities_count_as_missing() {
+    let mut state = combat(&[CardId::BloodForBlood], &[MonsterId::JawWorm]);
+    assert!(!state.fidelity_audit().anything_missing());
+    state.player.creature.powers.push(power(PowerId::Combust));
+    assert!(state.fidelity_audit().anything_missing());
+  }
+}
@@ -0,0 +1,350 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+use crate::communication_mod_state as communication;
+use crate::simulation_state::*;
+
+/// How far to trust a state imported from CommunicationMod.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
+pub enum ImportFidelity {
+  /// Something in the combat isn't simulated at all, so analyses of it may be garbage.
+  Low,
+  /// Everything that matters was recognized, but some of it was guessed or left out.
+  Approximate,
+  Exact,
+}
+
+/// Everything `CombatState::from_communication_mod` didn't recognize or had to guess, instead of
+/// silently turning unknown cards into Injury and unknown monsters into Cultists.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+pub struct ImportReport {
+  pub unknown_cards: Vec<String>,
+  pub unknown_monsters: Vec<String>,
+  pub unknown_powers: Vec<String>,
+  pub unknown_relics: Vec<String>,
+  pub unknown_potions: Vec<String>,
+  pub unknown_orbs: Vec<String>,
+  /// The monster's ID and CommunicationMod's move ID.
+  pub unmapped_intents: Vec<(String, i32)>,
+  pub assumptions: Vec<String>,
+}
+
+fn note<T: PartialEq>(list: &mut Vec<T>, value: T) {
+  if !list.contains(&value) {
+    list.push(value);
+  }
+}
+
+impl ImportReport {
+  /// Finds everything in `observed` that the simulation doesn't know about. Assumptions are added
+  /// separately, by the code that makes them.
+  pub fn check(observed: &communication::GameState) -> ImportReport {
+    let mut report = ImportReport::default();
+    for relic in &observed.relics {
+      if RelicId::from(&*relic.id) == RelicId::Unknown {
+        note(&mut report.unknown_relics, relic.id.clone());
+      }
+    }
+    for potion in &observed.potions {
+      let known = CardId::from_communication_mod(&potion.id).map_or(false, |id| {
+        <&CardInfo>::from(id).card_type == CardType::Potion
+      });
+      if !known && potion.id != "Potion Slot" {
+        note(&mut report.unknown_potions, potion.id.clone());
+      }
+    }
+    let combat = match &observed.combat_state {
+      Some(combat) => combat,
+      None => return report,
+    };
+
+    let piles = [
+      &combat.draw_pile,
+      &combat.discard_pile,
+      &combat.exhaust_pile,
+      &combat.hand,
+      &combat.limbo,
+    ];
+    for card in piles
+      .iter()
+      .flat_map(|pile| pile.iter())
+      .chain(&combat.card_in_play)
+    {
+      report.check_card(card);
+    }
+    report.check_powers(&combat.player.powers);
+    for orb in &combat.player.orbs {
+      if OrbType::from_communication_mod(&orb.id).is_none() && orb.id != "Empty" {
+        note(&mut report.unknown_orbs, orb.id.clone());
+      }
+    }
+
+    for monster in &combat.monsters {
+      if monster.is_gone && !monster.half_dead {
+        continue;
+      }
+      report.check_powers(&monster.powers);
+      let monster_id = match MonsterId::from_communication_mod(&monster.id) {
+        Some(MonsterId::UnknownMonster) | None => {
+          note(&mut report.unknown_monsters, monster.id.clone());
+          continue;
+        }
+        Some(monster_id) => monster_id,
+      };
+      let moves = std::iter::once(monster.move_id)
+        .chain(monster.last_move_id)
+        .chain(monster.second_last_move_id);
+      for move_id in moves {
+        if monster_id.intent_from_communication_mod(move_id).is_none() {
+          note(&mut report.unmapped_intents, (monster.id.clone(), move_id));
+        }
+      }
+    }
+    report
+  }
+
+  fn check_card(&mut self, card: &communication::Card) {
+    if CardId::from_communication_mod(&card.id).is_none() {
+      note(&mut self.unknown_cards, card.id.clone());
+    }
+  }
+
+  fn check_powers(&mut self, powers: &[communication::Power]) {
+    for power in powers {
+      match PowerId::from_communication_mod(&power.id) {
+        None | Some(PowerId::Unknown) => note(&mut self.unknown_powers, power.id.clone()),
+        Some(_) => {}
+      }
+      if let Some(card) = &power.card {
+        self.check_card(card);
+      }
+    }
+  }
+
+  pub fn assume(&mut self, assumption: impl Into<String>) {
+    note(&mut self.assumptions, assumption.into());
+  }
+
+  pub fn fidelity(&self) -> ImportFidelity {
+    if !self.unknown_cards.is_empty()
+      || !self.unknown_powers.is_empty()
+      || !self.unknown_orbs.is_empty()
+      || !self.unmapped_intents.is_empty()
+    {
+      ImportFidelity::Low
+    } else if !self.unknown_monsters.is_empty()
+      || !self.unknown_relics.is_empty()
+      || !self.unknown_potions.is_empty()
+      || !self.assumptions.is_empty()
+    {
+      // unknown monsters replay the intent we can see and then follow a pessimistic model, and
+      // unknown relics and potions are left out, which is usually harmless
+      ImportFidelity::Approximate
+    } else {
+      ImportFidelity::Exact
+    }
+  }
+
+  /// Like `fidelity`, but also `Low` when the imported combat has something the simulation
+  /// doesn't model at all, which makes analyses just as wrong as an unknown card would.
+  pub fn fidelity_with_audit(&self, audit: &FidelityAudit) -> ImportFidelity {
+    if audit.anything_missing() {
+      ImportFidelity::Low
+    } else {
+      self.fidelity()
+    }
+  }
+}
+
+impl Display for ImportFidelity {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{:?}", self)
+  }
+}
+
+impl Display for ImportReport {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    writeln!(f, "Import fidelity: {}", self.fidelity())?;
+    let lists = [
+      ("Unknown cards", &self.unknown_cards),
+      ("Unknown monsters", &self.unknown_monsters),
+      ("Unknown powers", &self.unknown_powers),
+      ("Unknown relics", &self.unknown_relics),
+      ("Unknown potions", &self.unknown_potions),
+      ("Unknown orbs", &self.unknown_orbs),
+    ];
+    for (heading, list) in lists.iter() {
+      if !list.is_empty() {
+        writeln!(f, "{}: {}", heading, list.join(", "))?;
+      }
+    }
+    for (monster, move_id) in &self.unmapped_intents {
+      writeln!(f, "Unmapped intent: {} move {}", monster, move_id)?;
+    }
+    for assumption in &self.assumptions {
+      writeln!(f, "Assumed: {}", assumption)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::simulation_state::fidelity::{AuditedEntity, FidelityWarning};
+  use serde_json::json;
+
+  fn observed(
+    hand: &[&str],
+    monsters: &[(&str, i32, bool, bool)],
+    potions: &[&str],
+  ) -> communication::GameState {
+    let hand: Vec<_> = hand
+      .iter()
+      .map(|id| {
+        json!({"name": id, "uuid": id, "cost": 1, "upgrades": 0, "id": id, "type": "ATTACK",
+          "rarity": "BASIC", "has_target": true, "exhausts": false})
+      })
+      .collect();
+    let monsters: Vec<_> = monsters
+      .iter()
+      .map(|&(id, move_id, half_dead, is_gone)| {
+        json!({"name": id, "id": id, "current_hp": 40, "max_hp": 40, "block": 0,
+          "intent": "ATTACK", "move_id": move_id, "last_move_id": null,
+          "second_last_move_id": null, "half_dead": half_dead, "is_gone": is_gone,
+          "powers": []})
+      })
+      .collect();
+    let potions: Vec<_> = potions
+      .iter()
+      .map(|id| {
+        json!({"name": id, "id": id, "can_use": true, "can_discard": true,
+          "requires_target": false})
+      })
+      .collect();
+    serde_json::from_value(json!({
+      "screen_name": "NONE", "is_screen_up": false, "screen_type": "NONE", "screen_state": {},
+      "room_phase": "COMBAT", "action_phase": "WAITING_ON_USER", "room_type": "MonsterRoom",
+      "current_hp": 70, "max_hp": 80, "floor": 3, "act": 1, "act_boss": "Hexaghost", "gold": 99,
+      "seed": 1, "class": "IRONCLAD", "ascension_level": 0,
+      "relics": [{"name": "Vajra", "id": "Vajra", "counter": -1}],
+      "deck": [], "potions": potions, "map": null, "current_action": null, "choice_list": null,
+      "combat_state": {
+        "draw_pile": [], "discard_pile": [], "exhaust_pile": [], "hand": hand, "limbo": [],
+        "card_in_play": null, "cards_discarded_this_turn": 0, "turn": 1,
+        "player": {"current_hp": 70, "max_hp": 80, "block": 0, "powers": [], "energy": 3,
+          "orbs": []},
+        "monsters": monsters,
+      },
+    }))
+    .unwrap()
+  }
+
+  fn with(change: impl FnOnce(&mut ImportReport)) -> ImportReport {
+    let mut report = ImportReport::default();
+    change(&mut report);
+    report
+  }
+
+  #[test]
+  fn fidelity_is_the_worst_problem() {
+    assert_eq!(ImportReport::default().fidelity(), ImportFidelity::Exact);
+
+    let approximate = [
+      with(|r| r.unknown_monsters.push("Monster".into())),
+      with(|r| r.unknown_relics.push("Relic".into())),
+      with(|r| r.unknown_potions.push("Potion".into())),
+      with(|r| r.assume("something")),
+    ];
+    for report in &approximate {
+      assert_eq!(report.fidelity(), ImportFidelity::Approximate, "{}", report);
+    }
+
+    let low = [
+      with(|r| r.unknown_cards.push("Card".into())),
+      with(|r| r.unknown_powers.push("Power".into())),
+      with(|r| r.unknown_orbs.push("Orb".into())),
+      with(|r| r.unmapped_intents.push(("JawWorm".into(), 99))),
+      with(|r| {
+        r.unknown_cards.push("Card".into());
+        r.unknown_monsters.push("Monster".into());
+        r.assume("something");
+      }),
+    ];
+    for report in &low {
+      assert_eq!(report.fidelity(), ImportFidelity::Low, "{}", report);
+    }
+  }
+
+  #[test]
+  fn missing_entities_make_fidelity_low() {
+    let warning = |entity, fidelity| FidelityWarning { entity, fidelity };
+    let partial = FidelityAudit {
+      warnings: vec![warning(
+        AuditedEntity::Card(CardId::BloodForBlood),
+        Fidelity::Partial("cost doesn't drop when losing hitpoints"),
+      )],
+    };
+    let missing = FidelityAudit {
+      warnings: vec![warning(
+        AuditedEntity::Power(PowerId::Combust),
+        Fidelity::Missing("does nothing"),
+      )],
+    };
+    let exact = ImportReport::default();
+    let approximate = with(|r| r.assume("something"));
+    assert_eq!(
+      exact.fidelity_with_audit(&FidelityAudit::default()),
+      ImportFidelity::Exact
+    );
+    assert_eq!(exact.fidelity_with_audit(&partial), ImportFidelity::Exact);
+    assert_eq!(
+      approximate.fidelity_with_audit(&partial),
+      ImportFidelity::Approximate
+    );
+    assert_eq!(exact.fidelity_with_audit(&missing), ImportFidelity::Low);
+    assert_eq!(
+      approximate.fidelity_with_audit(&missing),
+      ImportFidelity::Low
+    );
+  }
+
+  #[test]
+  fn check_finds_what_the_simulation_doesnt_know() {
+    let known = observed(
+      &["Strike_R", "Bash"],
+      &[("JawWorm", 1, false, false)],
+      &["Potion Slot", "Fire Potion"],
+    );
+    let report = ImportReport::check(&known);
+    assert_eq!(report, ImportReport::default());
+    assert_eq!(report.fidelity(), ImportFidelity::Exact);
+
+    let mut unknown = observed(
+      &["Strike_R", "Not a Card", "Not a Card"],
+      &[
+        ("JawWorm", 99, false, false),
+        ("Not a Monster", 1, false, false),
+        // dead monsters don't matter, but half-dead ones come back
+        ("Dead Monster", 1, false, true),
+        ("Half-dead Monster", 1, true, true),
+      ],
+      &["Potion Slot", "Not a Potion"],
+    );
+    unknown.relics.push(communication::Relic {
+      name: "Not a Relic".to_string(),
+      id: "Not a Relic".to_string(),
+      counter: -1,
+    });
+    let report = ImportReport::check(&unknown);
+    assert_eq!(report.unknown_relics, ["Not a Relic"]);
+    assert_eq!(report.unknown_cards, ["Not a Card"]);
+    assert_eq!(
+      report.unknown_monsters,
+      ["Not a Monster", "Half-dead Monster"]
+    );
+    assert_eq!(report.unknown_potions, ["Not a Potion"]);
+    assert_eq!(report.unmapped_intents, [("JawWorm".to_string(), 99)]);
+    assert_eq!(report.fidelity(), ImportFidelity::Low);
+  }
+}
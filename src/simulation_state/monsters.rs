@@ -5,6 +5,7 @@ use std::convert::From;
 
 use crate::seed_system::Distribution;
 use crate::simulation::*;
+use crate::simulation_state::fidelity::Fidelity::Partial;
 use crate::simulation_state::*;
 use enum_map::Enum;
 
@@ -136,6 +137,29 @@ pub fn intent_choice_distribution(
   context.final_distribution()
 }
 
+/// The intents a monster picks between when the game rolls `num`, from 0 to 99, for it. Most
+/// monsters only need `num`; the rest roll again to pick between these.
+pub fn intent_choices_for_num(
+  state: &CombatState,
+  monster_index: usize,
+  num: i32,
+) -> Option<Distribution<IntentId>> {
+  let monster = &state.monsters[monster_index];
+  let mut context = IntentChoiceContext {
+    state,
+    monster,
+    monster_index,
+    ascension: monster.ascension,
+    num_distribution: Vec::new(),
+  };
+  monster.monster_id.make_intent_distribution(&mut context);
+  context
+    .num_distribution
+    .into_iter()
+    .find(|&(excluded, _)| num < excluded)
+    .map(|(_, distribution)| distribution)
+}
+
 pub struct DoIntentContext<'a, R: Runner> {
   pub runner: &'a mut R,
   pub monster_index: usize,
@@ -193,14 +217,14 @@ pub trait IntentEffectsContext {
   }
 
   fn attack(&mut self, base_damage: i32) {
-    // hack: this is actually NOT where powers are applied to card/monster damage in the actual code
     let info =
-      DamageInfoNoPowers::new(Some(self.creature_index()), base_damage, DamageType::Normal)
-        .apply_all_powers(self.state(), CreatureIndex::Player);
-    self.action(DamageAction {
-      info,
-      target: CreatureIndex::Player,
-    });
+      DamageInfoNoPowers::new(Some(self.creature_index()), base_damage, DamageType::Normal);
+    self.action(MonsterAttackAction { info });
+  }
+  fn vampire_attack(&mut self, base_damage: i32) {
+    let info =
+      DamageInfoNoPowers::new(Some(self.creature_index()), base_damage, DamageType::Normal);
+    self.action(MonsterVampireAttackAction { info });
   }
   fn power_self(&mut self, power_id: PowerId, amount: i32) {
     self.action(ApplyPowerAction {
@@ -224,6 +248,13 @@ pub trait IntentEffectsContext {
       amount,
     });
   }
+  fn spawn_minion(&mut self, monster_id: MonsterId) {
+    self.action(SpawnMonsterAction {
+      monster_id,
+      hitpoints: hitpoints_range(monster_id, self.ascension()),
+      minion: true,
+    });
+  }
   fn discard_status(&mut self, card_id: CardId, amount: i32) {
     for _ in 0..amount {
       self.action(DiscardNewCard(SingleCard::create(card_id)));
@@ -287,18 +318,27 @@ pub fn consider_intent_actions(
 
 impl CombatState {
   pub fn total_monster_attack_intent_damage(&self) -> i32 {
-    struct CountAttackIntentDamage {
+    struct CountAttackIntentDamage<'a> {
+      state: &'a CombatState,
       total: i32,
     }
-    impl ConsiderAction for CountAttackIntentDamage {
+    impl<'a> ConsiderAction for CountAttackIntentDamage<'a> {
       fn consider(&mut self, action: impl Action) {
         // It theoretically makes more sense to do this on the type level, but that would make the code more complicated, and I'm almost certain this will be optimized out.
-        if let DynAction::DamageAction(action) = action.clone().into() {
-          self.total += action.info.output;
-        }
+        let info = match action.clone().into() {
+          DynAction::MonsterAttackAction(action) => action.info,
+          DynAction::MonsterVampireAttackAction(action) => action.info,
+          _ => return,
+        };
+        self.total += info
+          .apply_all_powers(self.state, CreatureIndex::Player)
+          .output;
       }
     }
-    let mut counter = CountAttackIntentDamage { total: 0 };
+    let mut counter = CountAttackIntentDamage {
+      state: self,
+      total: 0,
+    };
     for (index, monster) in self.monsters.iter().enumerate() {
       if !monster.gone {
         consider_intent_actions(self, index, &mut counter);
@@ -313,7 +353,9 @@ impl CombatState {
     impl ConsiderAction for Visitor {
       fn consider(&mut self, action: impl Action) {
         // It theoretically makes more sense to do this on the type level, but that would make the code more complicated, and I'm almost certain this will be optimized out.
-        if let DynAction::DamageAction(action) = action.clone().into() {
+        if let DynAction::MonsterAttackAction(_) | DynAction::MonsterVampireAttackAction(_) =
+          action.clone().into()
+        {
           self.is_attacking = true;
         }
       }
@@ -343,9 +385,33 @@ pub trait MonsterBehavior: Sized + Copy + Into<MonsterId> {
   fn make_intent_distribution(context: &mut IntentChoiceContext);
 
   fn after_choosing_intent(runner: &mut impl Runner, monster_index: usize) {}
+  /// Effects at the start of combat, before the player's first turn.
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {}
+  /// Effects when it reaches 0 hitpoints, after its powers' `on_death`. Monsters that fall
+  /// without dying set `half_dead` here.
+  fn on_death(runner: &mut impl Runner, monster_index: usize) {}
   fn intent_effects(context: &mut impl IntentEffectsContext);
 }
 
+/// For `pre_battle`, which doesn't have a context to apply powers with.
+fn power_monster(runner: &mut impl Runner, monster_index: usize, power_id: PowerId, amount: i32) {
+  runner.action_bottom(ApplyPowerAction {
+    source: CreatureIndex::Monster(monster_index),
+    target: CreatureIndex::Monster(monster_index),
+    power_id,
+    amount,
+  });
+}
+
+/// For bosses and elites whose companions are their minions from the start.
+fn minions_except(runner: &mut impl Runner, monster_index: usize) {
+  for index in 0..runner.state().monsters.len() {
+    if index != monster_index {
+      power_monster(runner, index, PowerId::Minion, -1);
+    }
+  }
+}
+
 macro_rules! intent {
   (pub enum $Enum:ident {$($spire_id:tt: $Variant: ident,)*}) => {
     #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Enum)]
@@ -373,7 +439,7 @@ macro_rules! intent {
   };
 }
 macro_rules! monsters {
-  ($([$id: expr, $Variant: ident],)*) => {
+  ($([$id: expr, $Variant: ident $(, $fidelity: expr)?],)*) => {
     #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
     pub enum MonsterId {
       $($Variant,)*
@@ -390,14 +456,22 @@ macro_rules! monsters {
 
     impl From<& str> for MonsterId {
       fn from (source: & str)->MonsterId {
-        match source {
-          $($id => MonsterId::$Variant,)*
-          _ => MonsterId::Cultist,
-        }
+        MonsterId::from_communication_mod(source).unwrap_or(MonsterId::UnknownMonster)
       }
     }
 
     impl MonsterId {
+      pub fn from_communication_mod(source: & str)->Option<MonsterId> {
+        match source {
+          $($id => Some(MonsterId::$Variant),)*
+          _ => None,
+        }
+      }
+      pub fn fidelity(self) -> Fidelity {
+        match self {
+          $(MonsterId::$Variant => None $(.or(Some($fidelity)))? .unwrap_or(Fidelity::Complete),)*
+        }
+      }
       pub fn intent_name(self, intent_id: IntentId) -> String {
         match self {
         $(MonsterId::$Variant => format!("{:?}", <<$Variant as MonsterBehavior>::Intent as Intent>::from_id (intent_id)),)*
@@ -418,6 +492,16 @@ macro_rules! monsters {
         $(MonsterId::$Variant => <$Variant as MonsterBehavior>::after_choosing_intent (runner, monster_index),)*
         }
       }
+      pub fn pre_battle (self, runner: &mut impl Runner, monster_index: usize) {
+        match self {
+        $(MonsterId::$Variant => <$Variant as MonsterBehavior>::pre_battle (runner, monster_index),)*
+        }
+      }
+      pub fn on_death (self, runner: &mut impl Runner, monster_index: usize) {
+        match self {
+        $(MonsterId::$Variant => <$Variant as MonsterBehavior>::on_death (runner, monster_index),)*
+        }
+      }
       pub fn intent_effects(self, context: &mut impl IntentEffectsContext) {
         match self {
         $(MonsterId::$Variant => <$Variant as MonsterBehavior>::intent_effects(context),)*
@@ -458,9 +542,52 @@ monsters! {
 
   ["Byrd", Byrd],
   ["SphericGuardian", SphericGuardian],
+  ["Chosen", Chosen],
+  ["Shelled Parasite", ShelledParasite],
+  ["SnakePlant", SnakePlant],
+  ["Snecko", Snecko],
+  ["Centurion", Centurion],
+  ["Healer", Mystic],
+  ["Mugger", Mugger],
+  ["BanditBear", Bear],
+  ["BanditChild", Pointy],
+  ["BanditLeader", Romeo],
 
   ["SlaverBoss", Taskmaster],
   ["GremlinLeader", GremlinLeader],
+  ["BookOfStabbing", BookOfStabbing],
+
+  ["Champ", TheChamp],
+  ["BronzeAutomaton", BronzeAutomaton],
+  ["BronzeOrb", BronzeOrb],
+  ["TheCollector", TheCollector],
+  ["TorchHead", TorchHead],
+
+  ["Darkling", Darkling],
+  ["Orb Walker", OrbWalker],
+  ["Spiker", Spiker],
+  ["Repulsor", Repulsor],
+  ["Exploder", Exploder],
+  ["Maw", Maw],
+  ["Serpent", SpireGrowth],
+  ["Transient", Transient],
+  ["WrithingMass", WrithingMass],
+
+  ["GiantHead", GiantHead],
+  ["Nemesis", Nemesis],
+  ["Reptomancer", Reptomancer],
+  ["Dagger", SnakeDagger],
+
+  ["TimeEater", TimeEater],
+  ["AwakenedOne", AwakenedOne],
+  ["Donu", Donu],
+  ["Deca", Deca],
+
+  ["SpireShield", SpireShield],
+  ["SpireSpear", SpireSpear],
+  ["CorruptHeart", CorruptHeart],
+
+  ["Unknown", UnknownMonster, Partial("replays the intent we saw, then guesses")],
 }
 
 impl Default for MonsterId {
@@ -469,7 +596,222 @@ impl Default for MonsterId {
   }
 }
 
+/// The range of a monster's starting hitpoints, excluding the upper bound.
+pub fn hitpoints_range(monster_id: MonsterId, ascension: i32) -> (i32, i32) {
+  use MonsterId::*;
+  // normal monsters get more hitpoints at ascension 7, elites at 8 and bosses at 9
+  let (ascension_threshold, normal, raised) = match monster_id {
+    RedLouse => (7, (10, 16), (11, 17)),
+    GreenLouse => (7, (11, 18), (12, 19)),
+    Cultist => (7, (48, 55), (50, 57)),
+    JawWorm => (7, (40, 45), (42, 47)),
+    AcidSlimeS => (7, (8, 13), (9, 14)),
+    AcidSlimeM => (7, (28, 33), (29, 35)),
+    AcidSlimeL => (7, (65, 70), (68, 73)),
+    SpikeSlimeS => (7, (10, 15), (11, 16)),
+    SpikeSlimeM => (7, (28, 33), (29, 35)),
+    SpikeSlimeL => (7, (64, 71), (67, 74)),
+    FungiBeast => (7, (22, 29), (24, 29)),
+    Looter => (7, (44, 49), (46, 51)),
+    SlaverBlue | SlaverRed => (7, (46, 51), (48, 53)),
+    MadGremlin => (7, (20, 25), (21, 26)),
+    SneakyGremlin => (7, (10, 15), (11, 16)),
+    FatGremlin => (7, (13, 18), (14, 19)),
+    GremlinWizard => (7, (21, 26), (22, 27)),
+    ShieldGremlin => (7, (12, 16), (13, 18)),
+
+    Sentry => (8, (38, 43), (39, 46)),
+    GremlinNob => (8, (82, 87), (85, 91)),
+    Lagavulin => (8, (109, 112), (112, 116)),
+
+    TheGuardian => (9, (240, 241), (250, 251)),
+    Hexaghost => (9, (250, 251), (264, 265)),
+    SlimeBoss => (9, (140, 141), (150, 151)),
+
+    Byrd => (7, (25, 32), (26, 34)),
+    SphericGuardian => (7, (20, 21), (20, 21)),
+    Chosen => (7, (95, 100), (98, 104)),
+    ShelledParasite => (7, (68, 73), (70, 76)),
+    SnakePlant => (7, (75, 80), (78, 83)),
+    Snecko => (7, (114, 121), (120, 126)),
+    Centurion => (7, (76, 81), (78, 84)),
+    Mystic => (7, (48, 57), (50, 59)),
+    Mugger => (7, (48, 53), (50, 55)),
+    Bear => (7, (38, 43), (40, 45)),
+    Pointy => (7, (30, 31), (34, 35)),
+    Romeo => (7, (35, 40), (37, 42)),
+
+    Taskmaster => (8, (54, 61), (57, 65)),
+    GremlinLeader => (8, (140, 149), (145, 156)),
+    BookOfStabbing => (8, (160, 165), (168, 173)),
+
+    TheChamp => (9, (420, 421), (440, 441)),
+    BronzeAutomaton => (9, (300, 301), (320, 321)),
+    BronzeOrb => (9, (52, 59), (54, 61)),
+    TheCollector => (9, (282, 283), (300, 301)),
+    TorchHead => (9, (38, 41), (40, 46)),
+
+    Darkling => (7, (48, 57), (50, 60)),
+    OrbWalker => (7, (90, 97), (92, 103)),
+    Spiker => (7, (42, 57), (44, 61)),
+    Repulsor => (7, (29, 36), (31, 39)),
+    Exploder => (7, (30, 31), (30, 36)),
+    Maw => (7, (300, 301), (300, 301)),
+    SpireGrowth => (7, (170, 171), (190, 191)),
+    Transient => (7, (999, 1000), (999, 1000)),
+    WrithingMass => (7, (160, 161), (175, 176)),
+
+    GiantHead => (8, (500, 501), (520, 521)),
+    Nemesis => (8, (185, 186), (200, 201)),
+    Reptomancer => (8, (180, 191), (190, 201)),
+    SnakeDagger => (8, (20, 26), (20, 26)),
+
+    TimeEater => (9, (456, 457), (480, 481)),
+    AwakenedOne => (9, (300, 301), (320, 321)),
+    Donu | Deca => (9, (250, 251), (265, 266)),
+
+    SpireShield => (8, (110, 111), (125, 126)),
+    SpireSpear => (8, (160, 161), (180, 181)),
+    CorruptHeart => (9, (750, 751), (800, 801)),
+
+    // only ever comes from CommunicationMod, which tells us its hitpoints
+    UnknownMonster => (7, (1, 2), (1, 2)),
+  };
+  if ascension >= ascension_threshold {
+    raised
+  } else {
+    normal
+  }
+}
+
 pub mod beyond;
 pub mod city;
 pub mod ending;
 pub mod exordium;
+pub mod unknown;
+
+#[cfg(test)]
+mod tests {
+  use super::beyond::ReptomancerIntent;
+  use super::*;
+  use crate::simulation::tests::*;
+
+  fn make_minion(monster: &mut Monster) {
+    monster.creature.powers.push(Power {
+      power_id: PowerId::Minion,
+      amount: -1,
+      ..Default::default()
+    });
+  }
+
+  fn dagger(hitpoints: (i32, i32)) -> SpawnMonsterAction {
+    SpawnMonsterAction {
+      monster_id: MonsterId::SnakeDagger,
+      hitpoints,
+      minion: true,
+    }
+  }
+
+  #[test]
+  fn spawned_monsters_take_the_place_of_a_dead_minion() {
+    let mut state = combat(
+      &[],
+      &[
+        MonsterId::Reptomancer,
+        MonsterId::SnakeDagger,
+        MonsterId::SnakeDagger,
+      ],
+    );
+    make_minion(&mut state.monsters[1]);
+    make_minion(&mut state.monsters[2]);
+    state.monsters[2].gone = true;
+    assert_eq!(state.living_minions(), 1);
+    assert_eq!(state.next_spawn_slot(), Some(2));
+
+    match dagger((20, 26)).determinism(&state) {
+      Determinism::Random(distribution) => {
+        let hitpoints: Vec<i32> = distribution.0.iter().map(|&(_, value)| value).collect();
+        assert_eq!(hitpoints, [20, 21, 22, 23, 24, 25]);
+      }
+      other => panic!("spawning is {:?}", other),
+    }
+    dagger((20, 26)).execute_random(&mut runner(&mut state), 23);
+    assert_eq!(state.monsters.len(), 3);
+    let spawned = &state.monsters[2];
+    assert!(!spawned.gone);
+    assert_eq!(spawned.creature.hitpoints, 23);
+    assert_eq!(spawned.creature.max_hitpoints, 23);
+    assert!(spawned.creature.has_power(PowerId::Minion));
+    assert!(spawned.move_history.is_empty());
+    assert_eq!(state.living_minions(), 2);
+  }
+
+  #[test]
+  fn nothing_spawns_without_room() {
+    let mut state = combat(&[], &[MonsterId::SnakeDagger; MAX_MONSTERS]);
+    assert_eq!(state.next_spawn_slot(), None);
+    assert_eq!(
+      dagger((20, 26)).determinism(&state),
+      Determinism::Deterministic
+    );
+    run(&mut state, dagger((20, 26)));
+    assert_eq!(state.monsters.len(), MAX_MONSTERS);
+  }
+
+  #[test]
+  fn minions_die_with_their_leader() {
+    let mut state = combat(
+      &[],
+      &[
+        MonsterId::Reptomancer,
+        MonsterId::SnakeDagger,
+        MonsterId::SnakeDagger,
+      ],
+    );
+    make_minion(&mut state.monsters[1]);
+    make_minion(&mut state.monsters[2]);
+    run(&mut state, SuicideAction(1));
+    assert!(state.monsters[1].gone);
+    assert!(!state.combat_over());
+
+    run(&mut state, SuicideAction(0));
+    assert!(state.monsters.iter().all(|monster| monster.gone));
+    assert!(state.combat_over());
+  }
+
+  #[test]
+  fn splitting_spawns_monsters_with_the_remaining_hitpoints() {
+    let mut state = combat(&[], &[MonsterId::AcidSlimeL, MonsterId::SpikeSlimeL]);
+    state.monsters[0].creature.hitpoints = 31;
+    run(&mut state, SplitAction(0, [MonsterId::AcidSlimeM; 2]));
+    assert!(state.monsters[0].gone);
+    assert_eq!(state.monsters.len(), 4);
+    for monster in &state.monsters[2..] {
+      assert_eq!(monster.monster_id, MonsterId::AcidSlimeM);
+      assert_eq!(monster.creature.hitpoints, 31);
+      assert!(!monster.creature.has_power(PowerId::Minion));
+    }
+  }
+
+  #[test]
+  fn a_lone_slime_splitting_keeps_the_combat_going() {
+    let mut state = combat(&[], &[MonsterId::SpikeSlimeL]);
+    state.monsters[0].creature.hitpoints = 20;
+    run(&mut state, SplitAction(0, [MonsterId::SpikeSlimeM; 2]));
+    assert!(state.monsters[0].gone);
+    assert_eq!(state.monsters.len(), 3);
+    assert!(state.monsters[1..].iter().all(|monster| !monster.gone));
+    assert!(!state.combat_over());
+  }
+
+  #[test]
+  fn spawned_monsters_wait_for_their_first_intent() {
+    let mut state = combat(&[], &[MonsterId::Reptomancer]);
+    state.monsters[0].move_history = vec![ReptomancerIntent::SpawnDagger.id()];
+    choose(&mut state, EndTurn);
+    assert_eq!(state.monsters.len(), 2);
+    assert_eq!(state.monsters[1].monster_id, MonsterId::SnakeDagger);
+    assert_eq!(state.player.creature.hitpoints, 70);
+    assert_eq!(state.monsters[1].move_history.len(), 1);
+  }
+}
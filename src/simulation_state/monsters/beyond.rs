@@ -1 +1,1120 @@
+use super::*;
 
+intent! {
+  pub enum ReptomancerIntent {
+    1: SnakeStrike,
+    2: SpawnDagger,
+    3: BigBite,
+  }
+}
+impl MonsterBehavior for Reptomancer {
+  type Intent = ReptomancerIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use ReptomancerIntent::*;
+    if context.first_move() {
+      context.always(SpawnDagger);
+      return;
+    }
+    // it can only spawn while there are at most 3 other monsters
+    let others = context
+      .state()
+      .monsters
+      .iter()
+      .enumerate()
+      .filter(|&(index, monster)| !monster.gone && index != context.monster_index())
+      .count();
+    let spawn = if others <= 3 && !context.did_repeats(Repeats(2), SpawnDagger) {
+      SpawnDagger
+    } else {
+      SnakeStrike
+    };
+    // the rerolls exclude the range that led to the repeat
+    context.if_num_lt(
+      33,
+      context.with_max_repeats(Repeats(1), SnakeStrike, split(33.0 / 67.0, spawn, BigBite)),
+    );
+    context.if_num_lt(66, spawn);
+    context.else_num(context.with_max_repeats(Repeats(1), BigBite, split(0.5, SnakeStrike, spawn)));
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    // the daggers it starts with are minions too
+    minions_except(runner, monster_index);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use ReptomancerIntent::*;
+    match context.intent::<Self::Intent>() {
+      SnakeStrike => {
+        for _ in 0..2 {
+          context.attack(context.with_ascension(Ascension(3), 16, 13));
+        }
+        context.power_player(PowerId::Weak, 1);
+      }
+      SpawnDagger => {
+        let room = 4 - context.state().living_minions().min(4);
+        for _ in 0..context.with_ascension(Ascension(18), 2, 1).min(room) {
+          context.spawn_minion(MonsterId::SnakeDagger);
+        }
+      }
+      BigBite => context.attack(context.with_ascension(Ascension(3), 34, 30)),
+    }
+  }
+}
+
+intent! {
+  pub enum SnakeDaggerIntent {
+    1: Wound,
+    2: Explode,
+  }
+}
+impl MonsterBehavior for SnakeDagger {
+  type Intent = SnakeDaggerIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use SnakeDaggerIntent::*;
+    context.always(if context.first_move() { Wound } else { Explode });
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use SnakeDaggerIntent::*;
+    match context.intent::<Self::Intent>() {
+      Wound => {
+        context.attack(9);
+        context.discard_status(CardId::Wound, 1);
+      }
+      Explode => {
+        context.attack(25);
+        context.action(SuicideAction(context.monster_index()));
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum DarklingIntent {
+    1: Chomp,
+    2: Harden,
+    3: Nip,
+    4: Count,
+    5: Reincarnate,
+  }
+}
+impl MonsterBehavior for Darkling {
+  type Intent = DarklingIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use DarklingIntent::*;
+    // it gets the Count intent when it falls
+    if context.monster().half_dead {
+      context.always(Reincarnate);
+      return;
+    }
+    if context.first_move() {
+      context.always(split(0.5, Harden, Nip));
+      return;
+    }
+    let last = context.last_intent::<Self::Intent>();
+    let harden = if last != Some(Harden) { Harden } else { Nip };
+    // only the Darklings in the even slots chomp
+    let can_chomp = last != Some(Chomp) && context.monster_index() % 2 == 0;
+    if context.did_repeats(Repeats(2), Nip) {
+      // the reroll from the Nip range is over the whole range again
+      context.always(if can_chomp {
+        split(4.0 / 7.0, Chomp, harden)
+      } else {
+        Distribution::from(harden)
+      });
+    } else {
+      // the reroll from the Chomp range is over the other two ranges
+      context.if_num_lt(
+        40,
+        if can_chomp {
+          Distribution::from(Chomp)
+        } else {
+          split(0.5, harden, Nip)
+        },
+      );
+      context.if_num_lt(70, harden);
+      context.else_num(Nip);
+    }
+  }
+  fn on_death(runner: &mut impl Runner, monster_index: usize) {
+    let state = runner.state_mut();
+    let monster = &mut state.monsters[monster_index];
+    if !monster.creature.has_power(PowerId::Regrow) {
+      return;
+    }
+    monster.half_dead = true;
+    monster.creature.powers.clear();
+    monster.push_intent(DarklingIntent::Count.id());
+    // they only die for good if they all fall together
+    if state
+      .monsters
+      .iter()
+      .all(|monster| monster.monster_id != MonsterId::Darkling || monster.half_dead)
+    {
+      for monster in &mut state.monsters {
+        if monster.monster_id == MonsterId::Darkling {
+          monster.half_dead = false;
+        }
+      }
+    }
+  }
+  fn after_choosing_intent(runner: &mut impl Runner, monster_index: usize) {
+    if runner.state().monster_intent(monster_index) == DarklingIntent::Nip.id() {
+      let ascension = runner.state().monsters[monster_index].ascension;
+      let bonus = if ascension >= 2 { 2 } else { 0 };
+      runner.action_now(&InitializeMonsterInnateDamageAmount {
+        monster_index,
+        range: (7 + bonus, 12 + bonus),
+      });
+    }
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    power_monster(runner, monster_index, PowerId::Regrow, -1);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use DarklingIntent::*;
+    match context.intent::<Self::Intent>() {
+      Chomp => {
+        for _ in 0..2 {
+          context.attack(context.with_ascension(Ascension(2), 9, 8));
+        }
+      }
+      Harden => {
+        context.block(12);
+        if context.ascension() >= 17 {
+          context.power_self(PowerId::Strength, 2);
+        }
+      }
+      Nip => context.attack(context.monster().innate_damage_amount.unwrap()),
+      Count => {}
+      Reincarnate => {
+        context.action(ReviveMonsterAction {
+          monster_index: context.monster_index(),
+          hitpoints: context.monster().creature.max_hitpoints / 2,
+        });
+        context.power_self(PowerId::Regrow, -1);
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum OrbWalkerIntent {
+    1: Laser,
+    2: Claw,
+  }
+}
+impl MonsterBehavior for OrbWalker {
+  type Intent = OrbWalkerIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use OrbWalkerIntent::*;
+    context.if_num_lt(40, context.with_max_repeats(Repeats(2), Claw, Laser));
+    context.else_num(context.with_max_repeats(Repeats(2), Laser, Claw));
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    let ascension = runner.state().monsters[monster_index].ascension;
+    power_monster(
+      runner,
+      monster_index,
+      PowerId::GenericStrengthUp,
+      if ascension >= 17 { 5 } else { 3 },
+    );
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use OrbWalkerIntent::*;
+    match context.intent::<Self::Intent>() {
+      Laser => {
+        context.attack(context.with_ascension(Ascension(2), 11, 10));
+        context.discard_status(CardId::Burn, 1);
+        context.action(ShuffleNewCardIntoDrawPile(SingleCard::create(CardId::Burn)));
+      }
+      Claw => context.attack(context.with_ascension(Ascension(2), 16, 15)),
+    }
+  }
+}
+
+intent! {
+  pub enum SpikerIntent {
+    1: Attack,
+    2: BuffThorns,
+  }
+}
+impl MonsterBehavior for Spiker {
+  type Intent = SpikerIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use SpikerIntent::*;
+    let buffs = context
+      .monster()
+      .move_history
+      .iter()
+      .filter(|&&intent| intent == BuffThorns.id())
+      .count();
+    if buffs > 5 {
+      context.always(Attack);
+      return;
+    }
+    if context.last_intent::<Self::Intent>() != Some(Attack) {
+      context.if_num_lt(50, Attack);
+    }
+    context.else_num(BuffThorns);
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    let ascension = runner.state().monsters[monster_index].ascension;
+    let thorns = if ascension >= 17 {
+      7
+    } else if ascension >= 2 {
+      4
+    } else {
+      3
+    };
+    power_monster(runner, monster_index, PowerId::Thorns, thorns);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use SpikerIntent::*;
+    match context.intent::<Self::Intent>() {
+      Attack => context.attack(context.with_ascension(Ascension(2), 9, 7)),
+      BuffThorns => context.power_self(PowerId::Thorns, 2),
+    }
+  }
+}
+
+intent! {
+  pub enum RepulsorIntent {
+    1: Daze,
+    2: Attack,
+  }
+}
+impl MonsterBehavior for Repulsor {
+  type Intent = RepulsorIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use RepulsorIntent::*;
+    if context.last_intent::<Self::Intent>() != Some(Attack) {
+      context.if_num_lt(20, Attack);
+    }
+    context.else_num(Daze);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use RepulsorIntent::*;
+    match context.intent::<Self::Intent>() {
+      Daze => {
+        for _ in 0..2 {
+          context.action(ShuffleNewCardIntoDrawPile(SingleCard::create(
+            CardId::Dazed,
+          )));
+        }
+      }
+      Attack => context.attack(context.with_ascension(Ascension(2), 13, 11)),
+    }
+  }
+}
+
+intent! {
+  pub enum ExploderIntent {
+    1: Attack,
+    2: Explode,
+  }
+}
+impl MonsterBehavior for Exploder {
+  type Intent = ExploderIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use ExploderIntent::*;
+    context.always(if context.monster().move_history.len() < 2 {
+      Attack
+    } else {
+      Explode
+    });
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    power_monster(runner, monster_index, PowerId::Explosive, 3);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use ExploderIntent::*;
+    match context.intent::<Self::Intent>() {
+      Attack => context.attack(context.with_ascension(Ascension(2), 11, 9)),
+      // the explosion itself is the Explosive power
+      Explode => {}
+    }
+  }
+}
+
+intent! {
+  pub enum MawIntent {
+    2: Roar,
+    3: Slam,
+    4: Drool,
+    5: NomNom,
+  }
+}
+impl MonsterBehavior for Maw {
+  type Intent = MawIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use MawIntent::*;
+    let last = context.last_intent::<Self::Intent>();
+    if !context.monster().move_history.contains(&Roar.id()) {
+      context.always(Roar);
+      return;
+    }
+    if last != Some(NomNom) {
+      context.if_num_lt(50, NomNom);
+    }
+    context.else_num(if last == Some(Slam) || last == Some(NomNom) {
+      Drool
+    } else {
+      Slam
+    });
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use MawIntent::*;
+    match context.intent::<Self::Intent>() {
+      Roar => {
+        let amount = context.with_ascension(Ascension(17), 5, 3);
+        context.power_player(PowerId::Weak, amount);
+        context.power_player(PowerId::Frail, amount);
+      }
+      Slam => context.attack(context.with_ascension(Ascension(2), 30, 25)),
+      Drool => context.power_self(
+        PowerId::Strength,
+        context.with_ascension(Ascension(17), 5, 3),
+      ),
+      NomNom => {
+        // the number of bites grows by one every other turn
+        let hits = ((context.monster().move_history.len() + 1) / 2).max(1);
+        for _ in 0..hits {
+          context.attack(5);
+        }
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum SpireGrowthIntent {
+    1: QuickTackle,
+    2: Constrict,
+    3: Smash,
+  }
+}
+impl MonsterBehavior for SpireGrowth {
+  type Intent = SpireGrowthIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use SpireGrowthIntent::*;
+    let can_constrict = !context
+      .state()
+      .player
+      .creature
+      .has_power(PowerId::Constricted)
+      && context.last_intent::<Self::Intent>() != Some(Constrict);
+    if context.ascension() >= 17 && can_constrict {
+      context.always(Constrict);
+      return;
+    }
+    if !context.did_repeats(Repeats(2), QuickTackle) {
+      context.if_num_lt(50, QuickTackle);
+    }
+    context.else_num(if can_constrict {
+      Distribution::from(Constrict)
+    } else {
+      context.with_max_repeats(Repeats(2), Smash, QuickTackle)
+    });
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use SpireGrowthIntent::*;
+    match context.intent::<Self::Intent>() {
+      QuickTackle => context.attack(context.with_ascension(Ascension(2), 18, 16)),
+      Constrict => context.power_player(
+        PowerId::Constricted,
+        context.with_ascension(Ascension(17), 12, 10),
+      ),
+      Smash => context.attack(context.with_ascension(Ascension(2), 25, 22)),
+    }
+  }
+}
+
+intent! {
+  pub enum TransientIntent {
+    1: Attack,
+  }
+}
+impl MonsterBehavior for Transient {
+  type Intent = TransientIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    context.always(TransientIntent::Attack);
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    let ascension = runner.state().monsters[monster_index].ascension;
+    power_monster(
+      runner,
+      monster_index,
+      PowerId::Fading,
+      if ascension >= 17 { 6 } else { 5 },
+    );
+    power_monster(runner, monster_index, PowerId::Shifting, -1);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use TransientIntent::*;
+    match context.intent::<Self::Intent>() {
+      Attack => {
+        let previous_attacks = context.monster().move_history.len() as i32 - 1;
+        context.attack(context.with_ascension(Ascension(2), 40, 30) + 10 * previous_attacks);
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum WrithingMassIntent {
+    0: BigHit,
+    1: MultiHit,
+    2: AttackBlock,
+    3: AttackDebuff,
+    4: Implant,
+  }
+}
+impl WrithingMass {
+  /// The game picks this monster's moves with chains of rerolls into other ranges of the random
+  /// number, so this follows them range by range, giving up on the rare long chains.
+  fn roll(
+    context: &IntentChoiceContext,
+    low: i32,
+    high: i32,
+    depth: u32,
+  ) -> Distribution<IntentId> {
+    let mut result = Distribution::new();
+    for &(start, end) in &[(0, 10), (10, 20), (20, 40), (40, 70), (70, 100)] {
+      let overlap = std::cmp::min(end, high) - std::cmp::max(start, low);
+      if overlap > 0 {
+        result += Self::roll_range(context, start, depth) * (overlap as f64 / (high - low) as f64);
+      }
+    }
+    result
+  }
+  fn roll_range(context: &IntentChoiceContext, start: i32, depth: u32) -> Distribution<IntentId> {
+    use WrithingMassIntent::*;
+    let last = context.last_intent::<WrithingMassIntent>();
+    let reroll = |low, high, fallback: WrithingMassIntent| {
+      if depth == 0 {
+        Distribution::from(fallback)
+      } else {
+        Self::roll(context, low, high, depth - 1)
+      }
+    };
+    match start {
+      0 if last == Some(BigHit) => reroll(10, 100, BigHit),
+      0 => BigHit.into(),
+      10 if last != Some(Implant) && !context.monster().move_history.contains(&Implant.id()) => {
+        Implant.into()
+      }
+      10 => Distribution::from(BigHit) * 0.1 + reroll(20, 100, BigHit) * 0.9,
+      20 if last == Some(AttackDebuff) => {
+        reroll(0, 20, AttackDebuff) * 0.4 + reroll(40, 100, AttackDebuff) * 0.6
+      }
+      20 => AttackDebuff.into(),
+      40 if last == Some(MultiHit) => {
+        Distribution::from(AttackBlock) * 0.3 + reroll(0, 40, MultiHit) * 0.7
+      }
+      40 => MultiHit.into(),
+      _ if last == Some(AttackBlock) => reroll(0, 70, AttackBlock),
+      _ => AttackBlock.into(),
+    }
+  }
+}
+impl MonsterBehavior for WrithingMass {
+  type Intent = WrithingMassIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use WrithingMassIntent::*;
+    if context.first_move() {
+      context.if_num_lt(33, MultiHit);
+      context.if_num_lt(66, AttackBlock);
+      context.else_num(AttackDebuff);
+    } else {
+      let distribution = Self::roll(context, 0, 100, 3);
+      context.always(distribution);
+    }
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    power_monster(runner, monster_index, PowerId::Reactive, -1);
+    power_monster(runner, monster_index, PowerId::Malleable, 3);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use WrithingMassIntent::*;
+    match context.intent::<Self::Intent>() {
+      BigHit => context.attack(context.with_ascension(Ascension(2), 38, 32)),
+      MultiHit => {
+        for _ in 0..3 {
+          context.attack(context.with_ascension(Ascension(2), 9, 7));
+        }
+      }
+      AttackBlock => {
+        let amount = context.with_ascension(Ascension(2), 16, 15);
+        context.attack(amount);
+        context.block(amount);
+      }
+      AttackDebuff => {
+        context.attack(context.with_ascension(Ascension(2), 12, 10));
+        context.power_player(PowerId::Weak, 2);
+        context.power_player(PowerId::Vulnerable, 2);
+      }
+      // it adds a Parasite to the deck, which doesn't matter for the rest of the combat
+      Implant => {}
+    }
+  }
+}
+
+intent! {
+  pub enum GiantHeadIntent {
+    1: Glare,
+    2: ItIsTime,
+    3: Count,
+  }
+}
+impl GiantHead {
+  /// The countdown after the move with this index (counting from 1), which It Is Time scales with.
+  fn count_after(ascension: i32, move_number: usize) -> i32 {
+    let start = if ascension >= 18 { 4 } else { 5 };
+    std::cmp::max(start - move_number as i32, -6)
+  }
+}
+impl MonsterBehavior for GiantHead {
+  type Intent = GiantHeadIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use GiantHeadIntent::*;
+    let move_number = context.monster().move_history.len() + 1;
+    if Self::count_after(context.ascension(), move_number - 1) <= 1 {
+      context.always(ItIsTime);
+      return;
+    }
+    context.if_num_lt(50, context.with_max_repeats(Repeats(2), Glare, Count));
+    context.else_num(context.with_max_repeats(Repeats(2), Count, Glare));
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    power_monster(runner, monster_index, PowerId::Slow, 0);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use GiantHeadIntent::*;
+    match context.intent::<Self::Intent>() {
+      Glare => context.power_player(PowerId::Weak, 1),
+      ItIsTime => {
+        let count = Self::count_after(context.ascension(), context.monster().move_history.len());
+        context.attack(context.with_ascension(Ascension(3), 40, 30) - count * 5);
+      }
+      Count => context.attack(13),
+    }
+  }
+}
+
+intent! {
+  pub enum NemesisIntent {
+    2: TriAttack,
+    3: Scythe,
+    4: TriBurn,
+  }
+}
+impl MonsterBehavior for Nemesis {
+  type Intent = NemesisIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use NemesisIntent::*;
+    if context.first_move() {
+      context.always(split(0.5, TriAttack, TriBurn));
+      return;
+    }
+    let last = context.last_intent::<Self::Intent>();
+    // Scythe has a cooldown of two moves
+    let can_scythe = !context
+      .monster()
+      .move_history
+      .iter()
+      .rev()
+      .take(2)
+      .any(|&intent| intent == Scythe.id());
+    let tri_attack = context.with_max_repeats(Repeats(2), TriAttack, TriBurn);
+    let tri_burn = context.with_max_repeats(Repeats(1), TriBurn, TriAttack);
+    context.if_num_lt(
+      30,
+      if can_scythe {
+        Distribution::from(Scythe)
+      } else {
+        tri_attack.clone() * 0.5 + tri_burn * 0.5
+      },
+    );
+    context.if_num_lt(
+      65,
+      if !context.did_repeats(Repeats(2), TriAttack) {
+        Distribution::from(TriAttack)
+      } else {
+        split(0.5, if can_scythe { Scythe } else { TriBurn }, TriBurn)
+      },
+    );
+    context.else_num(if last != Some(TriBurn) {
+      Distribution::from(TriBurn)
+    } else if can_scythe {
+      split(0.5, Scythe, TriAttack)
+    } else {
+      Distribution::from(TriAttack)
+    });
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use NemesisIntent::*;
+    match context.intent::<Self::Intent>() {
+      TriAttack => {
+        for _ in 0..3 {
+          context.attack(context.with_ascension(Ascension(3), 7, 6));
+        }
+      }
+      Scythe => context.attack(45),
+      TriBurn => context.discard_status(CardId::Burn, context.with_ascension(Ascension(18), 5, 3)),
+    }
+    // it's intangible every other turn
+    if !context.monster().creature.has_power(PowerId::Intangible) {
+      context.power_self(PowerId::Intangible, 1);
+    }
+  }
+}
+
+intent! {
+  pub enum TimeEaterIntent {
+    2: Reverberate,
+    3: Ripple,
+    4: HeadSlam,
+    5: Haste,
+  }
+}
+impl MonsterBehavior for TimeEater {
+  type Intent = TimeEaterIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use TimeEaterIntent::*;
+    let creature = &context.monster().creature;
+    if creature.hitpoints < creature.max_hitpoints / 2
+      && !context.monster().move_history.contains(&Haste.id())
+    {
+      context.always(Haste);
+      return;
+    }
+    // the rerolls exclude the range that led to the repeat
+    context.if_num_lt(
+      45,
+      context.with_max_repeats(Repeats(2), Reverberate, split(0.6, HeadSlam, Ripple)),
+    );
+    context.if_num_lt(
+      80,
+      context.with_max_repeats(Repeats(1), HeadSlam, split(0.66, Reverberate, Ripple)),
+    );
+    context.else_num(context.with_max_repeats(
+      Repeats(1),
+      Ripple,
+      split(0.6, Reverberate, HeadSlam),
+    ));
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    power_monster(runner, monster_index, PowerId::TimeWarp, 0);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use TimeEaterIntent::*;
+    match context.intent::<Self::Intent>() {
+      Reverberate => {
+        for _ in 0..3 {
+          context.attack(context.with_ascension(Ascension(4), 8, 7));
+        }
+      }
+      Ripple => {
+        context.block(20);
+        context.power_player(PowerId::Vulnerable, 1);
+        context.power_player(PowerId::Weak, 1);
+        if context.ascension() >= 19 {
+          context.power_player(PowerId::Frail, 1);
+        }
+      }
+      HeadSlam => {
+        context.attack(context.with_ascension(Ascension(4), 32, 26));
+        context.power_player(PowerId::DrawReduction, 1);
+        if context.ascension() >= 19 {
+          context.discard_status(CardId::Slimed, 2);
+        }
+      }
+      Haste => {
+        context.action(RemoveDebuffsAction(context.creature_index()));
+        let creature = &context.monster().creature;
+        let amount = creature.max_hitpoints / 2 - creature.hitpoints;
+        context.action(HealAction {
+          creature_index: context.creature_index(),
+          amount,
+        });
+        if context.ascension() >= 19 {
+          context.block(32);
+        }
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum AwakenedOneIntent {
+    1: Slash,
+    2: SoulStrike,
+    3: Rebirth,
+    5: DarkEcho,
+    6: Sludge,
+    8: Tackle,
+  }
+}
+impl MonsterBehavior for AwakenedOne {
+  type Intent = AwakenedOneIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use AwakenedOneIntent::*;
+    let last = context.last_intent::<Self::Intent>();
+    // it gets the Rebirth intent when it falls
+    if context.monster().half_dead {
+      context.always(Rebirth);
+      return;
+    }
+    if context.monster().creature.has_power(PowerId::Unawakened) {
+      if context.first_move() {
+        context.always(Slash);
+        return;
+      }
+      context.if_num_lt(25, context.with_max_repeats(Repeats(1), SoulStrike, Slash));
+      context.else_num(context.with_max_repeats(Repeats(2), Slash, SoulStrike));
+    } else if last == Some(Rebirth) {
+      context.always(DarkEcho);
+    } else {
+      context.if_num_lt(50, context.with_max_repeats(Repeats(2), Sludge, Tackle));
+      context.else_num(context.with_max_repeats(Repeats(2), Tackle, Sludge));
+    }
+  }
+  fn on_death(runner: &mut impl Runner, monster_index: usize) {
+    let state = runner.state_mut();
+    let monster = &mut state.monsters[monster_index];
+    if monster.creature.has_power(PowerId::Unawakened) {
+      monster.half_dead = true;
+      monster.creature.powers.retain(|power| {
+        !is_debuff(power.power_id, power.amount)
+          && power.power_id != PowerId::Curiosity
+          && power.power_id != PowerId::Unawakened
+      });
+      monster.push_intent(AwakenedOneIntent::Rebirth.id());
+    } else {
+      // the Cultists run away when the Awakened One dies for good
+      for monster in &mut state.monsters {
+        monster.gone = true;
+      }
+    }
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    let ascension = runner.state().monsters[monster_index].ascension;
+    let regenerate = if ascension >= 19 { 15 } else { 10 };
+    let curiosity = if ascension >= 19 { 2 } else { 1 };
+    power_monster(
+      runner,
+      monster_index,
+      PowerId::RegenerateMonster,
+      regenerate,
+    );
+    power_monster(runner, monster_index, PowerId::Curiosity, curiosity);
+    power_monster(runner, monster_index, PowerId::Unawakened, -1);
+    if ascension >= 4 {
+      power_monster(runner, monster_index, PowerId::Strength, 2);
+    }
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use AwakenedOneIntent::*;
+    match context.intent::<Self::Intent>() {
+      Slash => context.attack(20),
+      SoulStrike => {
+        for _ in 0..4 {
+          context.attack(6);
+        }
+      }
+      Rebirth => context.action(ReviveMonsterAction {
+        monster_index: context.monster_index(),
+        hitpoints: context.monster().creature.max_hitpoints,
+      }),
+      DarkEcho => context.attack(40),
+      Sludge => {
+        context.attack(18);
+        context.action(ShuffleNewCardIntoDrawPile(SingleCard::create(
+          CardId::VoidCard,
+        )));
+      }
+      Tackle => {
+        for _ in 0..3 {
+          context.attack(10);
+        }
+      }
+    }
+  }
+}
+
+fn donu_and_deca_artifact(runner: &mut impl Runner, monster_index: usize) {
+  let ascension = runner.state().monsters[monster_index].ascension;
+  power_monster(
+    runner,
+    monster_index,
+    PowerId::Artifact,
+    if ascension >= 19 { 3 } else { 2 },
+  );
+}
+
+intent! {
+  pub enum DonuIntent {
+    0: Beam,
+    2: CircleOfPower,
+  }
+}
+impl MonsterBehavior for Donu {
+  type Intent = DonuIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use DonuIntent::*;
+    context.always(match context.last_intent::<Self::Intent>() {
+      Some(CircleOfPower) => Beam,
+      _ => CircleOfPower,
+    });
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    donu_and_deca_artifact(runner, monster_index);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use DonuIntent::*;
+    match context.intent::<Self::Intent>() {
+      Beam => {
+        for _ in 0..2 {
+          context.attack(context.with_ascension(Ascension(4), 12, 10));
+        }
+      }
+      CircleOfPower => {
+        let source = context.creature_index();
+        for index in 0..context.state().monsters.len() {
+          if !context.state().monsters[index].gone {
+            context.action(ApplyPowerAction {
+              source,
+              target: CreatureIndex::Monster(index),
+              power_id: PowerId::Strength,
+              amount: 3,
+            });
+          }
+        }
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum DecaIntent {
+    0: Beam,
+    2: SquareOfProtection,
+  }
+}
+impl MonsterBehavior for Deca {
+  type Intent = DecaIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use DecaIntent::*;
+    context.always(match context.last_intent::<Self::Intent>() {
+      Some(Beam) => SquareOfProtection,
+      _ => Beam,
+    });
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    donu_and_deca_artifact(runner, monster_index);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use DecaIntent::*;
+    match context.intent::<Self::Intent>() {
+      Beam => {
+        for _ in 0..2 {
+          context.attack(context.with_ascension(Ascension(4), 12, 10));
+        }
+        context.discard_status(CardId::Dazed, 2);
+      }
+      SquareOfProtection => {
+        let source = context.creature_index();
+        for index in 0..context.state().monsters.len() {
+          if !context.state().monsters[index].gone {
+            let target = CreatureIndex::Monster(index);
+            context.action(GainBlockAction {
+              creature_index: target,
+              amount: 16,
+            });
+            if context.ascension() >= 19 {
+              context.action(ApplyPowerAction {
+                source,
+                target,
+                power_id: PowerId::PlatedArmor,
+                amount: 3,
+              });
+            }
+          }
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::simulation::tests::*;
+
+  fn give_power(creature: &mut Creature, power_id: PowerId, amount: i32) {
+    creature.powers.push(Power {
+      power_id,
+      amount,
+      ..Default::default()
+    });
+  }
+
+  #[test]
+  fn it_is_time_hits_harder_after_the_countdown() {
+    use GiantHeadIntent::*;
+    let mut state = combat(&[], &[MonsterId::GiantHead]);
+    state.monsters[0].move_history = vec![Glare.id(), Count.id(), Glare.id(), Count.id()];
+    assert_eq!(
+      intent_choice_distribution(&state, 0).unwrap().0.as_slice(),
+      &[(1.0, ItIsTime.id())]
+    );
+    state.monsters[0].move_history.push(ItIsTime.id());
+    assert_eq!(state.total_monster_attack_intent_damage(), 30);
+    state.monsters[0].move_history.push(ItIsTime.id());
+    assert_eq!(state.total_monster_attack_intent_damage(), 35);
+  }
+
+  #[test]
+  fn exploders_explode_on_their_third_turn() {
+    use ExploderIntent::*;
+    let mut state = combat(&[], &[MonsterId::Exploder, MonsterId::Exploder]);
+    state.monsters[0].move_history = vec![Attack.id(), Attack.id(), Explode.id()];
+    state.monsters[1].move_history = vec![Attack.id()];
+    give_power(&mut state.monsters[0].creature, PowerId::Explosive, 1);
+    give_power(&mut state.monsters[1].creature, PowerId::Explosive, 3);
+    choose(&mut state, EndTurn);
+    assert_eq!(state.player.creature.hitpoints, 70 - 30 - 9);
+    assert!(state.monsters[0].gone);
+    assert_eq!(
+      state.monsters[1].creature.power_amount(PowerId::Explosive),
+      2
+    );
+  }
+
+  #[test]
+  fn slow_grows_with_every_card_played() {
+    let mut state = combat(&[CardId::StrikeR; 3], &[MonsterId::GiantHead]);
+    give_power(&mut state.monsters[0].creature, PowerId::Slow, 0);
+    for _ in 0..3 {
+      play(&mut state, CardId::StrikeR, 0);
+    }
+    assert_eq!(state.monsters[0].creature.hitpoints, 40 - 6 - 6 - 7);
+    assert_eq!(state.monsters[0].creature.power_amount(PowerId::Slow), 3);
+  }
+
+  #[test]
+  fn time_warp_ends_the_turn_after_twelve_cards() {
+    let mut state = combat(&[CardId::StrikeR], &[MonsterId::TimeEater]);
+    state.monsters[0].move_history = vec![TimeEaterIntent::Ripple.id()];
+    give_power(&mut state.monsters[0].creature, PowerId::TimeWarp, 11);
+    play(&mut state, CardId::StrikeR, 0);
+    assert_eq!(state.turn_number, 2);
+    let time_eater = &state.monsters[0].creature;
+    assert_eq!(time_eater.power_amount(PowerId::TimeWarp), 0);
+    assert_eq!(time_eater.power_amount(PowerId::Strength), 2);
+    assert_eq!(time_eater.block, 20);
+  }
+
+  #[test]
+  fn invincible_caps_the_damage_each_turn() {
+    let mut state = combat(&[CardId::StrikeR, CardId::StrikeR], &[MonsterId::GiantHead]);
+    give_power(&mut state.monsters[0].creature, PowerId::Invincible, 10);
+    play(&mut state, CardId::StrikeR, 0);
+    play(&mut state, CardId::StrikeR, 0);
+    assert_eq!(state.monsters[0].creature.hitpoints, 40 - 10);
+    assert_eq!(
+      state.monsters[0].creature.power_amount(PowerId::Invincible),
+      0
+    );
+  }
+
+  fn darklings() -> CombatState {
+    let mut state = combat(&[], &[MonsterId::Darkling; 3]);
+    for monster in &mut state.monsters {
+      monster.innate_damage_amount = Some(8);
+      monster.move_history = vec![DarklingIntent::Harden.id()];
+      give_power(&mut monster.creature, PowerId::Regrow, -1);
+    }
+    state
+  }
+
+  #[test]
+  fn darklings_only_die_together() {
+    let mut state = darklings();
+    run(&mut state, SuicideAction(0));
+    let fallen = &state.monsters[0];
+    assert!(fallen.gone && fallen.half_dead);
+    assert_eq!(
+      fallen.move_history.last(),
+      Some(&DarklingIntent::Count.id())
+    );
+    assert!(!state.combat_over());
+    assert_eq!(
+      intent_choice_distribution(&state, 0).unwrap().0.as_slice(),
+      &[(1.0, DarklingIntent::Reincarnate.id())]
+    );
+
+    run(&mut state, SuicideAction(1));
+    assert!(!state.combat_over());
+    run(&mut state, SuicideAction(2));
+    assert!(state.monsters.iter().all(|monster| !monster.half_dead));
+    assert!(state.combat_over());
+  }
+
+  #[test]
+  fn darklings_reincarnate_with_half_their_hitpoints() {
+    let mut state = darklings();
+    run(&mut state, SuicideAction(0));
+    choose(&mut state, EndTurn);
+    assert!(state.monsters[0].half_dead);
+    choose(&mut state, EndTurn);
+    let revived = &state.monsters[0];
+    assert!(!revived.gone && !revived.half_dead);
+    assert_eq!(revived.creature.hitpoints, 20);
+    assert!(revived.creature.has_power(PowerId::Regrow));
+  }
+
+  #[test]
+  fn the_awakened_one_comes_back_once() {
+    let mut state = combat(
+      &[],
+      &[
+        MonsterId::Cultist,
+        MonsterId::AwakenedOne,
+        MonsterId::Cultist,
+      ],
+    );
+    let awakened_one = &mut state.monsters[1].creature;
+    give_power(awakened_one, PowerId::Unawakened, -1);
+    give_power(awakened_one, PowerId::Curiosity, 1);
+    give_power(awakened_one, PowerId::RegenerateMonster, 10);
+    give_power(awakened_one, PowerId::Weak, 2);
+    run(&mut state, SuicideAction(1));
+    let fallen = &state.monsters[1];
+    assert!(fallen.half_dead);
+    let powers: Vec<PowerId> = fallen
+      .creature
+      .powers
+      .iter()
+      .map(|power| power.power_id)
+      .collect();
+    assert_eq!(powers, [PowerId::RegenerateMonster]);
+    assert_eq!(
+      intent_choice_distribution(&state, 1).unwrap().0.as_slice(),
+      &[(1.0, AwakenedOneIntent::Rebirth.id())]
+    );
+
+    run(
+      &mut state,
+      ReviveMonsterAction {
+        monster_index: 1,
+        hitpoints: 40,
+      },
+    );
+    assert!(!state.monsters[1].gone);
+    assert_eq!(state.monsters[1].creature.hitpoints, 40);
+    // the second time, it takes the Cultists with it
+    run(&mut state, SuicideAction(1));
+    assert!(state.combat_over());
+    assert!(state.monsters.iter().all(|monster| monster.gone));
+  }
+}
@@ -30,6 +30,15 @@ impl MonsterBehavior for Byrd {
       context.always(context.with_max_repeats(Repeats(1), Headbutt, Fly));
     }
   }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    let ascension = runner.state().monsters[monster_index].ascension;
+    power_monster(
+      runner,
+      monster_index,
+      PowerId::Flight,
+      if ascension >= 17 { 4 } else { 3 },
+    );
+  }
   fn intent_effects(context: &mut impl IntentEffectsContext) {
     use ByrdIntent::*;
     match context.intent::<Self::Intent>() {
@@ -67,6 +76,14 @@ impl MonsterBehavior for SphericGuardian {
       context.always(context.with_max_repeats(Repeats(1), Slam, Harden));
     }
   }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    power_monster(runner, monster_index, PowerId::Barricade, -1);
+    power_monster(runner, monster_index, PowerId::Artifact, 3);
+    runner.action_bottom(GainBlockAction {
+      creature_index: CreatureIndex::Monster(monster_index),
+      amount: 40,
+    });
+  }
   fn intent_effects(context: &mut impl IntentEffectsContext) {
     use SphericGuardianIntent::*;
     match context.intent::<Self::Intent>() {
@@ -88,6 +105,421 @@ impl MonsterBehavior for SphericGuardian {
   }
 }
 
+intent! {
+  pub enum ChosenIntent {
+    1: Zap,
+    2: Drain,
+    3: Debilitate,
+    4: Hex,
+    5: Poke,
+  }
+}
+impl MonsterBehavior for Chosen {
+  type Intent = ChosenIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use ChosenIntent::*;
+    if context.first_move() && context.ascension() < 17 {
+      context.always(Poke);
+    } else if !context.monster().move_history.contains(&Hex.id()) {
+      context.always(Hex);
+    } else if let Some(Debilitate) | Some(Drain) = context.last_intent::<Self::Intent>() {
+      context.always(split(0.4, Zap, Poke));
+    } else {
+      context.always(split(0.5, Debilitate, Drain));
+    }
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use ChosenIntent::*;
+    match context.intent::<Self::Intent>() {
+      Zap => context.attack(context.with_ascension(Ascension(2), 21, 18)),
+      Drain => {
+        context.power_player(PowerId::Weak, 3);
+        context.power_self(PowerId::Strength, 3);
+      }
+      Debilitate => {
+        context.attack(context.with_ascension(Ascension(2), 12, 10));
+        context.power_player(PowerId::Vulnerable, 2);
+      }
+      Hex => context.power_player(PowerId::Hex, 1),
+      Poke => {
+        for _ in 0..2 {
+          context.attack(context.with_ascension(Ascension(2), 6, 5));
+        }
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum ShelledParasiteIntent {
+    1: Fell,
+    2: DoubleStrike,
+    3: LifeSuck,
+    4: Stunned,
+  }
+}
+impl MonsterBehavior for ShelledParasite {
+  type Intent = ShelledParasiteIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use ShelledParasiteIntent::*;
+    if context.first_move() {
+      context.always(context.with_ascension(
+        Ascension(17),
+        Distribution::from(Fell),
+        split(0.5, DoubleStrike, LifeSuck),
+      ));
+      return;
+    }
+    let last = context.last_intent::<Self::Intent>();
+    if last == Some(Stunned) {
+      context.always(Fell);
+      return;
+    }
+    let double_strike = context.with_max_repeats(Repeats(2), DoubleStrike, LifeSuck);
+    let life_suck = context.with_max_repeats(Repeats(2), LifeSuck, DoubleStrike);
+    if last == Some(Fell) {
+      // rerolled in the range above Fell's
+      context.if_num_lt(20, double_strike.clone() * 0.5 + life_suck.clone() * 0.5);
+    } else {
+      context.if_num_lt(20, Fell);
+    }
+    context.if_num_lt(60, double_strike);
+    context.else_num(life_suck);
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    power_monster(runner, monster_index, PowerId::PlatedArmor, 14);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use ShelledParasiteIntent::*;
+    match context.intent::<Self::Intent>() {
+      Fell => {
+        context.attack(context.with_ascension(Ascension(2), 21, 18));
+        context.power_player(PowerId::Frail, 2);
+      }
+      DoubleStrike => {
+        for _ in 0..2 {
+          context.attack(context.with_ascension(Ascension(2), 7, 6));
+        }
+      }
+      LifeSuck => context.vampire_attack(context.with_ascension(Ascension(2), 12, 10)),
+      Stunned => {}
+    }
+  }
+}
+
+intent! {
+  pub enum SnakePlantIntent {
+    1: Chomp,
+    2: Spores,
+  }
+}
+impl MonsterBehavior for SnakePlant {
+  type Intent = SnakePlantIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use SnakePlantIntent::*;
+    // at higher ascensions, it won't use Spores within two moves of the last time
+    let recent = context.with_ascension(Ascension(17), 2, 1);
+    let spored_recently = context
+      .monster()
+      .move_history
+      .iter()
+      .rev()
+      .take(recent)
+      .any(|&intent| intent == Spores.id());
+    context.if_num_lt(65, context.with_max_repeats(Repeats(2), Chomp, Spores));
+    context.else_num(if spored_recently { Chomp } else { Spores });
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    power_monster(runner, monster_index, PowerId::Malleable, 3);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use SnakePlantIntent::*;
+    match context.intent::<Self::Intent>() {
+      Chomp => {
+        for _ in 0..3 {
+          context.attack(context.with_ascension(Ascension(2), 8, 7));
+        }
+      }
+      Spores => {
+        context.power_player(PowerId::Frail, 2);
+        context.power_player(PowerId::Weak, 2);
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum SneckoIntent {
+    1: Glare,
+    2: Bite,
+    3: TailWhip,
+  }
+}
+impl MonsterBehavior for Snecko {
+  type Intent = SneckoIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use SneckoIntent::*;
+    if context.first_move() {
+      context.always(Glare);
+    } else {
+      context.if_num_lt(40, TailWhip);
+      context.else_num(context.with_max_repeats(Repeats(2), Bite, TailWhip));
+    }
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use SneckoIntent::*;
+    match context.intent::<Self::Intent>() {
+      Glare => context.power_player(PowerId::Confusion, -1),
+      Bite => context.attack(context.with_ascension(Ascension(2), 18, 15)),
+      TailWhip => {
+        context.attack(context.with_ascension(Ascension(2), 10, 8));
+        context.power_player(PowerId::Vulnerable, 2);
+        if context.ascension() >= 17 {
+          context.power_player(PowerId::Weak, 2);
+        }
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum CenturionIntent {
+    1: Slash,
+    2: Protect,
+    3: Fury,
+  }
+}
+impl MonsterBehavior for Centurion {
+  type Intent = CenturionIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use CenturionIntent::*;
+    let others_alive = context
+      .state()
+      .monsters
+      .iter()
+      .enumerate()
+      .any(|(index, monster)| index != context.monster_index() && !monster.gone);
+    let support = if others_alive { Protect } else { Fury };
+    if !context.did_repeats(Repeats(2), Protect) && !context.did_repeats(Repeats(2), Fury) {
+      context.if_num_geq(65, support);
+    }
+    context.else_num(context.with_max_repeats(Repeats(2), Slash, support));
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use CenturionIntent::*;
+    match context.intent::<Self::Intent>() {
+      Slash => context.attack(context.with_ascension(Ascension(2), 14, 12)),
+      Protect => context.action(GainBlockRandomMonsterAction {
+        source: context.monster_index(),
+        amount: context.with_ascension(Ascension(17), 20, 15),
+      }),
+      Fury => {
+        for _ in 0..3 {
+          context.attack(context.with_ascension(Ascension(2), 7, 6));
+        }
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum MysticIntent {
+    1: Attack,
+    2: Heal,
+    3: Buff,
+  }
+}
+impl MonsterBehavior for Mystic {
+  type Intent = MysticIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use MysticIntent::*;
+    let missing_hitpoints: i32 = context
+      .state()
+      .monsters
+      .iter()
+      .filter(|monster| !monster.gone)
+      .map(|monster| monster.creature.max_hitpoints - monster.creature.hitpoints)
+      .sum();
+    if missing_hitpoints > context.with_ascension(Ascension(17), 15, 20)
+      && !context.did_repeats(Repeats(2), Heal)
+    {
+      context.always(Heal);
+      return;
+    }
+    let max_buff_repeats = Repeats(context.with_ascension(Ascension(17), 1, 2));
+    if !context.did_repeats(max_buff_repeats, Buff) {
+      context.if_num_geq(40, Buff);
+    }
+    context.else_num(context.with_max_repeats(Repeats(2), Attack, Buff));
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use MysticIntent::*;
+    match context.intent::<Self::Intent>() {
+      Attack => {
+        context.attack(context.with_ascension(Ascension(2), 9, 8));
+        context.power_player(PowerId::Frail, 2);
+      }
+      Heal => {
+        let amount = context.with_ascension(Ascension(17), 20, 16);
+        for index in 0..context.state().monsters.len() {
+          if !context.state().monsters[index].gone {
+            context.action(HealAction {
+              creature_index: CreatureIndex::Monster(index),
+              amount,
+            });
+          }
+        }
+      }
+      Buff => {
+        let source = context.creature_index();
+        let amount = context.with_ascensions(Ascension(17), 4, Ascension(2), 3, 2);
+        for index in 0..context.state().monsters.len() {
+          if !context.state().monsters[index].gone {
+            context.action(ApplyPowerAction {
+              source,
+              target: CreatureIndex::Monster(index),
+              power_id: PowerId::Strength,
+              amount,
+            });
+          }
+        }
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum MuggerIntent {
+    1: Mug,
+    4: Lunge,
+    2: SmokeBomb,
+    3: Escape,
+  }
+}
+impl MonsterBehavior for Mugger {
+  type Intent = MuggerIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use MuggerIntent::*;
+    if context.state().turn_number < 2 {
+      context.always(Mug);
+    } else if context.state().turn_number == 2 {
+      context.always(split(0.5, Lunge, SmokeBomb));
+    } else {
+      context.always(context.with_max_repeats(Repeats(1), SmokeBomb, Escape));
+    }
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    let ascension = runner.state().monsters[monster_index].ascension;
+    power_monster(
+      runner,
+      monster_index,
+      PowerId::Thievery,
+      if ascension >= 17 { 20 } else { 15 },
+    );
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use MuggerIntent::*;
+    match context.intent::<Self::Intent>() {
+      Mug => context.attack(context.with_ascension(Ascension(2), 11, 10)),
+      Lunge => context.attack(context.with_ascension(Ascension(2), 18, 16)),
+      SmokeBomb => context.block(context.with_ascension(Ascension(17), 17, 11)),
+      Escape => context.action(EscapeAction(context.monster_index())),
+    }
+  }
+}
+
+intent! {
+  pub enum BearIntent {
+    1: Maul,
+    2: BearHug,
+    3: Lunge,
+  }
+}
+impl MonsterBehavior for Bear {
+  type Intent = BearIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use BearIntent::*;
+    context.always(match context.last_intent::<Self::Intent>() {
+      None => BearHug,
+      Some(Lunge) => Maul,
+      Some(_) => Lunge,
+    });
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use BearIntent::*;
+    match context.intent::<Self::Intent>() {
+      Maul => context.attack(context.with_ascension(Ascension(2), 20, 18)),
+      BearHug => context.power_player(
+        PowerId::Dexterity,
+        context.with_ascension(Ascension(17), -4, -2),
+      ),
+      Lunge => {
+        context.attack(context.with_ascension(Ascension(2), 10, 9));
+        context.block(9);
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum PointyIntent {
+    1: Attack,
+  }
+}
+impl MonsterBehavior for Pointy {
+  type Intent = PointyIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    context.always(PointyIntent::Attack);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use PointyIntent::*;
+    match context.intent::<Self::Intent>() {
+      Attack => {
+        for _ in 0..2 {
+          context.attack(context.with_ascension(Ascension(2), 6, 5));
+        }
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum RomeoIntent {
+    1: CrossSlash,
+    2: Mock,
+    3: AgonizingSlash,
+  }
+}
+impl MonsterBehavior for Romeo {
+  type Intent = RomeoIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use RomeoIntent::*;
+    context.always(match context.last_intent::<Self::Intent>() {
+      None => Mock,
+      Some(Mock) => AgonizingSlash,
+      Some(AgonizingSlash) => CrossSlash,
+      Some(CrossSlash) => {
+        if context.ascension() >= 17 && !context.did_repeats(Repeats(2), CrossSlash) {
+          CrossSlash
+        } else {
+          AgonizingSlash
+        }
+      }
+    });
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use RomeoIntent::*;
+    match context.intent::<Self::Intent>() {
+      CrossSlash => context.attack(context.with_ascension(Ascension(2), 17, 15)),
+      Mock => {}
+      AgonizingSlash => {
+        context.attack(context.with_ascension(Ascension(2), 12, 10));
+        context.power_player(PowerId::Weak, context.with_ascension(Ascension(17), 3, 2));
+      }
+    }
+  }
+}
+
 intent! {
   pub enum TaskmasterIntent {
     2: ScouringWhip,
@@ -138,17 +570,21 @@ impl MonsterBehavior for GremlinLeader {
       context.if_num_lt(75, context.with_max_repeats(Repeats(1), Rally, Stab));
       context.else_num(context.with_max_repeats(Repeats(1), Stab, Rally));
     } else if num_alive_gremlins < 2 {
-      let last = context.last_intent::<GremlinLeaderIntent>().unwrap();
-      context.always(match last {
-        Stab => split(0.625, Rally, Encourage),
-        Rally => unreachable!(),
-        Encourage => split(0.5, Rally, Stab),
+      context.always(match context.last_intent::<GremlinLeaderIntent>() {
+        Some(Stab) => split(0.625, Rally, Encourage),
+        // Rally usually refills the slots, but the summons can fail or die before the next choice.
+        Some(Rally) => split(0.5, Encourage, Stab),
+        Some(Encourage) | None => split(0.5, Rally, Stab),
       });
     } else {
       context.if_num_lt(66, context.with_max_repeats(Repeats(1), Encourage, Stab));
       context.else_num(context.with_max_repeats(Repeats(1), Stab, Encourage));
     }
   }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    // the gremlins it starts with are minions too
+    minions_except(runner, monster_index);
+  }
   fn intent_effects(context: &mut impl IntentEffectsContext) {
     use GremlinLeaderIntent::*;
     match context.intent::<Self::Intent>() {
@@ -183,3 +619,423 @@ impl MonsterBehavior for GremlinLeader {
     }
   }
 }
+
+intent! {
+  pub enum BookOfStabbingIntent {
+    1: MultiStab,
+    2: SingleStab,
+  }
+}
+impl MonsterBehavior for BookOfStabbing {
+  type Intent = BookOfStabbingIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use BookOfStabbingIntent::*;
+    if context.last_intent::<Self::Intent>() == Some(SingleStab) {
+      context.if_num_lt(15, MultiStab);
+    } else {
+      context.if_num_lt(15, SingleStab);
+    }
+    context.else_num(context.with_max_repeats(Repeats(2), MultiStab, SingleStab));
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    power_monster(runner, monster_index, PowerId::PainfulStabs, -1);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use BookOfStabbingIntent::*;
+    match context.intent::<Self::Intent>() {
+      MultiStab => {
+        // The number of stabs grows with every Multi-Stab, and at A18 with every Single Stab too.
+        // TODO: the history imported from CommunicationMod only has the last few moves, so this
+        // undercounts in the middle of a long fight.
+        let history = &context.monster().move_history;
+        let multi_stabs = history
+          .iter()
+          .filter(|&&intent| intent == MultiStab.id())
+          .count();
+        let single_stabs = history
+          .iter()
+          .filter(|&&intent| intent == SingleStab.id())
+          .count();
+        let stabs = 1 + multi_stabs + context.with_ascension(Ascension(18), single_stabs, 0);
+        for _ in 0..stabs {
+          context.attack(context.with_ascension(Ascension(3), 7, 6));
+        }
+      }
+      SingleStab => context.attack(context.with_ascension(Ascension(3), 24, 21)),
+    }
+  }
+}
+
+intent! {
+  pub enum TheChampIntent {
+    1: HeavySlash,
+    2: DefensiveStance,
+    3: Execute,
+    4: FaceSlap,
+    5: Gloat,
+    6: Taunt,
+    7: Anger,
+  }
+}
+impl MonsterBehavior for TheChamp {
+  type Intent = TheChampIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use TheChampIntent::*;
+    let history = &context.monster().move_history;
+    let creature = &context.monster().creature;
+    let enraged = history.contains(&Anger.id());
+    if !enraged && creature.hitpoints < creature.max_hitpoints / 2 {
+      context.always(Anger);
+      return;
+    }
+    if enraged
+      && !history
+        .iter()
+        .rev()
+        .take(2)
+        .any(|&intent| intent == Execute.id())
+    {
+      context.always(Execute);
+      return;
+    }
+    // it taunts every 4th move until it's enraged
+    let moves_since_taunt = history
+      .iter()
+      .rev()
+      .take_while(|&&intent| intent != Taunt.id())
+      .count();
+    if !enraged && moves_since_taunt == 3 {
+      context.always(Taunt);
+      return;
+    }
+
+    let last = context.last_intent::<Self::Intent>();
+    let defensive_stances = history
+      .iter()
+      .filter(|&&intent| intent == DefensiveStance.id())
+      .count();
+    if last != Some(DefensiveStance) && defensive_stances < 2 {
+      context.if_num_leq(
+        context.with_ascension(Ascension(19), 30, 15),
+        DefensiveStance,
+      );
+    }
+    if last != Some(Gloat) && last != Some(DefensiveStance) {
+      context.if_num_leq(30, Gloat);
+    }
+    if last != Some(FaceSlap) {
+      context.if_num_leq(55, FaceSlap);
+    }
+    context.else_num(context.with_max_repeats(Repeats(1), HeavySlash, FaceSlap));
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use TheChampIntent::*;
+    let strength = context.with_ascensions(Ascension(19), 4, Ascension(4), 3, 2);
+    match context.intent::<Self::Intent>() {
+      HeavySlash => context.attack(context.with_ascension(Ascension(4), 18, 16)),
+      DefensiveStance => {
+        context.block(context.with_ascensions(Ascension(19), 20, Ascension(9), 18, 15));
+        context.power_self(
+          PowerId::Metallicize,
+          context.with_ascensions(Ascension(19), 7, Ascension(9), 6, 5),
+        );
+      }
+      Execute => {
+        for _ in 0..2 {
+          context.attack(10);
+        }
+      }
+      FaceSlap => {
+        context.attack(context.with_ascension(Ascension(4), 14, 12));
+        context.power_player(PowerId::Frail, 2);
+        context.power_player(PowerId::Vulnerable, 2);
+      }
+      Gloat => context.power_self(PowerId::Strength, strength),
+      Taunt => {
+        context.power_player(PowerId::Weak, 2);
+        context.power_player(PowerId::Vulnerable, 2);
+      }
+      Anger => {
+        context.action(RemoveDebuffsAction(context.creature_index()));
+        context.power_self(PowerId::Strength, strength * 3);
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum BronzeAutomatonIntent {
+    1: Flail,
+    2: HyperBeam,
+    3: Stunned,
+    4: SpawnOrbs,
+    5: Boost,
+  }
+}
+impl MonsterBehavior for BronzeAutomaton {
+  type Intent = BronzeAutomatonIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use BronzeAutomatonIntent::*;
+    let turn = context.state().turn_number;
+    if turn == 0 {
+      context.always(SpawnOrbs);
+    } else {
+      context.always(match (turn - 1) % 6 {
+        0 | 2 => Flail,
+        1 | 3 => Boost,
+        4 => HyperBeam,
+        5 => context.with_ascension(Ascension(19), Boost, Stunned),
+        _ => unreachable!(),
+      });
+    }
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    power_monster(runner, monster_index, PowerId::Artifact, 3);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use BronzeAutomatonIntent::*;
+    match context.intent::<Self::Intent>() {
+      Flail => {
+        for _ in 0..2 {
+          context.attack(context.with_ascension(Ascension(4), 8, 7));
+        }
+      }
+      HyperBeam => context.attack(context.with_ascension(Ascension(4), 50, 45)),
+      Stunned => {}
+      SpawnOrbs => {
+        for _ in 0..2 {
+          context.spawn_minion(MonsterId::BronzeOrb);
+        }
+      }
+      Boost => {
+        context.block(context.with_ascension(Ascension(9), 12, 9));
+        context.power_self(
+          PowerId::Strength,
+          context.with_ascension(Ascension(4), 4, 3),
+        );
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum BronzeOrbIntent {
+    1: Beam,
+    2: SupportBeam,
+    3: Stasis,
+  }
+}
+impl MonsterBehavior for BronzeOrb {
+  type Intent = BronzeOrbIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use BronzeOrbIntent::*;
+    let used_stasis = context.monster().creature.has_power(PowerId::Stasis)
+      || context.monster().move_history.contains(&Stasis.id());
+    let beam = context.with_max_repeats(Repeats(2), Beam, SupportBeam);
+    if used_stasis {
+      context.if_num_lt(70, beam.clone());
+      context.else_num(context.with_max_repeats(Repeats(2), SupportBeam, beam));
+    } else {
+      context.if_num_lt(25, beam);
+      context.else_num(Stasis);
+    }
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use BronzeOrbIntent::*;
+    match context.intent::<Self::Intent>() {
+      Beam => context.attack(8),
+      SupportBeam => {
+        if let Some(automaton) = context
+          .state()
+          .monsters
+          .iter()
+          .position(|monster| monster.monster_id == MonsterId::BronzeAutomaton && !monster.gone)
+        {
+          context.action(GainBlockAction {
+            creature_index: CreatureIndex::Monster(automaton),
+            amount: 12,
+          });
+        }
+      }
+      Stasis => context.action(StasisAction(context.monster_index())),
+    }
+  }
+}
+
+intent! {
+  pub enum TheCollectorIntent {
+    1: Spawn,
+    2: Fireball,
+    3: Buff,
+    4: MegaDebuff,
+    5: Revive,
+  }
+}
+impl MonsterBehavior for TheCollector {
+  type Intent = TheCollectorIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use TheCollectorIntent::*;
+    if context.first_move() {
+      context.always(Spawn);
+    } else if context.state().turn_number == 3 {
+      context.always(MegaDebuff);
+    } else {
+      let buff = context.with_max_repeats(Repeats(1), Buff, Fireball);
+      let fireball = context.with_max_repeats(Repeats(2), Fireball, buff.clone());
+      if context.state().living_minions() < 2
+        && context.last_intent::<Self::Intent>() != Some(Revive)
+      {
+        context.if_num_leq(25, Revive);
+      }
+      context.if_num_leq(70, fireball);
+      context.else_num(buff);
+    }
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use TheCollectorIntent::*;
+    match context.intent::<Self::Intent>() {
+      Spawn | Revive => {
+        for _ in context.state().living_minions()..2 {
+          context.spawn_minion(MonsterId::TorchHead);
+        }
+      }
+      Fireball => context.attack(context.with_ascension(Ascension(4), 21, 18)),
+      Buff => {
+        context.block(context.with_ascensions(Ascension(19), 23, Ascension(9), 18, 15));
+        let source = context.creature_index();
+        let amount = context.with_ascensions(Ascension(19), 5, Ascension(4), 4, 3);
+        for index in 0..context.state().monsters.len() {
+          if !context.state().monsters[index].gone {
+            context.action(ApplyPowerAction {
+              source,
+              target: CreatureIndex::Monster(index),
+              power_id: PowerId::Strength,
+              amount,
+            });
+          }
+        }
+      }
+      MegaDebuff => {
+        let amount = context.with_ascension(Ascension(19), 5, 3);
+        context.power_player(PowerId::Weak, amount);
+        context.power_player(PowerId::Vulnerable, amount);
+        context.power_player(PowerId::Frail, amount);
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum TorchHeadIntent {
+    1: Tackle,
+  }
+}
+impl MonsterBehavior for TorchHead {
+  type Intent = TorchHeadIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    context.always(TorchHeadIntent::Tackle);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use TorchHeadIntent::*;
+    match context.intent::<Self::Intent>() {
+      Tackle => context.attack(7),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::simulation::tests::*;
+
+  fn next_intents(state: &CombatState, monster_index: usize) -> Vec<(f64, i32)> {
+    intent_choice_distribution(state, monster_index)
+      .unwrap()
+      .0
+      .into_vec()
+  }
+
+  #[test]
+  fn chosen_hexes_after_its_first_poke() {
+    let mut state = combat(&[], &[MonsterId::Chosen]);
+    assert_eq!(next_intents(&state, 0), [(1.0, ChosenIntent::Poke.id())]);
+    state.monsters[0].move_history = vec![ChosenIntent::Poke.id()];
+    assert_eq!(next_intents(&state, 0), [(1.0, ChosenIntent::Hex.id())]);
+    state.monsters[0].ascension = 17;
+    state.monsters[0].move_history.clear();
+    assert_eq!(next_intents(&state, 0), [(1.0, ChosenIntent::Hex.id())]);
+  }
+
+  #[test]
+  fn hex_shuffles_in_dazed_for_non_attacks() {
+    let mut state = combat(&[CardId::DefendR, CardId::StrikeR], &[MonsterId::Chosen]);
+    state.player.creature.powers.push(Power {
+      power_id: PowerId::Hex,
+      amount: 1,
+      ..Default::default()
+    });
+    play(&mut state, CardId::StrikeR, 0);
+    assert_eq!(state.draw_pile_len(), 0);
+    play(&mut state, CardId::DefendR, 0);
+    assert_eq!(
+      state.draw_pile.as_slice(),
+      cards(&[CardId::Dazed]).as_slice()
+    );
+  }
+
+  #[test]
+  fn life_suck_heals_for_the_damage_taken() {
+    let mut state = combat(&[], &[MonsterId::ShelledParasite]);
+    state.monsters[0].creature.hitpoints = 30;
+    state.monsters[0].move_history = vec![ShelledParasiteIntent::LifeSuck.id()];
+    state.player.creature.block = 4;
+    assert_eq!(state.total_monster_attack_intent_damage(), 10);
+    choose(&mut state, EndTurn);
+    assert_eq!(state.player.creature.hitpoints, 70 - 6);
+    assert_eq!(state.monsters[0].creature.hitpoints, 30 + 6);
+  }
+
+  #[test]
+  fn malleable_grows_for_the_rest_of_the_round() {
+    let mut state = combat(&[CardId::StrikeR, CardId::StrikeR], &[MonsterId::Snecko]);
+    state.monsters[0].creature.powers.push(Power {
+      power_id: PowerId::Malleable,
+      amount: 3,
+      ..Default::default()
+    });
+    play(&mut state, CardId::StrikeR, 0);
+    assert_eq!(state.monsters[0].creature.hitpoints, 40 - 6);
+    assert_eq!(state.monsters[0].creature.block, 3);
+    play(&mut state, CardId::StrikeR, 0);
+    assert_eq!(state.monsters[0].creature.hitpoints, 40 - 6 - 3);
+    assert_eq!(state.monsters[0].creature.block, 4);
+    assert_eq!(
+      state.monsters[0].creature.power_amount(PowerId::Malleable),
+      5
+    );
+  }
+
+  #[test]
+  fn book_of_stabbing_stabs_more_each_time() {
+    let mut state = combat(&[], &[MonsterId::BookOfStabbing]);
+    state.monsters[0].move_history = vec![
+      BookOfStabbingIntent::MultiStab.id(),
+      BookOfStabbingIntent::SingleStab.id(),
+      BookOfStabbingIntent::MultiStab.id(),
+    ];
+    assert_eq!(state.total_monster_attack_intent_damage(), 3 * 6);
+  }
+
+  #[test]
+  fn confusion_randomizes_the_cost_of_drawn_cards() {
+    let state = combat(&[CardId::Bash], &[MonsterId::Snecko]);
+    let action = RandomizeCardCostAction(SingleCard::create(CardId::Bash));
+    assert_eq!(
+      action.determinism(&state),
+      Determinism::Random(Distribution((0..4).map(|cost| (1.0, cost)).collect()))
+    );
+    let mut state = state;
+    action.execute_random(&mut runner(&mut state), 0);
+    assert_eq!(state.hand[0].cost, 0);
+  }
+}
@@ -1 +1,360 @@
+use super::*;
 
+/// Where this monster is in its repeating cycle of 3 moves, given the move it always makes at
+/// `anchor_position`. The game just counts the moves, but CommunicationMod only tells us the last
+/// few, so this goes by the last time it made the anchor move instead.
+fn cycle_position(
+  context: &IntentChoiceContext,
+  anchor: impl Intent,
+  anchor_position: usize,
+  moves_before_cycle: usize,
+) -> usize {
+  let anchor = anchor.id();
+  let history = &context.monster().move_history;
+  match history.iter().rev().position(|&intent| intent == anchor) {
+    Some(moves_since) => (anchor_position + 1 + moves_since) % 3,
+    None => history.len().saturating_sub(moves_before_cycle) % 3,
+  }
+}
+
+intent! {
+  pub enum SpireShieldIntent {
+    1: Bash,
+    2: Fortify,
+    3: Smash,
+  }
+}
+/// Spire Shield and Spire Spear can only surround the player together.
+fn end_surround(runner: &mut impl Runner) {
+  let state = runner.state_mut();
+  state
+    .player
+    .creature
+    .powers
+    .retain(|power| power.power_id != PowerId::Surrounded);
+  for monster in &mut state.monsters {
+    monster
+      .creature
+      .powers
+      .retain(|power| power.power_id != PowerId::BackAttack);
+  }
+}
+
+impl MonsterBehavior for SpireShield {
+  type Intent = SpireShieldIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use SpireShieldIntent::*;
+    match cycle_position(context, Smash, 2, 0) {
+      0 => context.always(split(0.5, Fortify, Bash)),
+      1 => context.always(context.with_max_repeats(Repeats(1), Bash, Fortify)),
+      _ => context.always(Smash),
+    }
+  }
+  fn on_death(runner: &mut impl Runner, _monster_index: usize) {
+    end_surround(runner);
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    let ascension = runner.state().monsters[monster_index].ascension;
+    runner.action_bottom(ApplyPowerAction {
+      source: CreatureIndex::Monster(monster_index),
+      target: CreatureIndex::Player,
+      power_id: PowerId::Surrounded,
+      amount: -1,
+    });
+    // the player starts out facing the Spear
+    power_monster(runner, monster_index, PowerId::BackAttack, -1);
+    power_monster(
+      runner,
+      monster_index,
+      PowerId::Artifact,
+      if ascension >= 18 { 2 } else { 1 },
+    );
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use SpireShieldIntent::*;
+    match context.intent::<Self::Intent>() {
+      Bash => {
+        context.attack(context.with_ascension(Ascension(3), 14, 12));
+        if context.state().player.orb_slots > 0 {
+          context.action(SpireShieldDebuffAction(context.monster_index()));
+        } else {
+          context.power_player(PowerId::Strength, -1);
+        }
+      }
+      Fortify => {
+        for index in 0..context.state().monsters.len() {
+          if !context.state().monsters[index].gone {
+            context.action(GainBlockAction {
+              creature_index: CreatureIndex::Monster(index),
+              amount: 30,
+            });
+          }
+        }
+      }
+      Smash => {
+        let info = DamageInfoNoPowers::new(
+          Some(context.creature_index()),
+          context.with_ascension(Ascension(3), 38, 34),
+          DamageType::Normal,
+        );
+        // it blocks for the damage it's about to deal, not what actually gets through
+        let block = if context.ascension() >= 18 {
+          99
+        } else {
+          info
+            .apply_all_powers(context.state(), CreatureIndex::Player)
+            .output
+        };
+        context.action(MonsterAttackAction { info });
+        context.block(block);
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum SpireSpearIntent {
+    1: BurnStrike,
+    2: Piercer,
+    3: Skewer,
+  }
+}
+impl MonsterBehavior for SpireSpear {
+  type Intent = SpireSpearIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use SpireSpearIntent::*;
+    match cycle_position(context, Skewer, 1, 0) {
+      0 => context.always(context.with_max_repeats(Repeats(1), BurnStrike, Piercer)),
+      1 => context.always(Skewer),
+      _ => context.always(split(0.5, Piercer, BurnStrike)),
+    }
+  }
+  fn on_death(runner: &mut impl Runner, _monster_index: usize) {
+    end_surround(runner);
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    let ascension = runner.state().monsters[monster_index].ascension;
+    power_monster(
+      runner,
+      monster_index,
+      PowerId::Artifact,
+      if ascension >= 18 { 2 } else { 1 },
+    );
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use SpireSpearIntent::*;
+    match context.intent::<Self::Intent>() {
+      BurnStrike => {
+        for _ in 0..2 {
+          context.attack(context.with_ascension(Ascension(3), 6, 5));
+        }
+        if context.ascension() >= 18 {
+          for _ in 0..2 {
+            context.action(ShuffleNewCardIntoDrawPile(SingleCard::create(CardId::Burn)));
+          }
+        } else {
+          context.discard_status(CardId::Burn, 2);
+        }
+      }
+      Piercer => {
+        let source = context.creature_index();
+        for index in 0..context.state().monsters.len() {
+          if !context.state().monsters[index].gone {
+            context.action(ApplyPowerAction {
+              source,
+              target: CreatureIndex::Monster(index),
+              power_id: PowerId::Strength,
+              amount: 2,
+            });
+          }
+        }
+      }
+      Skewer => {
+        for _ in 0..context.with_ascension(Ascension(3), 4, 3) {
+          context.attack(10);
+        }
+      }
+    }
+  }
+}
+
+intent! {
+  pub enum CorruptHeartIntent {
+    1: BloodShots,
+    2: Echo,
+    3: Debilitate,
+    4: Buff,
+  }
+}
+impl MonsterBehavior for CorruptHeart {
+  type Intent = CorruptHeartIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    use CorruptHeartIntent::*;
+    if context.first_move() {
+      context.always(Debilitate);
+      return;
+    }
+    match cycle_position(context, Buff, 2, 1) {
+      0 => context.always(split(0.5, BloodShots, Echo)),
+      1 => context.always(context.with_max_repeats(Repeats(1), Echo, BloodShots)),
+      _ => context.always(Buff),
+    }
+  }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    let ascension = runner.state().monsters[monster_index].ascension;
+    power_monster(
+      runner,
+      monster_index,
+      PowerId::Invincible,
+      if ascension >= 19 { 200 } else { 300 },
+    );
+    power_monster(
+      runner,
+      monster_index,
+      PowerId::BeatOfDeath,
+      if ascension >= 19 { 2 } else { 1 },
+    );
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use CorruptHeartIntent::*;
+    match context.intent::<Self::Intent>() {
+      BloodShots => {
+        for _ in 0..context.with_ascension(Ascension(4), 15, 12) {
+          context.attack(2);
+        }
+      }
+      Echo => context.attack(context.with_ascension(Ascension(4), 45, 40)),
+      Debilitate => {
+        context.power_player(PowerId::Vulnerable, 2);
+        context.power_player(PowerId::Weak, 2);
+        context.power_player(PowerId::Frail, 2);
+        for &card_id in &[
+          CardId::Dazed,
+          CardId::Slimed,
+          CardId::Wound,
+          CardId::Burn,
+          CardId::VoidCard,
+        ] {
+          context.action(ShuffleNewCardIntoDrawPile(SingleCard::create(card_id)));
+        }
+      }
+      Buff => {
+        // it gets rid of negative Strength before gaining its 2
+        let strength = context.monster().creature.power_amount(PowerId::Strength);
+        context.power_self(PowerId::Strength, 2 - strength.min(0));
+        // TODO: CommunicationMod only gives us the last few moves, so this can undercount
+        let previous_buffs = context
+          .monster()
+          .move_history
+          .iter()
+          .rev()
+          .skip(1)
+          .filter(|&&intent| intent == Buff.id())
+          .count();
+        match previous_buffs {
+          0 => context.power_self(PowerId::Artifact, 2),
+          1 => context.power_self(PowerId::BeatOfDeath, 1),
+          2 => context.power_self(PowerId::PainfulStabs, -1),
+          3 => context.power_self(PowerId::Strength, 10),
+          _ => context.power_self(PowerId::Strength, 50),
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::simulation::tests::*;
+
+  fn give_power(creature: &mut Creature, power_id: PowerId, amount: i32) {
+    creature.powers.push(Power {
+      power_id,
+      amount,
+      ..Default::default()
+    });
+  }
+
+  fn surrounded() -> CombatState {
+    let mut state = combat(
+      &[CardId::StrikeR, CardId::StrikeR],
+      &[MonsterId::SpireShield, MonsterId::SpireSpear],
+    );
+    give_power(&mut state.player.creature, PowerId::Surrounded, -1);
+    give_power(&mut state.monsters[0].creature, PowerId::BackAttack, -1);
+    state
+  }
+
+  #[test]
+  fn the_heart_cycles_after_debilitating() {
+    use CorruptHeartIntent::*;
+    let mut state = combat(&[], &[MonsterId::CorruptHeart]);
+    let mut next = |history: &[CorruptHeartIntent]| {
+      state.monsters[0].move_history = history.iter().map(|intent| intent.id()).collect();
+      intent_choice_distribution(&state, 0).unwrap().0.into_vec()
+    };
+    assert_eq!(next(&[]), [(1.0, Debilitate.id())]);
+    assert_eq!(
+      next(&[Debilitate]),
+      [(0.5, BloodShots.id()), (0.5, Echo.id())]
+    );
+    assert_eq!(next(&[Debilitate, Echo]), [(1.0, BloodShots.id())]);
+    assert_eq!(next(&[Debilitate, Echo, BloodShots]), [(1.0, Buff.id())]);
+    assert_eq!(
+      next(&[Debilitate, Echo, BloodShots, Buff]),
+      [(0.5, BloodShots.id()), (0.5, Echo.id())]
+    );
+  }
+
+  #[test]
+  fn attacking_one_side_turns_the_player_around() {
+    let mut state = surrounded();
+    play(&mut state, CardId::StrikeR, 0);
+    assert!(!state.monsters[0].creature.has_power(PowerId::BackAttack));
+    assert!(state.monsters[1].creature.has_power(PowerId::BackAttack));
+    play(&mut state, CardId::StrikeR, 0);
+    assert!(state.monsters[1].creature.has_power(PowerId::BackAttack));
+  }
+
+  #[test]
+  fn back_attacks_do_more_damage() {
+    let mut state = surrounded();
+    state.monsters[0].move_history = vec![SpireShieldIntent::Fortify.id()];
+    state.monsters[1].move_history = vec![SpireSpearIntent::BurnStrike.id()];
+    assert_eq!(state.total_monster_attack_intent_damage(), 5 + 5);
+    play(&mut state, CardId::StrikeR, 0);
+    assert_eq!(state.total_monster_attack_intent_damage(), 7 + 7);
+  }
+
+  #[test]
+  fn the_player_is_only_surrounded_while_both_are_alive() {
+    let mut state = surrounded();
+    run(&mut state, SuicideAction(1));
+    assert!(!state.player.creature.has_power(PowerId::Surrounded));
+    assert!(!state.monsters[0].creature.has_power(PowerId::BackAttack));
+  }
+
+  #[test]
+  fn beat_of_death_hurts_for_every_card() {
+    let mut state = combat(
+      &[CardId::DefendR, CardId::DefendR],
+      &[MonsterId::CorruptHeart],
+    );
+    give_power(&mut state.monsters[0].creature, PowerId::BeatOfDeath, 2);
+    play(&mut state, CardId::DefendR, 0);
+    play(&mut state, CardId::DefendR, 0);
+    // each beat comes after the block from its Defend
+    assert_eq!(state.player.creature.hitpoints, 70);
+    assert_eq!(state.player.creature.block, 5 + 5 - 2 - 2);
+  }
+
+  #[test]
+  fn spire_shield_takes_strength_without_orbs() {
+    let mut state = combat(&[], &[MonsterId::SpireShield]);
+    state.monsters[0].move_history = vec![SpireShieldIntent::Bash.id()];
+    choose(&mut state, EndTurn);
+    assert_eq!(state.player.creature.hitpoints, 70 - 12);
+    assert_eq!(state.player.creature.power_amount(PowerId::Strength), -1);
+  }
+}
@@ -28,6 +28,23 @@ impl MonsterBehavior for Cultist {
   }
 }
 
+/// Both lice start out with a random amount of Curl Up.
+fn curl_up(runner: &mut impl Runner, monster_index: usize) {
+  let ascension = runner.state().monsters[monster_index].ascension;
+  let range = if ascension >= 17 {
+    (9, 13)
+  } else if ascension >= 7 {
+    (4, 9)
+  } else {
+    (3, 8)
+  };
+  runner.action_bottom(InitializeMonsterPowerAmount {
+    monster_index,
+    power_id: PowerId::CurlUp,
+    range,
+  });
+}
+
 intent! {
   pub enum RedLouseIntent {
     3: Bite,
@@ -52,6 +69,9 @@ impl MonsterBehavior for RedLouse {
       });
     }
   }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    curl_up(runner, monster_index);
+  }
   fn intent_effects(context: &mut impl IntentEffectsContext) {
     use RedLouseIntent::*;
     match context.intent::<Self::Intent>() {
@@ -85,6 +105,9 @@ impl MonsterBehavior for GreenLouse {
     RedLouse::after_choosing_intent(runner, monster_index);
   }
 
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    curl_up(runner, monster_index);
+  }
   fn intent_effects(context: &mut impl IntentEffectsContext) {
     use GreenLouseIntent::*;
     match context.intent::<Self::Intent>() {
@@ -377,6 +400,9 @@ impl MonsterBehavior for FungiBeast {
     context.if_num_lt(60, context.with_max_repeats(Repeats(2), Bite, Grow));
     context.else_num(context.with_max_repeats(Repeats(1), Grow, Bite));
   }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    power_monster(runner, monster_index, PowerId::SporeCloud, 2);
+  }
   fn intent_effects(context: &mut impl IntentEffectsContext) {
     use FungiBeastIntent::*;
     match context.intent::<Self::Intent>() {
@@ -408,6 +434,15 @@ impl MonsterBehavior for Looter {
       context.always(context.with_max_repeats(Repeats(1), SmokeBomb, Escape));
     }
   }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    let ascension = runner.state().monsters[monster_index].ascension;
+    power_monster(
+      runner,
+      monster_index,
+      PowerId::Thievery,
+      if ascension >= 17 { 20 } else { 15 },
+    );
+  }
   fn intent_effects(context: &mut impl IntentEffectsContext) {
     use LooterIntent::*;
     match context.intent::<Self::Intent>() {
@@ -502,6 +537,15 @@ impl MonsterBehavior for MadGremlin {
     use MadGremlinIntent::*;
     context.always(Scratch);
   }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    let ascension = runner.state().monsters[monster_index].ascension;
+    power_monster(
+      runner,
+      monster_index,
+      PowerId::Angry,
+      if ascension >= 17 { 2 } else { 1 },
+    );
+  }
   fn intent_effects(context: &mut impl IntentEffectsContext) {
     use MadGremlinIntent::*;
     match context.intent::<Self::Intent>() {
@@ -537,8 +581,17 @@ impl MonsterBehavior for GremlinWizard {
   type Intent = GremlinWizardIntent;
   fn make_intent_distribution(context: &mut IntentChoiceContext) {
     use GremlinWizardIntent::*;
-    if context.state().turn_number >= 3
-      && (context.ascension() >= 17 || (context.state().turn_number % 4) == 3)
+    // It charges twice before its first blast and three times before each one after that.
+    // Counting from its own history rather than the turn number keeps summoned wizards right.
+    let history = &context.monster().move_history;
+    let charges = history
+      .iter()
+      .rev()
+      .take_while(|&&intent| intent == Charging.id())
+      .count();
+    if (context.ascension() >= 17 && context.last_intent::<Self::Intent>() == Some(UltimateBlast))
+      || charges >= 3
+      || (charges == 2 && history.len() == 2)
     {
       context.always(UltimateBlast);
     } else {
@@ -637,6 +690,9 @@ impl MonsterBehavior for Sentry {
       }
     }
   }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    power_monster(runner, monster_index, PowerId::Artifact, 1);
+  }
   fn intent_effects(context: &mut impl IntentEffectsContext) {
     use SentryIntent::*;
     match context.intent::<Self::Intent>() {
@@ -722,6 +778,10 @@ impl MonsterBehavior for Lagavulin {
       });
     }
   }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    // it starts asleep
+    power_monster(runner, monster_index, PowerId::Metallicize, 8);
+  }
   fn intent_effects(context: &mut impl IntentEffectsContext) {
     use LagavulinIntent::*;
     match context.intent::<Self::Intent>() {
@@ -755,6 +815,23 @@ impl MonsterBehavior for TheGuardian {
       context.always(Whirlwind);
     }
   }
+  fn pre_battle(runner: &mut impl Runner, monster_index: usize) {
+    let ascension = runner.state().monsters[monster_index].ascension;
+    let threshold = if ascension >= 19 {
+      40
+    } else if ascension >= 9 {
+      35
+    } else {
+      30
+    };
+    power_monster(runner, monster_index, PowerId::ModeShift, threshold);
+    power_monster(
+      runner,
+      monster_index,
+      PowerId::ModeShiftDamageThreshold,
+      threshold,
+    );
+  }
   fn intent_effects(context: &mut impl IntentEffectsContext) {
     use TheGuardianIntent::*;
     match context.intent::<Self::Intent>() {
@@ -858,9 +935,19 @@ impl MonsterBehavior for Hexaghost {
       }
       Sear => {
         context.attack(6);
-        let upgraded = context.state().turn_number >= 8;
-        // TODO: apply upgrade
-        context.discard_status(CardId::Burn, context.with_ascension(Ascension(19), 2, 1));
+        // Burns are upgraded for the rest of the fight once it has used Inferno
+        let mut burn = SingleCard::create(CardId::Burn);
+        if context
+          .monster()
+          .move_history
+          .iter()
+          .any(|&intent| intent == Inferno.id())
+        {
+          burn.upgrade();
+        }
+        for _ in 0..context.with_ascension(Ascension(19), 2, 1) {
+          context.action(DiscardNewCard(burn.clone()));
+        }
       }
       Inflame => {
         context.power_self(
@@ -873,9 +960,14 @@ impl MonsterBehavior for Hexaghost {
         for _ in 0..6 {
           context.attack(context.with_ascension(Ascension(4), 3, 2));
         }
+        context.action(UpgradeCardsAction::new(
+          UpgradeCardsFilter::Id(CardId::Burn),
+          &[CardPile::Hand, CardPile::DrawPile, CardPile::DiscardPile],
+        ));
+        let mut burn = SingleCard::create(CardId::Burn);
+        burn.upgrade();
         for _ in 0..3 {
-          context.discard_status(CardId::Burn, 3);
-          // TODO: upgrade all burns
+          context.action(DiscardNewCard(burn.clone()));
         }
       }
     }
@@ -921,3 +1013,58 @@ impl MonsterBehavior for SlimeBoss {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::simulation::tests::*;
+
+  fn burns(state: &CombatState) -> Vec<&SingleCard> {
+    state
+      .hand
+      .iter()
+      .chain(state.draw_pile_cards())
+      .chain(&state.discard_pile)
+      .filter(|card| card.card_info.id == CardId::Burn)
+      .collect()
+  }
+
+  fn hexaghost(history: &[HexaghostIntent], turn_number: i32) -> CombatState {
+    let mut state = combat(&[], &[MonsterId::Hexaghost]);
+    state.monsters[0].move_history = history.iter().map(|intent| intent.id()).collect();
+    state.turn_number = turn_number;
+    state.draw_pile = cards(&[CardId::DefendR; 5]);
+    state
+  }
+
+  #[test]
+  fn inferno_upgrades_every_burn() {
+    let mut state = hexaghost(&[HexaghostIntent::Inferno], 9);
+    state.hand.push(SingleCard::create(CardId::Burn));
+    state.draw_pile.push(SingleCard::create(CardId::Burn));
+    state.discard_pile = cards(&[CardId::Burn]);
+    choose(&mut state, EndTurn);
+    assert_eq!(state.player.creature.hitpoints, 70 - 2 - 6 * 2);
+    let burns = burns(&state);
+    assert_eq!(burns.len(), 3 + 3);
+    assert!(burns.iter().all(|burn| burn.upgrades == 1));
+  }
+
+  #[test]
+  fn sear_burns_are_upgraded_after_the_first_inferno() {
+    use HexaghostIntent::*;
+    let mut state = hexaghost(&[Sear], 12);
+    choose(&mut state, EndTurn);
+    assert_eq!(
+      state.discard_pile.as_slice(),
+      cards(&[CardId::Burn]).as_slice()
+    );
+
+    // it doesn't matter which turn the imported combat started on
+    let mut state = hexaghost(&[Inferno, Sear, Tackle, Sear], 3);
+    choose(&mut state, EndTurn);
+    let burns = burns(&state);
+    assert_eq!(burns.len(), 1);
+    assert_eq!(burns[0].upgrades, 1);
+  }
+}
@@ -0,0 +1,350 @@
+use super::*;
+use crate::communication_mod_state as communication;
+
+/// The kinds of intent the game shows, as CommunicationMod names them.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
+pub enum ObservedIntentKind {
+  Attack,
+  AttackBuff,
+  AttackDebuff,
+  AttackDefend,
+  Buff,
+  Debuff,
+  StrongDebuff,
+  Defend,
+  DefendDebuff,
+  DefendBuff,
+  Escape,
+  Magic,
+  Sleep,
+  Stun,
+  None,
+  Unknown,
+}
+
+impl ObservedIntentKind {
+  pub fn from_communication_mod(intent: &str) -> Option<ObservedIntentKind> {
+    use ObservedIntentKind::*;
+    Some(match intent {
+      "ATTACK" => Attack,
+      "ATTACK_BUFF" => AttackBuff,
+      "ATTACK_DEBUFF" => AttackDebuff,
+      "ATTACK_DEFEND" => AttackDefend,
+      "BUFF" => Buff,
+      "DEBUFF" => Debuff,
+      "STRONG_DEBUFF" => StrongDebuff,
+      "DEFEND" => Defend,
+      "DEFEND_DEBUFF" => DefendDebuff,
+      "DEFEND_BUFF" => DefendBuff,
+      "ESCAPE" => Escape,
+      "MAGIC" => Magic,
+      "SLEEP" => Sleep,
+      "STUN" => Stun,
+      "NONE" => None,
+      "UNKNOWN" | "DEBUG" => Unknown,
+      _ => return Option::None,
+    })
+  }
+  pub fn attacks(self) -> bool {
+    use ObservedIntentKind::*;
+    matches!(self, Attack | AttackBuff | AttackDebuff | AttackDefend)
+  }
+  pub fn buffs(self) -> bool {
+    use ObservedIntentKind::*;
+    matches!(self, AttackBuff | Buff | DefendBuff)
+  }
+  pub fn debuffs(self) -> bool {
+    use ObservedIntentKind::*;
+    matches!(self, AttackDebuff | Debuff | StrongDebuff | DefendDebuff)
+  }
+  pub fn defends(self) -> bool {
+    use ObservedIntentKind::*;
+    matches!(self, AttackDefend | Defend | DefendDebuff | DefendBuff)
+  }
+}
+
+/// Everything CommunicationMod tells us about a monster's next move.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub struct ObservedIntent {
+  pub kind: ObservedIntentKind,
+  /// Before the monster's powers; CommunicationMod reports -1 for moves that don't attack.
+  pub base_damage: i32,
+  pub hits: i32,
+}
+
+impl ObservedIntent {
+  pub fn from_communication_mod(monster: &communication::Monster) -> ObservedIntent {
+    ObservedIntent {
+      kind: ObservedIntentKind::from_communication_mod(&monster.intent)
+        .unwrap_or(ObservedIntentKind::Unknown),
+      base_damage: monster.move_base_damage,
+      hits: monster.move_hits.max(1),
+    }
+  }
+  /// As (base damage, hits).
+  pub fn attack(&self) -> Option<(i32, i32)> {
+    if self.kind.attacks() && self.base_damage >= 0 {
+      Some((self.base_damage, self.hits))
+    } else {
+      None
+    }
+  }
+}
+
+/// What we guess a monster we don't simulate will do once we're past the move we observed. It's
+/// meant to be pessimistic, so that plans which survive it will probably survive the real thing.
+/// Files for `--unknown-monster-model` can leave out fields, which keep their defaults.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct UnknownMonsterModel {
+  /// For future turns, if we've never seen it attack. Otherwise it repeats its strongest attack.
+  pub attack_damage: i32,
+  pub attack_hits: i32,
+  /// Strength it gains on each turn after the observed one.
+  pub strength_per_turn: i32,
+  /// Strength it gains when we see it buff.
+  pub buff_strength: i32,
+  /// Weak and Vulnerable it applies to the player when we see it debuff, twice this for strong
+  /// debuffs.
+  pub debuff_amount: i32,
+  /// Block it gains when we see it defend.
+  pub defend_block: i32,
+}
+
+impl Default for UnknownMonsterModel {
+  fn default() -> Self {
+    UnknownMonsterModel {
+      attack_damage: 12,
+      attack_hits: 1,
+      strength_per_turn: 1,
+      buff_strength: 3,
+      debuff_amount: 1,
+      defend_block: 12,
+    }
+  }
+}
+
+/// What `UnknownMonster` works from.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub struct UnknownMonsterInfo {
+  pub observed: ObservedIntent,
+  /// As (base damage, hits), including from earlier observations of the same monster.
+  pub strongest_attack: Option<(i32, i32)>,
+  pub model: UnknownMonsterModel,
+}
+
+impl UnknownMonsterInfo {
+  pub fn new(observed: ObservedIntent, model: UnknownMonsterModel) -> UnknownMonsterInfo {
+    UnknownMonsterInfo {
+      observed,
+      strongest_attack: observed.attack(),
+      model,
+    }
+  }
+  /// Carries over what we learned from an earlier observation of the same monster.
+  pub fn remember(&mut self, earlier: &UnknownMonsterInfo) {
+    let total = |(damage, hits): (i32, i32)| damage * hits;
+    self.strongest_attack = match (self.strongest_attack, earlier.strongest_attack) {
+      (Some(current), Some(earlier)) if total(earlier) > total(current) => Some(earlier),
+      (current, earlier) => current.or(earlier),
+    };
+  }
+}
+
+/// Not every move of a monster we don't simulate gets a number we recognize, so all of them are
+/// `Observed`, and every move after that is `Predicted`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Enum)]
+pub enum UnknownMonsterIntent {
+  Observed,
+  Predicted,
+}
+impl Intent for UnknownMonsterIntent {
+  fn from_communication_mod(_intent_id: i32) -> Option<Self> {
+    Some(UnknownMonsterIntent::Observed)
+  }
+}
+impl From<UnknownMonsterIntent> for IntentId {
+  fn from(intent: UnknownMonsterIntent) -> IntentId {
+    intent.id()
+  }
+}
+impl From<UnknownMonsterIntent> for Distribution<IntentId> {
+  fn from(value: UnknownMonsterIntent) -> Distribution<IntentId> {
+    Distribution::from(value.id())
+  }
+}
+
+/// Stands in for monsters we don't simulate, such as modded ones. It makes the move
+/// CommunicationMod showed us as closely as the intent describes it, then follows
+/// `UnknownMonsterModel`.
+impl MonsterBehavior for UnknownMonster {
+  type Intent = UnknownMonsterIntent;
+  fn make_intent_distribution(context: &mut IntentChoiceContext) {
+    context.always(UnknownMonsterIntent::Predicted);
+  }
+  fn intent_effects(context: &mut impl IntentEffectsContext) {
+    use UnknownMonsterIntent::*;
+    let info = match context.monster().unknown_monster {
+      Some(info) => info,
+      // only happens if someone made one by hand; it does nothing
+      None => return,
+    };
+    let model = info.model;
+    match context.intent::<Self::Intent>() {
+      Observed => {
+        let observed = info.observed;
+        if let Some((damage, hits)) = observed.attack() {
+          for _ in 0..hits {
+            context.attack(damage);
+          }
+        }
+        if observed.kind.defends() {
+          context.block(model.defend_block);
+        }
+        if observed.kind.buffs() {
+          context.power_self(PowerId::Strength, model.buff_strength);
+        }
+        if observed.kind.debuffs() {
+          let amount = if observed.kind == ObservedIntentKind::StrongDebuff {
+            model.debuff_amount * 2
+          } else {
+            model.debuff_amount
+          };
+          context.power_player(PowerId::Weak, amount);
+          context.power_player(PowerId::Vulnerable, amount);
+        }
+        if observed.kind == ObservedIntentKind::Escape {
+          context.action(EscapeAction(context.monster_index()));
+        }
+      }
+      Predicted => {
+        let (damage, hits) = info
+          .strongest_attack
+          .unwrap_or((model.attack_damage, model.attack_hits));
+        for _ in 0..hits {
+          context.attack(damage);
+        }
+        if model.strength_per_turn != 0 {
+          context.power_self(PowerId::Strength, model.strength_per_turn);
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::simulation::tests::*;
+
+  fn unknown_monster(kind: ObservedIntentKind, base_damage: i32, hits: i32) -> CombatState {
+    let mut state = combat(&[], &[MonsterId::UnknownMonster]);
+    let observed = ObservedIntent {
+      kind,
+      base_damage,
+      hits,
+    };
+    let monster = &mut state.monsters[0];
+    monster.unknown_monster = Some(UnknownMonsterInfo::new(observed, Default::default()));
+    monster.move_history = vec![UnknownMonsterIntent::Observed.id()];
+    state
+  }
+
+  #[test]
+  fn the_observed_move_is_replayed() {
+    let mut state = unknown_monster(ObservedIntentKind::AttackBuff, 5, 2);
+    assert_eq!(state.total_monster_attack_intent_damage(), 10);
+    choose(&mut state, EndTurn);
+    assert_eq!(state.player.creature.hitpoints, 70 - 10);
+    assert_eq!(
+      state.monsters[0].creature.power_amount(PowerId::Strength),
+      3
+    );
+    assert_eq!(
+      state.monsters[0].move_history.last(),
+      Some(&UnknownMonsterIntent::Predicted.id())
+    );
+  }
+
+  #[test]
+  fn observed_debuffs_are_weak_and_vulnerable() {
+    let mut state = unknown_monster(ObservedIntentKind::StrongDebuff, -1, 0);
+    assert_eq!(state.total_monster_attack_intent_damage(), 0);
+    choose(&mut state, EndTurn);
+    assert_eq!(state.player.creature.hitpoints, 70);
+    assert!(state.player.creature.has_power(PowerId::Weak));
+    assert!(state.player.creature.has_power(PowerId::Vulnerable));
+  }
+
+  #[test]
+  fn later_moves_repeat_the_strongest_attack_and_grow() {
+    let mut state = unknown_monster(ObservedIntentKind::Attack, 5, 2);
+    state.monsters[0].move_history = vec![UnknownMonsterIntent::Predicted.id()];
+    choose(&mut state, EndTurn);
+    assert_eq!(state.player.creature.hitpoints, 70 - 10);
+    assert_eq!(
+      state.monsters[0].creature.power_amount(PowerId::Strength),
+      1
+    );
+    assert_eq!(state.total_monster_attack_intent_damage(), 6 * 2);
+  }
+
+  #[test]
+  fn monsters_that_never_attacked_use_the_model() {
+    let mut state = unknown_monster(ObservedIntentKind::Defend, -1, 0);
+    choose(&mut state, EndTurn);
+    assert_eq!(state.player.creature.hitpoints, 70);
+    assert_eq!(state.monsters[0].creature.block, 12);
+    assert_eq!(state.total_monster_attack_intent_damage(), 12);
+  }
+
+  #[test]
+  fn the_strongest_attack_is_remembered() {
+    let observed = |kind, base_damage, hits| ObservedIntent {
+      kind,
+      base_damage,
+      hits,
+    };
+    let model = UnknownMonsterModel::default();
+    let earlier = UnknownMonsterInfo::new(observed(ObservedIntentKind::Attack, 6, 3), model);
+    let mut weaker = UnknownMonsterInfo::new(observed(ObservedIntentKind::Attack, 15, 1), model);
+    weaker.remember(&earlier);
+    assert_eq!(weaker.strongest_attack, Some((6, 3)));
+    let mut buffing = UnknownMonsterInfo::new(observed(ObservedIntentKind::Buff, -1, 0), model);
+    buffing.remember(&earlier);
+    assert_eq!(buffing.strongest_attack, Some((6, 3)));
+  }
+
+  #[test]
+  fn intents_are_imported() {
+    use ObservedIntentKind::*;
+    assert_eq!(
+      ObservedIntentKind::from_communication_mod("ATTACK_DEFEND"),
+      Some(AttackDefend)
+    );
+    assert_eq!(
+      ObservedIntentKind::from_communication_mod("DEBUG"),
+      Some(Unknown)
+    );
+    assert_eq!(
+      ObservedIntentKind::from_communication_mod("DANCE"),
+      Option::None
+    );
+    assert!(AttackDefend.attacks() && AttackDefend.defends());
+    assert!(StrongDebuff.debuffs() && !StrongDebuff.attacks());
+  }
+
+  #[test]
+  fn model_files_can_leave_out_fields() {
+    let model: UnknownMonsterModel =
+      serde_json::from_str(r#"{"attack_damage": 20, "attack_hits": 2}"#).unwrap();
+    assert_eq!(
+      model,
+      UnknownMonsterModel {
+        attack_damage: 20,
+        attack_hits: 2,
+        ..Default::default()
+      }
+    );
+  }
+}
@@ -0,0 +1,261 @@
+use serde::{Deserialize, Serialize};
+use std::convert::From;
+use std::fmt::{self, Display, Formatter};
+
+use crate::simulation_state::*;
+
+pub const MAX_ORB_SLOTS: usize = 10;
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
+pub enum OrbType {
+  Lightning,
+  Frost,
+  Dark,
+  Plasma,
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
+pub struct Orb {
+  pub orb_type: OrbType,
+  /// Only meaningful for Dark orbs, which store up damage every turn. The other orbs always
+  /// evoke for their base amount plus Focus.
+  pub evoke_amount: i32,
+}
+
+impl OrbType {
+  pub fn base_passive_amount(self) -> i32 {
+    match self {
+      OrbType::Lightning => 3,
+      OrbType::Frost => 2,
+      OrbType::Dark => 6,
+      OrbType::Plasma => 1,
+    }
+  }
+  pub fn base_evoke_amount(self) -> i32 {
+    match self {
+      OrbType::Lightning => 8,
+      OrbType::Frost => 5,
+      OrbType::Dark => 6,
+      OrbType::Plasma => 2,
+    }
+  }
+  pub fn affected_by_focus(self) -> bool {
+    self != OrbType::Plasma
+  }
+  pub fn from_communication_mod(id: &str) -> Option<OrbType> {
+    match id {
+      "Lightning" => Some(OrbType::Lightning),
+      "Frost" => Some(OrbType::Frost),
+      "Dark" => Some(OrbType::Dark),
+      "Plasma" => Some(OrbType::Plasma),
+      _ => None,
+    }
+  }
+}
+
+impl Orb {
+  pub fn new(orb_type: OrbType) -> Orb {
+    Orb {
+      orb_type,
+      evoke_amount: orb_type.base_evoke_amount(),
+    }
+  }
+}
+
+impl From<OrbType> for Orb {
+  fn from(orb_type: OrbType) -> Orb {
+    Orb::new(orb_type)
+  }
+}
+
+impl Player {
+  pub fn focus(&self) -> i32 {
+    self.creature.power_amount(PowerId::Focus)
+  }
+  pub fn orb_passive_amount(&self, orb: &Orb) -> i32 {
+    let base = orb.orb_type.base_passive_amount();
+    if orb.orb_type.affected_by_focus() {
+      std::cmp::max(0, base + self.focus())
+    } else {
+      base
+    }
+  }
+  pub fn orb_evoke_amount(&self, orb: &Orb) -> i32 {
+    match orb.orb_type {
+      OrbType::Dark => orb.evoke_amount,
+      OrbType::Plasma => orb.orb_type.base_evoke_amount(),
+      _ => std::cmp::max(0, orb.orb_type.base_evoke_amount() + self.focus()),
+    }
+  }
+  pub fn has_empty_orb_slot(&self) -> bool {
+    self.orbs.len() < self.orb_slots
+  }
+}
+
+/// Orb damage ignores the player's powers, but Lock-On increases it.
+pub fn orb_damage_info(
+  state: &CombatState,
+  target: CreatureIndex,
+  amount: i32,
+) -> DamageInfoAllPowers {
+  let mut info = DamageInfoNoPowers::new(Some(CreatureIndex::Player), amount, DamageType::Thorns)
+    .ignore_powers();
+  if state.get_creature(target).has_power(PowerId::LockOn) {
+    info.output = (info.output as f64 * 1.5) as i32;
+  }
+  info
+}
+
+/// The passive effect of the orb at `orb_index`, which normally happens at the end of the turn (or
+/// for Plasma, the start of the turn). Dark orbs store up their damage right away; the other orbs
+/// return the action for their effect, for the caller to queue.
+pub fn orb_passive_effect(state: &mut CombatState, orb_index: usize) -> Option<DynAction> {
+  let player = &mut state.player;
+  let amount = player.orb_passive_amount(&player.orbs[orb_index]);
+  let orb = &mut player.orbs[orb_index];
+  match orb.orb_type {
+    OrbType::Lightning => Some(LightningOrbDamageAction { amount }.into()),
+    OrbType::Frost => Some(
+      GainBlockAction {
+        creature_index: CreatureIndex::Player,
+        amount,
+      }
+      .into(),
+    ),
+    OrbType::Dark => {
+      orb.evoke_amount += amount;
+      None
+    }
+    OrbType::Plasma => Some(GainEnergyAction(amount).into()),
+  }
+}
+
+pub fn orb_evoke_effect(state: &CombatState, orb: &Orb) -> DynAction {
+  let amount = state.player.orb_evoke_amount(orb);
+  match orb.orb_type {
+    OrbType::Lightning => LightningOrbDamageAction { amount }.into(),
+    OrbType::Frost => GainBlockAction {
+      creature_index: CreatureIndex::Player,
+      amount,
+    }
+    .into(),
+    OrbType::Dark => DarkOrbEvokeAction { amount }.into(),
+    OrbType::Plasma => GainEnergyAction(amount).into(),
+  }
+}
+
+impl Display for Orb {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{:?}", self.orb_type)?;
+    if self.orb_type == OrbType::Dark {
+      write!(f, "{}", self.evoke_amount)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::simulation::tests::*;
+  use crate::simulation_state::monsters::exordium::CultistIntent;
+  use crate::simulation_state::monsters::Intent;
+
+  /// A Defect with 3 orb slots, against a Cultist that spends the monster turn on Incantation.
+  fn defect(hand: &[CardId]) -> CombatState {
+    let mut state = combat(hand, &[MonsterId::Cultist]);
+    state.monsters[0].move_history = vec![CultistIntent::Incantation.id()];
+    state.player.orb_slots = 3;
+    state
+  }
+
+  fn focus(state: &mut CombatState, amount: i32) {
+    run(
+      state,
+      ApplyPowerAction {
+        source: CreatureIndex::Player,
+        target: CreatureIndex::Player,
+        power_id: PowerId::Focus,
+        amount,
+      },
+    );
+  }
+
+  #[test]
+  fn focus_changes_every_orb_but_plasma() {
+    let mut state = defect(&[]);
+    focus(&mut state, 2);
+    let player = &state.player;
+    let amounts = |orb_type| {
+      let orb = Orb::new(orb_type);
+      (
+        player.orb_passive_amount(&orb),
+        player.orb_evoke_amount(&orb),
+      )
+    };
+    assert_eq!(amounts(OrbType::Lightning), (5, 10));
+    assert_eq!(amounts(OrbType::Frost), (4, 7));
+    // Dark's evoke amount is what it has stored up, which Focus only adds to over time
+    assert_eq!(amounts(OrbType::Dark), (8, 6));
+    assert_eq!(amounts(OrbType::Plasma), (1, 2));
+
+    focus(&mut state, -5);
+    assert_eq!(
+      state.player.orb_passive_amount(&Orb::new(OrbType::Frost)),
+      0
+    );
+  }
+
+  #[test]
+  fn channeling_into_full_slots_evokes_the_leftmost_orb() {
+    let mut state = defect(&[CardId::Zap]);
+    state.player.orbs = [OrbType::Frost, OrbType::Dark, OrbType::Dark]
+      .iter()
+      .map(|&orb_type| Orb::new(orb_type))
+      .collect();
+    play(&mut state, CardId::Zap, 0);
+    assert_eq!(state.player.creature.block, 5);
+    let orbs: Vec<OrbType> = state.player.orbs.iter().map(|orb| orb.orb_type).collect();
+    assert_eq!(orbs, [OrbType::Dark, OrbType::Dark, OrbType::Lightning]);
+  }
+
+  #[test]
+  fn dualcast_evokes_the_same_orb_twice() {
+    let mut state = defect(&[CardId::Dualcast]);
+    state.player.orbs.push(Orb::new(OrbType::Lightning));
+    play(&mut state, CardId::Dualcast, 0);
+    assert_eq!(state.monsters[0].creature.hitpoints, 40 - 8 - 8);
+    assert!(state.player.orbs.is_empty());
+  }
+
+  #[test]
+  fn orbs_trigger_at_the_end_of_the_turn() {
+    let mut state = defect(&[]);
+    state.player.orbs = [OrbType::Frost, OrbType::Lightning, OrbType::Dark]
+      .iter()
+      .map(|&orb_type| Orb::new(orb_type))
+      .collect();
+    choose(&mut state, EndTurn);
+    assert_eq!(state.monsters[0].creature.hitpoints, 40 - 3);
+    assert_eq!(state.player.orbs[2].evoke_amount, 6 + 6);
+    // the Frost block was gained at the end of the turn, and is gone at the start of the next
+    assert_eq!(state.turn_number, 2);
+    assert_eq!(state.player.creature.block, 0);
+  }
+
+  #[test]
+  fn orbs_are_imported() {
+    for &orb_type in &[
+      OrbType::Lightning,
+      OrbType::Frost,
+      OrbType::Dark,
+      OrbType::Plasma,
+    ] {
+      assert_eq!(
+        OrbType::from_communication_mod(&format!("{:?}", orb_type)),
+        Some(orb_type)
+      );
+    }
+    assert_eq!(OrbType::from_communication_mod("Empty"), None);
+  }
+}
@@ -3,11 +3,13 @@ use serde::{Deserialize, Serialize};
 use std::convert::From;
 
 use crate::simulation::*;
-use crate::simulation_state::monsters::city::ByrdIntent;
+use crate::simulation_state::fidelity::Fidelity::Missing;
+use crate::simulation_state::monsters::city::{ByrdIntent, ShelledParasiteIntent};
 use crate::simulation_state::monsters::exordium::TheGuardianIntent;
 use crate::simulation_state::monsters::Intent;
+use crate::simulation_state::orbs::orb_passive_effect;
 use crate::simulation_state::*;
-use PowerType::{Buff, Debuff, Relic};
+use PowerType::{Buff, Debuff};
 
 pub struct PowerHookContext<'a, R: Runner> {
   pub runner: &'a mut R,
@@ -147,10 +149,6 @@ impl<'a, R: Runner> PowerHookContext<'a, R> {
 
 #[allow(unused)]
 pub trait PowerBehavior {
-  fn inherent_energy(&self) -> i32 {
-    0
-  }
-
   fn priority(&self) -> i32 {
     5
   }
@@ -203,13 +201,28 @@ pub trait PowerBehavior {
   fn on_attack(
     &self,
     context: &mut PowerHookContext<impl Runner>,
+    info: &DamageInfoAllPowers,
     damage: i32,
     target: CreatureIndex,
   ) {
   }
-  fn on_attacked_to_change_damage(&self, context: &PowerNumericHookContext, damage: i32) -> i32 {
+  /// Caps applied to incoming damage before it's reduced by block (Intangible).
+  fn modify_damage_before_block(
+    &self,
+    context: &PowerNumericHookContext,
+    info: &DamageInfoAllPowers,
+    damage: i32,
+  ) -> i32 {
     damage
   }
+  /// The final stage of damage modification, after block (Buffer, Invincible).
+  fn on_attacked_to_change_damage(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    info: &DamageInfoAllPowers,
+    damage: &mut i32,
+  ) {
+  }
   fn on_inflict_damage(&self, context: &mut PowerHookContext<impl Runner>) {}
   fn on_card_draw(&self, context: &mut PowerHookContext<impl Runner>, card: &SingleCard) {}
   fn on_use_card(
@@ -234,12 +247,17 @@ pub trait PowerBehavior {
   fn on_heal(&self, context: &PowerNumericHookContext, amount: i32) -> i32 {
     amount
   }
+  /// Called before the player leaves `stance` for a different one.
+  fn on_exit_stance(&self, context: &mut PowerHookContext<impl Runner>, stance: Stance) {}
+  /// Called after the player has entered a different stance.
+  fn on_enter_stance(&self, context: &mut PowerHookContext<impl Runner>, stance: Stance) {}
+  fn on_scry(&self, context: &mut PowerHookContext<impl Runner>) {}
 }
 
 //pub fn
 
 macro_rules! powers {
-  ($([$id: expr, $Variant: ident, $power_type: expr],)*) => {
+  ($([$id: expr, $Variant: ident, $power_type: expr $(, $fidelity: expr)?],)*) => {
     #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Enum, Debug)]
     pub enum PowerId {
       $($Variant,)*
@@ -251,28 +269,30 @@ macro_rules! powers {
 
     impl From<& str> for PowerId {
       fn from (source: & str)->PowerId {
-        match source {
-          $($id => PowerId::$Variant,)*
-          _ => PowerId::Unknown,
-        }
+        PowerId::from_communication_mod(source).unwrap_or(PowerId::Unknown)
       }
     }
 
     impl PowerId {
+      pub fn from_communication_mod(source: & str)->Option<PowerId> {
+        match source {
+          $($id => Some(PowerId::$Variant),)*
+          _ => None,
+        }
+      }
       pub fn power_type(&self)->PowerType {
         match self {
           $(PowerId::$Variant => $power_type,)*
         }
       }
-    }
-
-    impl PowerBehavior for PowerId {
-      fn inherent_energy(&self) -> i32 {
+      pub fn fidelity(self)->Fidelity {
         match self {
-          $(PowerId::$Variant => $Variant.inherent_energy(),)*
+          $(PowerId::$Variant => None $(.or(Some($fidelity)))? .unwrap_or(Fidelity::Complete),)*
         }
       }
+    }
 
+    impl PowerBehavior for PowerId {
       fn priority(&self) -> i32 {
         match self {
           $(PowerId::$Variant => $Variant.priority(),)*
@@ -357,16 +377,32 @@ macro_rules! powers {
       fn on_attack(
         &self,
         context: &mut PowerHookContext<impl Runner>,
+        info: &DamageInfoAllPowers,
         damage: i32,
         target: CreatureIndex,
       ) {
         match self {
-          $(PowerId::$Variant => $Variant.on_attack(context, damage, target),)*
+          $(PowerId::$Variant => $Variant.on_attack(context, info, damage, target),)*
+        }
+      }
+      fn modify_damage_before_block(
+        &self,
+        context: &PowerNumericHookContext,
+        info: &DamageInfoAllPowers,
+        damage: i32,
+      ) -> i32 {
+        match self {
+          $(PowerId::$Variant => $Variant.modify_damage_before_block(context, info, damage),)*
         }
       }
-      fn on_attacked_to_change_damage(&self, context: &PowerNumericHookContext, damage: i32) -> i32 {
+      fn on_attacked_to_change_damage(
+        &self,
+        context: &mut PowerHookContext<impl Runner>,
+        info: &DamageInfoAllPowers,
+        damage: &mut i32,
+      ) {
         match self {
-          $(PowerId::$Variant => $Variant.on_attacked_to_change_damage(context, damage),)*
+          $(PowerId::$Variant => $Variant.on_attacked_to_change_damage(context, info, damage),)*
         }
       }
       fn on_inflict_damage(&self, context: &mut PowerHookContext<impl Runner>) {
@@ -434,6 +470,21 @@ macro_rules! powers {
           $(PowerId::$Variant => $Variant.on_after_card_played(context, card),)*
         }
       }
+      fn on_exit_stance(&self, context: &mut PowerHookContext<impl Runner>, stance: Stance) {
+        match self {
+          $(PowerId::$Variant => $Variant.on_exit_stance(context, stance),)*
+        }
+      }
+      fn on_enter_stance(&self, context: &mut PowerHookContext<impl Runner>, stance: Stance) {
+        match self {
+          $(PowerId::$Variant => $Variant.on_enter_stance(context, stance),)*
+        }
+      }
+      fn on_scry(&self, context: &mut PowerHookContext<impl Runner>) {
+        match self {
+          $(PowerId::$Variant => $Variant.on_scry(context),)*
+        }
+      }
     }
   }
 }
@@ -451,46 +502,114 @@ powers! {
   ["Metallicize", Metallicize, Buff],
   ["No Draw", NoDraw, Debuff],
   ["Plated Armor", PlatedArmor, Buff],
-
-  // Common relics
-  ["InkBottle", InkBottle, Relic],
-
-  // Boss relics
-  ["Busted Crown", BustedCrown, Relic],
-  ["Coffee Dripper", CoffeeDripper, Relic],
-  ["Cursed Key", CursedKey, Relic],
-  ["Ectoplasm", Ectoplasm, Relic],
-  ["Fusion Hammer", FusionHammer, Relic],
-  ["Mark of Pain", MarkOfPain, Relic],
-  ["Philosopher's Stone", PhilosophersStone, Relic],
-  ["Runic Pyramid", RunicPyramid, Relic],
-  ["SacredBark", SacredBark, Relic],
-  ["Sozu", Sozu, Relic],
-
-  // Event relics
-  ["Necronomicon", Necronomicon, Relic],
+  ["Intangible", Intangible, Buff],
+  ["IntangiblePlayer", IntangiblePlayer, Buff],
+  ["Buffer", Buffer, Buff],
+  ["Poison", Poison, Debuff],
+  ["Energized", Energized, Buff],
+  ["Draw Card", DrawCardNextTurn, Buff],
+  ["Next Turn Block", NextTurnBlock, Buff],
+  ["Focus", Focus, Buff],
+  ["Flex", LoseStrength, Debuff],
+  ["DexLoss", LoseDexterity, Debuff],
+  ["Minion", Minion, Buff],
 
   // Relic powers
   ["Pen Nib", PenNib, Buff],
 
+  // Potion powers
+  ["Regeneration", Regeneration, Buff],
+  ["DuplicationPower", Duplication, Buff],
+
   // Ironclad uncommon card powers
-  ["Combust", Combust, Buff],
-  ["Corruption", Corruption, Buff],
+  ["Combust", Combust, Buff, Missing("does nothing")],
+  ["Corruption", Corruption, Buff, Missing("does nothing")],
   ["Evolve", Evolve, Buff],
   ["Feel No Pain", FeelNoPain, Buff],
-  ["Fire Breathing", FireBreathing, Buff],
+  ["Fire Breathing", FireBreathing, Buff, Missing("does nothing")],
   ["Flame Barrier", FlameBarrier, Buff],
   ["Rage", Rage, Buff],
-  ["Rupture", Rupture, Buff],
+  ["Rupture", Rupture, Buff, Missing("does nothing")],
 
   // Ironclad rare card powers
   ["Barricade", Barricade, Buff],
   ["Berserk", Berserk, Buff],
-  ["Brutality", Brutality, Buff],
+  ["Brutality", Brutality, Buff, Missing("does nothing")],
   ["Dark Embrace", DarkEmbrace, Buff],
   ["Demon Form", DemonForm, Buff],
-  ["Double Tap", DoubleTap, Buff],
-  ["Juggernaut", Juggernaut, Buff],
+  ["Double Tap", DoubleTap, Buff, Missing("does nothing")],
+  ["Juggernaut", Juggernaut, Buff, Missing("does nothing")],
+
+  // Silent common card powers
+  ["Shackled", Shackled, Debuff],
+
+  // Silent uncommon card powers
+  ["Accuracy", Accuracy, Buff],
+  ["Blur", Blur, Buff],
+  ["Choked", Choked, Debuff],
+  ["Infinite Blades", InfiniteBlades, Buff],
+  ["Noxious Fumes", NoxiousFumes, Buff],
+  ["Retain Cards", RetainCards, Buff],
+
+  // Silent rare card powers
+  ["Thousand Cuts", ThousandCuts, Buff],
+  ["After Image", AfterImage, Buff],
+  ["Burst", Burst, Buff],
+  ["CorpseExplosionPower", CorpseExplosion, Debuff],
+  ["Envenom", Envenom, Buff],
+  ["Phantasmal", Phantasmal, Buff],
+  ["Double Damage", DoubleDamage, Buff],
+  ["Tools Of The Trade", ToolsOfTheTrade, Buff],
+  ["Wraith Form v2", WraithForm, Buff],
+
+  // Defect common card powers
+  ["Rebound", Rebound, Buff],
+
+  // Defect uncommon card powers
+  ["Lockon", LockOn, Debuff],
+  ["Equilibrium", Equilibrium, Buff],
+  ["Heatsink", Heatsink, Buff],
+  ["Hello", HelloWorld, Buff, Missing("no random cards")],
+  ["Loop", Loop, Buff],
+  ["Repair", Repair, Buff],
+  ["StaticDischarge", StaticDischarge, Buff],
+  ["Storm", Storm, Buff],
+
+  // Defect rare card powers
+  ["Amplify", Amplify, Buff],
+  ["Bias", BiasedCognition, Debuff],
+  ["Creative AI", CreativeAI, Buff, Missing("no random cards")],
+  ["Draw", Draw, Buff],
+  ["Echo Form", EchoForm, Buff],
+  ["Electro", Electrodynamics, Buff],
+
+  // Watcher common card powers
+  ["Mantra", Mantra, Buff],
+  ["Vigor", Vigor, Buff],
+  ["PathToVictoryPower", Mark, Debuff],
+  ["FreeAttackPower", FreeAttack, Buff],
+  ["WrathNextTurnPower", WrathNextTurn, Buff],
+
+  // Watcher uncommon card powers
+  ["BattleHymn", BattleHymn, Buff],
+  ["BlockReturnPower", BlockReturn, Debuff],
+  ["Collect", Collect, Buff],
+  ["EnergyDownPower", EnergyDown, Debuff],
+  ["WireheadingPower", Foresight, Buff],
+  ["LikeWaterPower", LikeWater, Buff],
+  ["Controlled", MentalFortress, Buff],
+  ["Nirvana", Nirvana, Buff],
+  ["Adaptation", Rushdown, Buff],
+  ["Study", Study, Buff],
+  ["WaveOfTheHandPower", WaveOfTheHand, Buff],
+
+  // Watcher rare card powers
+  ["DevaForm", DevaForm, Buff],
+  ["DevotionPower", Devotion, Buff],
+  ["EndTurnDeath", EndTurnDeath, Buff],
+  ["EstablishmentPower", Establishment, Buff],
+  ["MasterRealityPower", MasterReality, Buff, Missing("created cards aren't upgraded")],
+  ["OmegaPower", Omega, Buff],
 
   // Exordium monster powers
   ["Ritual", Ritual, Buff],
@@ -512,9 +631,39 @@ powers! {
 
   // City monster powers
   ["Flight", Flight, Buff],
-
-
-  ["Unknown", Unknown, Buff],
+  ["Stasis", Stasis, Buff],
+  ["Hex", Hex, Debuff],
+  ["Confusion", Confusion, Debuff],
+  ["Malleable", Malleable, Buff],
+
+  // City elite powers
+  ["Painful Stabs", PainfulStabs, Buff],
+
+  // Beyond monster powers
+  ["Generic Strength Up Power", GenericStrengthUp, Buff],
+  ["Explosive", Explosive, Buff],
+  ["Constricted", Constricted, Debuff],
+  ["Fading", Fading, Buff],
+  ["Shifting", Shifting, Buff],
+  ["Reactive", Reactive, Buff],
+  ["Slow", Slow, Debuff],
+  ["Regrow", Regrow, Buff],
+
+  // Beyond boss powers
+  ["Curiosity", Curiosity, Buff],
+  ["Unawakened", Unawakened, Buff],
+  ["Regenerate", RegenerateMonster, Buff],
+  ["Time Warp", TimeWarp, Buff],
+  ["Draw Reduction", DrawReduction, Debuff],
+  ["Invincible", Invincible, Buff],
+
+  // Ending powers
+  ["Surrounded", Surrounded, Buff],
+  ["BackAttack", BackAttack, Buff],
+  ["BeatOfDeath", BeatOfDeath, Buff],
+
+
+  ["Unknown", Unknown, Buff, Missing("stands in for powers we don't know")],
 }
 
 impl PowerBehavior for Vulnerable {
@@ -566,6 +715,156 @@ impl PowerBehavior for Weak {
   }
 }
 
+fn intangible_cap(damage: i32) -> i32 {
+  std::cmp::min(damage, 1)
+}
+
+impl PowerBehavior for Intangible {
+  fn at_damage_final_receive(
+    &self,
+    _context: &PowerNumericHookContext,
+    damage: f64,
+    _damage_type: DamageType,
+  ) -> f64 {
+    damage.min(1.0)
+  }
+  fn modify_damage_before_block(
+    &self,
+    _context: &PowerNumericHookContext,
+    _info: &DamageInfoAllPowers,
+    damage: i32,
+  ) -> i32 {
+    intangible_cap(damage)
+  }
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    if context.remove_just_applied() {
+      context.reduce_this_power();
+    }
+  }
+}
+
+impl PowerBehavior for IntangiblePlayer {
+  fn at_damage_final_receive(
+    &self,
+    _context: &PowerNumericHookContext,
+    damage: f64,
+    _damage_type: DamageType,
+  ) -> f64 {
+    damage.min(1.0)
+  }
+  fn modify_damage_before_block(
+    &self,
+    _context: &PowerNumericHookContext,
+    _info: &DamageInfoAllPowers,
+    damage: i32,
+  ) -> i32 {
+    intangible_cap(damage)
+  }
+  fn at_end_of_round(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.reduce_this_power();
+  }
+}
+
+impl PowerBehavior for Buffer {
+  fn on_attacked_to_change_damage(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    _info: &DamageInfoAllPowers,
+    damage: &mut i32,
+  ) {
+    if *damage > 0 {
+      *damage = 0;
+      context.reduce_this_power();
+    }
+  }
+}
+
+impl PowerBehavior for Poison {
+  fn stack_power(&self, power: &mut Power, stack_amount: i32) {
+    power.amount = std::cmp::min(power.amount + stack_amount, 9999);
+  }
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.action_bottom(DamageAction {
+      target: context.owner_index(),
+      info: DamageInfoNoPowers::new(None, context.amount(), DamageType::HitpointLoss)
+        .ignore_powers(),
+    });
+    context.reduce_this_power();
+  }
+}
+
+impl PowerBehavior for Energized {
+  fn on_energy_recharge(&self, context: &mut PowerHookContext<impl Runner>) {
+    let amount = context.amount();
+    context.state_mut().player.energy += amount;
+    context.remove_this_power();
+  }
+}
+
+impl PowerBehavior for DrawCardNextTurn {
+  fn at_start_of_turn_post_draw(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.action_bottom(DrawCards(context.amount()));
+    context.remove_this_power();
+  }
+}
+
+impl PowerBehavior for NextTurnBlock {
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.action_bottom(GainBlockAction {
+      creature_index: context.owner_index(),
+      amount: context.amount(),
+    });
+    context.remove_this_power();
+  }
+}
+
+impl PowerBehavior for LoseStrength {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.power_owner_bottom(PowerId::Strength, -context.amount());
+    context.remove_this_power();
+  }
+}
+
+impl PowerBehavior for LoseDexterity {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.power_owner_bottom(PowerId::Dexterity, -context.amount());
+    context.remove_this_power();
+  }
+}
+
+/// Marks a monster that leaves combat once the monsters that aren't minions are all gone.
+impl PowerBehavior for Minion {}
+
+impl PowerBehavior for Regeneration {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.action_bottom(HealAction {
+      creature_index: context.owner_index(),
+      amount: context.amount(),
+    });
+    context.reduce_this_power();
+  }
+}
+
+impl PowerBehavior for Duplication {
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    card: &SingleCard,
+    action: &mut UseCardAction,
+  ) {
+    if context.amount() > 0 && !action.purge_on_use {
+      let mut new_action = UseCardAction::new(card.clone(), action.target, context.state());
+      new_action.purge_on_use = true;
+      new_action.energy_on_use = action.energy_on_use;
+      context.state_mut().card_queue.push_back(new_action);
+      context.reduce_this_power();
+    }
+  }
+  fn at_end_of_round(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.remove_this_power();
+  }
+}
+
 impl PowerBehavior for Strength {
   fn stack_power(&self, power: &mut Power, stack_amount: i32) {
     power.amount += stack_amount;
@@ -634,8 +933,14 @@ impl PowerBehavior for Thorns {
 }
 
 impl PowerBehavior for Ritual {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    // on the player (Cultist Potion), it already triggers at the end of the turn it's gained
+    if context.owner_index() == CreatureIndex::Player {
+      context.power_owner_bottom(PowerId::Strength, context.amount());
+    }
+  }
   fn at_end_of_round(&self, context: &mut PowerHookContext<impl Runner>) {
-    if context.remove_just_applied() {
+    if context.owner_index() != CreatureIndex::Player && context.remove_just_applied() {
       context.power_owner_bottom(PowerId::Strength, context.amount());
     }
   }
@@ -806,115 +1111,381 @@ impl PowerBehavior for Flight {
   }
 }
 
-impl PowerBehavior for NoDraw {
-  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
-    context.remove_this_power();
+impl PowerBehavior for Stasis {
+  fn on_death(&self, context: &mut PowerHookContext<impl Runner>) {
+    if let Some(card) = context.this_power().card.clone() {
+      context.action_bottom(AddNewCardToHand(card));
+    }
   }
 }
 
-impl PowerBehavior for PlatedArmor {
-  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
-    context.action_bottom(GainBlockAction {
-      creature_index: context.owner_index(),
-      amount: context.this_power().amount,
-    });
+impl PowerBehavior for Hex {
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    card: &SingleCard,
+    _action: &mut UseCardAction,
+  ) {
+    if card.card_info.card_type != CardType::Attack {
+      for _ in 0..context.amount() {
+        context.action_bottom(ShuffleNewCardIntoDrawPile(SingleCard::create(
+          CardId::Dazed,
+        )));
+      }
+    }
+  }
+}
+
+impl PowerBehavior for Confusion {
+  fn on_card_draw(&self, context: &mut PowerHookContext<impl Runner>, card: &SingleCard) {
+    if card.cost >= 0 {
+      context.action_top(RandomizeCardCostAction(card.clone()));
+    }
   }
+}
 
+impl PowerBehavior for Malleable {
   fn on_attacked(
     &self,
     context: &mut PowerHookContext<impl Runner>,
     info: DamageInfoAllPowers,
     damage: i32,
   ) {
-    if damage > 0 && info.damage_type == DamageType::Normal {
-      context.reduce_this_power();
+    if damage > 0
+      && damage < context.owner_creature().hitpoints
+      && info.owner.is_some()
+      && info.damage_type == DamageType::Normal
+    {
+      context.action_bottom(GainBlockAction {
+        creature_index: context.owner_index(),
+        amount: context.amount(),
+      });
+      // misc is the amount it resets to at the end of the round
+      let power = context.this_power_mut();
+      if power.misc == 0 {
+        power.misc = power.amount;
+      }
+      power.amount += 1;
+    }
+  }
+
+  fn at_end_of_round(&self, context: &mut PowerHookContext<impl Runner>) {
+    let power = context.this_power_mut();
+    if power.misc > 0 {
+      power.amount = power.misc;
     }
   }
 }
 
-impl PowerBehavior for InkBottle {
-  fn on_use_card(
+impl PowerBehavior for PainfulStabs {
+  fn on_attack(
     &self,
     context: &mut PowerHookContext<impl Runner>,
-    _card: &SingleCard,
-    _action: &mut UseCardAction,
+    info: &DamageInfoAllPowers,
+    damage: i32,
+    _target: CreatureIndex,
   ) {
-    let amount = &mut context.this_power_mut().amount;
-    *amount += 1;
-    if *amount == 10 {
-      *amount = 0;
-      context.action_bottom(DrawCards(1));
+    if damage > 0 && info.damage_type != DamageType::Thorns {
+      context.action_bottom(DiscardNewCard(SingleCard::create(CardId::Wound)));
     }
   }
 }
 
-macro_rules! energy_relic {
-  () => {
-    fn inherent_energy(&self) -> i32 {
-      1
-    }
-  };
+impl PowerBehavior for GenericStrengthUp {
+  fn at_end_of_round(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.power_owner_bottom(PowerId::Strength, context.amount());
+  }
 }
 
-impl PowerBehavior for BustedCrown {
-  energy_relic! {}
-}
-impl PowerBehavior for CoffeeDripper {
-  energy_relic! {}
-}
-impl PowerBehavior for CursedKey {
-  energy_relic! {}
-}
-impl PowerBehavior for Ectoplasm {
-  energy_relic! {}
-}
-impl PowerBehavior for FusionHammer {
-  energy_relic! {}
-}
-impl PowerBehavior for MarkOfPain {
-  energy_relic! {}
-}
-impl PowerBehavior for PhilosophersStone {
-  energy_relic! {}
+impl PowerBehavior for Explosive {
+  fn during_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    if context.amount() == 1 {
+      context.action_bottom(DamageAction {
+        target: CreatureIndex::Player,
+        info: DamageInfoNoPowers::new(Some(context.owner_index()), 30, DamageType::Thorns)
+          .ignore_powers(),
+      });
+      if let CreatureIndex::Monster(index) = context.owner_index() {
+        context.action_bottom(SuicideAction(index));
+      }
+    } else {
+      context.action_bottom(ReducePowerAction {
+        target: context.owner_index(),
+        power_id: PowerId::Explosive,
+        amount: 1,
+      });
+    }
+  }
 }
-impl PowerBehavior for RunicPyramid {}
-impl PowerBehavior for SacredBark {}
-impl PowerBehavior for Sozu {
-  energy_relic! {}
+
+impl PowerBehavior for Constricted {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.action_bottom(DamageAction {
+      target: context.owner_index(),
+      info: DamageInfoNoPowers::new(None, context.amount(), DamageType::Thorns).ignore_powers(),
+    });
+  }
 }
 
-impl PowerBehavior for Necronomicon {
-  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
-    context.this_power_mut().amount = -1;
+impl PowerBehavior for Fading {
+  fn during_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    if context.amount() == 1 {
+      if let CreatureIndex::Monster(index) = context.owner_index() {
+        context.action_bottom(SuicideAction(index));
+      }
+    } else {
+      context.action_bottom(ReducePowerAction {
+        target: context.owner_index(),
+        power_id: PowerId::Fading,
+        amount: 1,
+      });
+    }
   }
-  fn on_use_card(
+}
+
+impl PowerBehavior for Shifting {
+  fn on_attacked(
     &self,
     context: &mut PowerHookContext<impl Runner>,
-    card: &SingleCard,
-    action: &mut UseCardAction,
+    _info: DamageInfoAllPowers,
+    damage: i32,
   ) {
-    if context.amount() != 0
-      && card.card_info.card_type == CardType::Attack
-      && card.cost_in_practice(context.state()) >= 2
-    {
-      context.this_power_mut().amount = 0;
-      let mut new_action = UseCardAction::new(card.clone(), action.target, context.state());
-      new_action.purge_on_use = true;
-      new_action.energy_on_use = action.energy_on_use;
-      context.state_mut().card_queue.push_back(new_action);
+    if damage > 0 {
+      context.power_owner_top(PowerId::Strength, -damage);
+      if !context.owner_creature().has_power(PowerId::Artifact) {
+        context.power_owner_top(PowerId::Shackled, damage);
+      }
     }
   }
 }
 
-impl PowerBehavior for PenNib {
-  fn priority(&self) -> i32 {
-    6
-  }
-  fn on_use_card(
+impl PowerBehavior for Reactive {
+  fn on_attacked(
     &self,
     context: &mut PowerHookContext<impl Runner>,
-    card: &SingleCard,
-    _action: &mut UseCardAction,
+    info: DamageInfoAllPowers,
+    damage: i32,
+  ) {
+    if info.owner.is_some()
+      && info.damage_type == DamageType::Normal
+      && damage > 0
+      && damage < context.owner_creature().hitpoints
+    {
+      if let CreatureIndex::Monster(index) = context.owner_index() {
+        context.action_bottom(ChooseMonsterIntent(index));
+      }
+    }
+  }
+}
+
+impl PowerBehavior for Slow {
+  fn at_damage_receive(
+    &self,
+    context: &PowerNumericHookContext,
+    damage: f64,
+    damage_type: DamageType,
+  ) -> f64 {
+    if damage_type != DamageType::Normal {
+      return damage;
+    }
+    damage * (1.0 + context.amount() as f64 * 0.1)
+  }
+  fn on_after_use_card(&self, context: &mut PowerHookContext<impl Runner>, _card: &SingleCard) {
+    context.power_owner_bottom(PowerId::Slow, 1);
+  }
+  fn at_end_of_round(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.this_power_mut().amount = 0;
+  }
+}
+
+// kill_monster leaves its owner half-dead instead
+impl PowerBehavior for Regrow {}
+
+impl PowerBehavior for Curiosity {
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    card: &SingleCard,
+    _action: &mut UseCardAction,
+  ) {
+    if card.card_info.card_type == CardType::Power {
+      context.power_owner_bottom(PowerId::Strength, context.amount());
+    }
+  }
+}
+
+// kill_monster leaves its owner half-dead instead, to come back in its second form
+impl PowerBehavior for Unawakened {}
+
+impl PowerBehavior for RegenerateMonster {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.action_bottom(HealAction {
+      creature_index: context.owner_index(),
+      amount: context.amount(),
+    });
+  }
+}
+
+impl PowerBehavior for TimeWarp {
+  fn on_after_use_card(&self, context: &mut PowerHookContext<impl Runner>, _card: &SingleCard) {
+    context.this_power_mut().amount += 1;
+    if context.amount() >= 12 {
+      context.this_power_mut().amount = 0;
+      let source = context.owner_index();
+      for index in 0..context.state().monsters.len() {
+        context.action_bottom(ApplyPowerAction {
+          source,
+          target: CreatureIndex::Monster(index),
+          power_id: PowerId::Strength,
+          amount: 2,
+        });
+      }
+      // the rest of the player's turn is skipped
+      context.action_bottom(EndTurn);
+    }
+  }
+}
+
+impl PowerBehavior for DrawReduction {
+  // the draw itself is in start_player_turn
+  fn at_end_of_round(&self, context: &mut PowerHookContext<impl Runner>) {
+    if context.remove_just_applied() {
+      context.reduce_this_power();
+    }
+  }
+}
+
+impl PowerBehavior for Invincible {
+  fn on_attacked_to_change_damage(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    _info: &DamageInfoAllPowers,
+    damage: &mut i32,
+  ) {
+    // misc is the amount it resets to at the start of each turn
+    let power = context.this_power_mut();
+    if power.misc == 0 {
+      power.misc = power.amount;
+    }
+    *damage = (*damage).min(power.amount);
+    power.amount -= *damage;
+  }
+
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    let power = context.this_power_mut();
+    if power.misc > 0 {
+      power.amount = power.misc;
+    }
+  }
+}
+
+impl PowerBehavior for Surrounded {
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    card: &SingleCard,
+    action: &mut UseCardAction,
+  ) {
+    // the player turns to face the target, which puts the other monster behind them
+    if !card.card_info.has_target {
+      return;
+    }
+    let target = action.target;
+    let monsters = &mut context.state_mut().monsters;
+    if !monsters[target].creature.has_power(PowerId::BackAttack) {
+      return;
+    }
+    for (index, monster) in monsters.iter_mut().enumerate() {
+      if index == target {
+        monster
+          .creature
+          .powers
+          .retain(|power| power.power_id != PowerId::BackAttack);
+      } else if !monster.gone {
+        monster.creature.powers.push(Power {
+          power_id: PowerId::BackAttack,
+          amount: -1,
+          ..Default::default()
+        });
+      }
+    }
+  }
+}
+
+impl PowerBehavior for BackAttack {
+  fn at_damage_give(
+    &self,
+    context: &PowerNumericHookContext,
+    damage: f64,
+    damage_type: DamageType,
+  ) -> f64 {
+    if damage_type == DamageType::Normal
+      && context.state.player.creature.has_power(PowerId::Surrounded)
+    {
+      damage * 1.5
+    } else {
+      damage
+    }
+  }
+}
+
+impl PowerBehavior for BeatOfDeath {
+  fn on_after_use_card(&self, context: &mut PowerHookContext<impl Runner>, _card: &SingleCard) {
+    context.action_bottom(DamageAction {
+      target: CreatureIndex::Player,
+      info: DamageInfoNoPowers::new(
+        Some(context.owner_index()),
+        context.amount(),
+        DamageType::Thorns,
+      )
+      .ignore_powers(),
+    });
+  }
+}
+
+impl PowerBehavior for NoDraw {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.remove_this_power();
+  }
+}
+
+impl PowerBehavior for PlatedArmor {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.action_bottom(GainBlockAction {
+      creature_index: context.owner_index(),
+      amount: context.this_power().amount,
+    });
+  }
+
+  fn on_attacked(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    info: DamageInfoAllPowers,
+    damage: i32,
+  ) {
+    if damage > 0 && info.damage_type == DamageType::Normal {
+      context.reduce_this_power();
+    }
+  }
+
+  fn on_remove(&self, context: &mut PowerHookContext<impl Runner>) {
+    if let CreatureIndex::Monster(index) = context.owner_index() {
+      if context.state().monsters[index].monster_id == MonsterId::ShelledParasite {
+        context.set_owner_intent(ShelledParasiteIntent::Stunned);
+      }
+    }
+  }
+}
+
+impl PowerBehavior for PenNib {
+  fn priority(&self) -> i32 {
+    6
+  }
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    card: &SingleCard,
+    _action: &mut UseCardAction,
   ) {
     if card.card_info.card_type == CardType::Attack {
       context.remove_this_power();
@@ -1033,5 +1604,591 @@ impl PowerBehavior for Juggernaut {
   }
 }
 
+impl PowerBehavior for Shackled {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.power_owner_bottom(PowerId::Strength, context.amount());
+    context.remove_this_power();
+  }
+}
+
+// Shivs check for this themselves
+impl PowerBehavior for Accuracy {}
+
+impl PowerBehavior for Blur {
+  // the block retention itself is in start_creature_turn, like Barricade
+  fn at_end_of_round(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.reduce_this_power();
+  }
+}
+
+impl PowerBehavior for Choked {
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    _card: &SingleCard,
+    _action: &mut UseCardAction,
+  ) {
+    context.action_bottom(DamageAction {
+      target: context.owner_index(),
+      info: DamageInfoNoPowers::new(None, context.amount(), DamageType::HitpointLoss)
+        .ignore_powers(),
+    });
+  }
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.remove_this_power();
+  }
+}
+
+impl PowerBehavior for InfiniteBlades {
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    for _ in 0..context.amount() {
+      context.action_bottom(AddNewCardToHand(SingleCard::create(CardId::Shiv)));
+    }
+  }
+}
+
+impl PowerBehavior for NoxiousFumes {
+  fn at_start_of_turn_post_draw(&self, context: &mut PowerHookContext<impl Runner>) {
+    for index in 0..context.state().monsters.len() {
+      if !context.state().monsters[index].gone {
+        context.action_bottom(ApplyPowerAction {
+          source: context.owner_index(),
+          target: CreatureIndex::Monster(index),
+          power_id: PowerId::Poison,
+          amount: context.amount(),
+        });
+      }
+    }
+  }
+}
+
+impl PowerBehavior for RetainCards {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    if !context.state().hand.is_empty() && !context.state().has_relic(RelicId::RunicPyramid) {
+      context.action_bottom(ChooseCardAction {
+        choice_type: CardChoiceType::RetainCard {
+          remaining: context.amount(),
+        },
+      });
+    }
+  }
+}
+
+impl PowerBehavior for ThousandCuts {
+  fn on_after_card_played(&self, context: &mut PowerHookContext<impl Runner>, _card: &SingleCard) {
+    context.action_bottom(DamageAllEnemiesActionIgnoringPowers {
+      info: DamageInfoNoPowers::new(
+        Some(context.owner_index()),
+        context.amount(),
+        DamageType::Thorns,
+      ),
+    });
+  }
+}
+
+impl PowerBehavior for AfterImage {
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    _card: &SingleCard,
+    _action: &mut UseCardAction,
+  ) {
+    context.action_bottom(GainBlockAction {
+      creature_index: context.owner_index(),
+      amount: context.amount(),
+    });
+  }
+}
+
+impl PowerBehavior for Burst {
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    card: &SingleCard,
+    action: &mut UseCardAction,
+  ) {
+    if context.amount() > 0 && card.card_info.card_type == CardType::Skill && !action.purge_on_use {
+      let mut new_action = UseCardAction::new(card.clone(), action.target, context.state());
+      new_action.purge_on_use = true;
+      new_action.energy_on_use = action.energy_on_use;
+      context.state_mut().card_queue.push_back(new_action);
+      context.reduce_this_power();
+    }
+  }
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.remove_this_power();
+  }
+}
+
+impl PowerBehavior for CorpseExplosion {
+  fn on_death(&self, context: &mut PowerHookContext<impl Runner>) {
+    let damage = context.owner_creature().max_hitpoints * context.amount();
+    context.action_bottom(DamageAllEnemiesActionIgnoringPowers {
+      info: DamageInfoNoPowers::new(None, damage, DamageType::Thorns),
+    });
+  }
+}
+
+impl PowerBehavior for Envenom {
+  fn on_attack(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    info: &DamageInfoAllPowers,
+    damage: i32,
+    target: CreatureIndex,
+  ) {
+    if damage > 0 && target != context.owner_index() && info.damage_type == DamageType::Normal {
+      context.action_top(ApplyPowerAction {
+        source: context.owner_index(),
+        target,
+        power_id: PowerId::Poison,
+        amount: context.amount(),
+      });
+    }
+  }
+}
+
+impl PowerBehavior for Phantasmal {
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.power_owner_bottom(PowerId::DoubleDamage, 1);
+    context.reduce_this_power();
+  }
+}
+
+impl PowerBehavior for DoubleDamage {
+  fn at_damage_give(
+    &self,
+    _context: &PowerNumericHookContext,
+    damage: f64,
+    damage_type: DamageType,
+  ) -> f64 {
+    if damage_type != DamageType::Normal {
+      return damage;
+    }
+    damage * 2.0
+  }
+  fn at_end_of_round(&self, context: &mut PowerHookContext<impl Runner>) {
+    if context.remove_just_applied() {
+      context.reduce_this_power();
+    }
+  }
+}
+
+impl PowerBehavior for ToolsOfTheTrade {
+  fn at_start_of_turn_post_draw(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.action_bottom(DrawCards(context.amount()));
+    for _ in 0..context.amount() {
+      context.action_bottom(ChooseCardAction {
+        choice_type: CardChoiceType::DiscardCard,
+      });
+    }
+  }
+}
+
+impl PowerBehavior for WraithForm {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.power_owner_bottom(PowerId::Dexterity, context.amount());
+  }
+}
+
+impl PowerBehavior for Focus {
+  fn stack_power(&self, power: &mut Power, stack_amount: i32) {
+    power.amount += stack_amount;
+    if power.amount > 999 {
+      power.amount = 999;
+    }
+    if power.amount < -999 {
+      power.amount = -999;
+    }
+  }
+  fn reduce_power(&self, power: &mut Power, reduce_amount: i32) {
+    self.stack_power(power, -reduce_amount);
+  }
+}
+
+impl PowerBehavior for Rebound {
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    card: &SingleCard,
+    action: &mut UseCardAction,
+  ) {
+    if card.card_info.card_type != CardType::Power {
+      action.rebound = true;
+    }
+    context.reduce_this_power();
+  }
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.remove_this_power();
+  }
+}
+
+// orbs check for this themselves
+impl PowerBehavior for LockOn {
+  fn at_end_of_round(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.reduce_this_power();
+  }
+}
+
+impl PowerBehavior for Equilibrium {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    for card in &mut context.state_mut().hand {
+      if !card.ethereal() {
+        card.retain = true;
+      }
+    }
+  }
+  fn at_end_of_round(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.reduce_this_power();
+  }
+}
+
+impl PowerBehavior for Heatsink {
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    card: &SingleCard,
+    _action: &mut UseCardAction,
+  ) {
+    if card.card_info.card_type == CardType::Power {
+      context.action_bottom(DrawCards(context.amount()));
+    }
+  }
+}
+
+impl PowerBehavior for HelloWorld {
+  //TODO: a random common card each turn
+}
+
+impl PowerBehavior for Loop {
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    if !context.state().player.orbs.is_empty() {
+      for _ in 0..context.amount() {
+        if let Some(effect) = orb_passive_effect(context.state_mut(), 0) {
+          context.action_bottom(effect);
+        }
+      }
+    }
+  }
+}
+
+// the healing happens after combat, so it's only counted in CombatResult
+impl PowerBehavior for Repair {}
+
+impl PowerBehavior for StaticDischarge {
+  fn on_attacked(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    info: DamageInfoAllPowers,
+    damage: i32,
+  ) {
+    if info.owner.is_some()
+      && info.owner != Some(context.owner_index())
+      && info.damage_type == DamageType::Normal
+      && damage > 0
+    {
+      for _ in 0..context.amount() {
+        context.action_top(ChannelOrbAction(Orb::new(OrbType::Lightning)));
+      }
+    }
+  }
+}
+
+impl PowerBehavior for Storm {
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    card: &SingleCard,
+    _action: &mut UseCardAction,
+  ) {
+    if card.card_info.card_type == CardType::Power {
+      for _ in 0..context.amount() {
+        context.action_bottom(ChannelOrbAction(Orb::new(OrbType::Lightning)));
+      }
+    }
+  }
+}
+
+impl PowerBehavior for Amplify {
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    card: &SingleCard,
+    action: &mut UseCardAction,
+  ) {
+    if context.amount() > 0 && card.card_info.card_type == CardType::Power && !action.purge_on_use {
+      let mut new_action = UseCardAction::new(card.clone(), action.target, context.state());
+      new_action.purge_on_use = true;
+      new_action.energy_on_use = action.energy_on_use;
+      context.state_mut().card_queue.push_back(new_action);
+      context.reduce_this_power();
+    }
+  }
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.remove_this_power();
+  }
+}
+
+impl PowerBehavior for BiasedCognition {
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.power_owner_bottom(PowerId::Focus, -context.amount());
+  }
+}
+
+impl PowerBehavior for CreativeAI {
+  //TODO: a random power card each turn
+}
+
+// EndMonstersTurns checks for this itself
+impl PowerBehavior for Draw {}
+
+impl PowerBehavior for EchoForm {
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    card: &SingleCard,
+    action: &mut UseCardAction,
+  ) {
+    // misc is the number of cards doubled this turn
+    let cards_played = context.state().cards_played_this_turn;
+    if !action.purge_on_use && cards_played - context.this_power().misc <= context.amount() {
+      context.this_power_mut().misc += 1;
+      let mut new_action = UseCardAction::new(card.clone(), action.target, context.state());
+      new_action.purge_on_use = true;
+      new_action.energy_on_use = action.energy_on_use;
+      context.state_mut().card_queue.push_back(new_action);
+    }
+  }
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.this_power_mut().misc = 0;
+  }
+}
+
+// Lightning orbs check for this themselves
+impl PowerBehavior for Electrodynamics {}
+
+impl PowerBehavior for Mantra {
+  fn on_specific_trigger(&self, context: &mut PowerHookContext<impl Runner>) {
+    if context.amount() >= 10 {
+      context.action_top(ChangeStanceAction(Stance::Divinity));
+      context.this_power_mut().amount -= 10;
+      if context.amount() <= 0 {
+        context.remove_this_power();
+      }
+    }
+  }
+}
+
+impl PowerBehavior for Vigor {
+  fn at_damage_give(
+    &self,
+    context: &PowerNumericHookContext,
+    damage: f64,
+    damage_type: DamageType,
+  ) -> f64 {
+    if damage_type == DamageType::Normal {
+      damage + context.amount() as f64
+    } else {
+      damage
+    }
+  }
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    card: &SingleCard,
+    _action: &mut UseCardAction,
+  ) {
+    if card.card_info.card_type == CardType::Attack {
+      context.remove_this_power();
+    }
+  }
+}
+
+// Pressure Points checks for this itself
+impl PowerBehavior for Mark {}
+
+// makes attacks free in SingleCard::cost_in_practice
+impl PowerBehavior for FreeAttack {
+  fn on_use_card(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    card: &SingleCard,
+    action: &mut UseCardAction,
+  ) {
+    if card.card_info.card_type == CardType::Attack && !action.purge_on_use {
+      context.reduce_this_power();
+    }
+  }
+}
+
+impl PowerBehavior for WrathNextTurn {
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.action_bottom(ChangeStanceAction(Stance::Wrath));
+    context.remove_this_power();
+  }
+}
+
+impl PowerBehavior for BattleHymn {
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    for _ in 0..context.amount() {
+      context.action_bottom(AddNewCardToHand(SingleCard::create(CardId::Smite)));
+    }
+  }
+}
+
+impl PowerBehavior for BlockReturn {
+  fn on_attacked(
+    &self,
+    context: &mut PowerHookContext<impl Runner>,
+    info: DamageInfoAllPowers,
+    _damage: i32,
+  ) {
+    if info.owner == Some(CreatureIndex::Player) && info.damage_type == DamageType::Normal {
+      context.action_top(GainBlockAction {
+        creature_index: CreatureIndex::Player,
+        amount: context.amount(),
+      });
+    }
+  }
+}
+
+impl PowerBehavior for Collect {
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    let mut miracle = SingleCard::create(CardId::Miracle);
+    miracle.upgrade();
+    context.action_bottom(AddNewCardToHand(miracle));
+    context.reduce_this_power();
+  }
+}
+
+impl PowerBehavior for EnergyDown {
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.action_bottom(LoseEnergyAction(context.amount()));
+  }
+}
+
+impl PowerBehavior for Foresight {
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.action_bottom(ScryAction(context.amount()));
+  }
+}
+
+impl PowerBehavior for LikeWater {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    if context.state().player.stance == Stance::Calm {
+      context.action_bottom(GainBlockAction {
+        creature_index: CreatureIndex::Player,
+        amount: context.amount(),
+      });
+    }
+  }
+}
+
+impl PowerBehavior for MentalFortress {
+  fn on_enter_stance(&self, context: &mut PowerHookContext<impl Runner>, _stance: Stance) {
+    context.action_bottom(GainBlockAction {
+      creature_index: CreatureIndex::Player,
+      amount: context.amount(),
+    });
+  }
+}
+
+impl PowerBehavior for Nirvana {
+  fn on_scry(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.action_bottom(GainBlockAction {
+      creature_index: CreatureIndex::Player,
+      amount: context.amount(),
+    });
+  }
+}
+
+impl PowerBehavior for Rushdown {
+  fn on_enter_stance(&self, context: &mut PowerHookContext<impl Runner>, stance: Stance) {
+    if stance == Stance::Wrath {
+      context.action_bottom(DrawCards(context.amount()));
+    }
+  }
+}
+
+impl PowerBehavior for Study {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    for _ in 0..context.amount() {
+      context.action_bottom(ShuffleNewCardIntoDrawPile(SingleCard::create(
+        CardId::Insight,
+      )));
+    }
+  }
+}
+
+impl PowerBehavior for WaveOfTheHand {
+  fn on_gained_block(&self, context: &mut PowerHookContext<impl Runner>, _block: f64) {
+    for monster_index in 0..context.state().monsters.len() {
+      context.action_bottom(ApplyPowerAction {
+        source: CreatureIndex::Player,
+        target: CreatureIndex::Monster(monster_index),
+        power_id: PowerId::Weak,
+        amount: context.amount(),
+      });
+    }
+  }
+  fn at_end_of_round(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.remove_this_power();
+  }
+}
+
+impl PowerBehavior for DevaForm {
+  fn on_energy_recharge(&self, context: &mut PowerHookContext<impl Runner>) {
+    // misc is the energy gained last turn
+    let gain = context.this_power().misc + context.amount();
+    context.this_power_mut().misc = gain;
+    context.state_mut().player.energy += gain;
+  }
+}
+
+impl PowerBehavior for Devotion {
+  fn at_start_of_turn_post_draw(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.power_owner_bottom(PowerId::Mantra, context.amount());
+  }
+}
+
+// Blasphemy
+impl PowerBehavior for EndTurnDeath {
+  fn at_start_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.action_bottom(DamageAction {
+      target: CreatureIndex::Player,
+      info: DamageInfoNoPowers::new(Some(CreatureIndex::Player), 99999, DamageType::HitpointLoss)
+        .ignore_powers(),
+    });
+    context.remove_this_power();
+  }
+}
+
+impl PowerBehavior for Establishment {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    let amount = context.amount();
+    for card in &mut context.state_mut().hand {
+      if (card.retain || card.retains()) && card.cost > 0 {
+        card.cost = std::cmp::max(0, card.cost - amount);
+      }
+    }
+  }
+}
+
+impl PowerBehavior for MasterReality {
+  //TODO: upgrade the cards created during combat
+}
+
+impl PowerBehavior for Omega {
+  fn at_end_of_turn(&self, context: &mut PowerHookContext<impl Runner>) {
+    context.action_bottom(DamageAllEnemiesActionIgnoringPowers {
+      info: DamageInfoNoPowers::new(
+        Some(CreatureIndex::Player),
+        context.amount(),
+        DamageType::Thorns,
+      ),
+    });
+  }
+}
+
 impl PowerBehavior for Split {}
 impl PowerBehavior for Unknown {}
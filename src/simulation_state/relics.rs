@@ -0,0 +1,590 @@
+use serde::{Deserialize, Serialize};
+use std::convert::From;
+use std::fmt::{self, Display, Formatter};
+
+use crate::communication_mod_state as communication;
+use crate::simulation::*;
+use crate::simulation_state::*;
+
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub struct Relic {
+  pub relic_id: RelicId,
+  /// The counter the game displays on the relic, or -1 for relics that don't count anything.
+  pub counter: i32,
+  /// For relics that trigger once per combat (Lantern, Centennial Puzzle) or once per turn
+  /// (Necronomicon).
+  #[serde(default)]
+  pub used: bool,
+}
+
+impl Relic {
+  pub fn new(relic_id: RelicId) -> Relic {
+    Relic {
+      relic_id,
+      counter: -1,
+      used: false,
+    }
+  }
+
+  pub fn from_communication_mod(relic: &communication::Relic) -> Option<Relic> {
+    let relic_id = RelicId::from(&*relic.id);
+    if relic_id == RelicId::Unknown {
+      return None;
+    }
+    Some(Relic {
+      relic_id,
+      counter: relic.counter,
+      // CommunicationMod doesn't report this. We only ever see states after the first turn has
+      // started, so Lantern has always triggered by then.
+      // TODO: Centennial Puzzle, which we optimistically assume hasn't triggered yet
+      used: relic_id == RelicId::Lantern,
+    })
+  }
+}
+
+pub struct RelicHookContext<'a, R: Runner> {
+  pub runner: &'a mut R,
+  pub relic_index: usize,
+}
+
+impl<'a, R: Runner> RelicHookContext<'a, R> {
+  pub fn state(&self) -> &CombatState {
+    self.runner.state()
+  }
+  pub fn state_mut(&mut self) -> &mut CombatState {
+    self.runner.state_mut()
+  }
+  pub fn this_relic(&self) -> &Relic {
+    &self.state().relics[self.relic_index]
+  }
+  pub fn this_relic_mut(&mut self) -> &mut Relic {
+    let relic_index = self.relic_index;
+    &mut self.state_mut().relics[relic_index]
+  }
+  /// Increments the counter, and returns true (resetting it) whenever it reaches `period`.
+  pub fn count_to(&mut self, period: i32) -> bool {
+    let counter = &mut self.this_relic_mut().counter;
+    *counter = (*counter).max(0) + 1;
+    if *counter >= period {
+      *counter = 0;
+      true
+    } else {
+      false
+    }
+  }
+
+  pub fn action_top(&mut self, action: impl Action) {
+    self.runner.action_top(action);
+  }
+  pub fn action_bottom(&mut self, action: impl Action) {
+    self.runner.action_bottom(action);
+  }
+
+  pub fn power_player_top(&mut self, power_id: PowerId, amount: i32) {
+    self.action_top(ApplyPowerAction {
+      source: CreatureIndex::Player,
+      target: CreatureIndex::Player,
+      power_id,
+      amount,
+    });
+  }
+  pub fn power_player_bottom(&mut self, power_id: PowerId, amount: i32) {
+    self.action_bottom(ApplyPowerAction {
+      source: CreatureIndex::Player,
+      target: CreatureIndex::Player,
+      power_id,
+      amount,
+    });
+  }
+  pub fn power_monsters_bottom(&mut self, power_id: PowerId, amount: i32) {
+    for monster_index in 0..self.state().monsters.len() {
+      if !self.state().monsters[monster_index].gone {
+        self.action_bottom(ApplyPowerAction {
+          source: CreatureIndex::Player,
+          target: CreatureIndex::Monster(monster_index),
+          power_id,
+          amount,
+        });
+      }
+    }
+  }
+}
+
+#[allow(unused)]
+pub trait RelicBehavior {
+  /// Energy added to the player's energy per turn.
+  fn inherent_energy(&self) -> i32 {
+    0
+  }
+  /// After the opening hand is drawn.
+  fn at_battle_start(&self, context: &mut RelicHookContext<impl Runner>) {}
+  /// Before the player draws for the turn, after energy is recharged.
+  fn at_turn_start(&self, context: &mut RelicHookContext<impl Runner>) {}
+  fn at_turn_start_post_draw(&self, context: &mut RelicHookContext<impl Runner>) {}
+  /// Before the player's end of turn powers.
+  fn at_end_of_turn(&self, context: &mut RelicHookContext<impl Runner>) {}
+  fn on_use_card(
+    &self,
+    context: &mut RelicHookContext<impl Runner>,
+    card: &SingleCard,
+    action: &mut UseCardAction,
+  ) {
+  }
+  /// Damage to the player, after the player's powers have modified it.
+  fn on_attacked_to_change_damage(
+    &self,
+    context: &mut RelicHookContext<impl Runner>,
+    info: &DamageInfoAllPowers,
+    damage: &mut i32,
+  ) {
+  }
+  /// HP loss of the player, after everything else has modified it.
+  fn on_lose_hp_last(&self, context: &mut RelicHookContext<impl Runner>, damage: &mut i32) {}
+  fn was_hp_lost(&self, context: &mut RelicHookContext<impl Runner>, damage: i32) {}
+}
+
+macro_rules! relics {
+  ($([$id: expr, $Variant: ident],)*) => {
+    #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
+    pub enum RelicId {
+      $($Variant,)*
+    }
+
+    $(
+    #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
+    pub struct $Variant;)*
+
+    impl From<& str> for RelicId {
+      fn from (source: & str)->RelicId {
+        match source {
+          $($id => RelicId::$Variant,)*
+          _ => RelicId::Unknown,
+        }
+      }
+    }
+
+    impl RelicBehavior for RelicId {
+      fn inherent_energy(&self) -> i32 {
+        match self {
+          $(RelicId::$Variant => $Variant.inherent_energy(),)*
+        }
+      }
+      fn at_battle_start(&self, context: &mut RelicHookContext<impl Runner>) {
+        match self {
+          $(RelicId::$Variant => $Variant.at_battle_start(context),)*
+        }
+      }
+      fn at_turn_start(&self, context: &mut RelicHookContext<impl Runner>) {
+        match self {
+          $(RelicId::$Variant => $Variant.at_turn_start(context),)*
+        }
+      }
+      fn at_turn_start_post_draw(&self, context: &mut RelicHookContext<impl Runner>) {
+        match self {
+          $(RelicId::$Variant => $Variant.at_turn_start_post_draw(context),)*
+        }
+      }
+      fn at_end_of_turn(&self, context: &mut RelicHookContext<impl Runner>) {
+        match self {
+          $(RelicId::$Variant => $Variant.at_end_of_turn(context),)*
+        }
+      }
+      fn on_use_card(
+        &self,
+        context: &mut RelicHookContext<impl Runner>,
+        card: &SingleCard,
+        action: &mut UseCardAction,
+      ) {
+        match self {
+          $(RelicId::$Variant => $Variant.on_use_card(context, card, action),)*
+        }
+      }
+      fn on_attacked_to_change_damage(
+        &self,
+        context: &mut RelicHookContext<impl Runner>,
+        info: &DamageInfoAllPowers,
+        damage: &mut i32,
+      ) {
+        match self {
+          $(RelicId::$Variant => $Variant.on_attacked_to_change_damage(context, info, damage),)*
+        }
+      }
+      fn on_lose_hp_last(&self, context: &mut RelicHookContext<impl Runner>, damage: &mut i32) {
+        match self {
+          $(RelicId::$Variant => $Variant.on_lose_hp_last(context, damage),)*
+        }
+      }
+      fn was_hp_lost(&self, context: &mut RelicHookContext<impl Runner>, damage: i32) {
+        match self {
+          $(RelicId::$Variant => $Variant.was_hp_lost(context, damage),)*
+        }
+      }
+    }
+  }
+}
+
+relics! {
+  ["Unknown", Unknown],
+
+  // Common relics
+  ["Anchor", Anchor],
+  ["Bag of Marbles", BagOfMarbles],
+  ["Bronze Scales", BronzeScales],
+  ["Centennial Puzzle", CentennialPuzzle],
+  ["Lantern", Lantern],
+  ["Pen Nib", PenNib],
+  ["Vajra", Vajra],
+
+  // Uncommon relics
+  ["InkBottle", InkBottle],
+  ["Kunai", Kunai],
+  ["Letter Opener", LetterOpener],
+  ["Ornamental Fan", OrnamentalFan],
+  ["Self Forming Clay", SelfFormingClay],
+  ["Shuriken", Shuriken],
+
+  // Rare relics
+  ["Torii", Torii],
+  ["TungstenRod", TungstenRod],
+
+  // Shop relics
+  ["Runic Cube", RunicCube],
+
+  // Boss relics
+  ["Busted Crown", BustedCrown],
+  ["Coffee Dripper", CoffeeDripper],
+  ["Cursed Key", CursedKey],
+  ["Ectoplasm", Ectoplasm],
+  ["Fusion Hammer", FusionHammer],
+  ["Mark of Pain", MarkOfPain],
+  ["Philosopher's Stone", PhilosophersStone],
+  ["Runic Pyramid", RunicPyramid],
+  ["SacredBark", SacredBark],
+  ["Sozu", Sozu],
+
+  // Event relics
+  ["Necronomicon", Necronomicon],
+}
+
+impl RelicBehavior for Unknown {}
+
+impl RelicBehavior for Anchor {
+  fn at_battle_start(&self, context: &mut RelicHookContext<impl Runner>) {
+    context.action_bottom(GainBlockAction {
+      creature_index: CreatureIndex::Player,
+      amount: 10,
+    });
+  }
+}
+
+impl RelicBehavior for BagOfMarbles {
+  fn at_battle_start(&self, context: &mut RelicHookContext<impl Runner>) {
+    context.power_monsters_bottom(PowerId::Vulnerable, 1);
+  }
+}
+
+impl RelicBehavior for BronzeScales {
+  fn at_battle_start(&self, context: &mut RelicHookContext<impl Runner>) {
+    context.power_player_top(PowerId::Thorns, 3);
+  }
+}
+
+impl RelicBehavior for CentennialPuzzle {
+  fn was_hp_lost(&self, context: &mut RelicHookContext<impl Runner>, damage: i32) {
+    if damage > 0 && !context.this_relic().used {
+      context.this_relic_mut().used = true;
+      context.action_top(DrawCards(3));
+    }
+  }
+}
+
+impl RelicBehavior for Lantern {
+  fn at_turn_start(&self, context: &mut RelicHookContext<impl Runner>) {
+    if !context.this_relic().used {
+      context.this_relic_mut().used = true;
+      context.action_top(GainEnergyAction(1));
+    }
+  }
+}
+
+impl RelicBehavior for PenNib {
+  fn at_battle_start(&self, context: &mut RelicHookContext<impl Runner>) {
+    if context.this_relic().counter == 9 {
+      context.power_player_top(PowerId::PenNib, 1);
+    }
+  }
+  fn on_use_card(
+    &self,
+    context: &mut RelicHookContext<impl Runner>,
+    card: &SingleCard,
+    _action: &mut UseCardAction,
+  ) {
+    if card.card_info.card_type == CardType::Attack {
+      context.count_to(10);
+      // the power doubles the attack after the one that sets the counter to 9
+      if context.this_relic().counter == 9 {
+        context.power_player_bottom(PowerId::PenNib, 1);
+      }
+    }
+  }
+}
+
+impl RelicBehavior for Vajra {
+  fn at_battle_start(&self, context: &mut RelicHookContext<impl Runner>) {
+    context.power_player_top(PowerId::Strength, 1);
+  }
+}
+
+impl RelicBehavior for InkBottle {
+  fn on_use_card(
+    &self,
+    context: &mut RelicHookContext<impl Runner>,
+    _card: &SingleCard,
+    _action: &mut UseCardAction,
+  ) {
+    if context.count_to(10) {
+      context.action_bottom(DrawCards(1));
+    }
+  }
+}
+
+/// Relics that trigger on every third card of a type played in a single turn.
+macro_rules! cards_per_turn_relic {
+  ($Relic: ident, $card_type: expr, |$context: ident| $effect: expr) => {
+    impl RelicBehavior for $Relic {
+      fn at_turn_start(&self, context: &mut RelicHookContext<impl Runner>) {
+        context.this_relic_mut().counter = 0;
+      }
+      fn on_use_card(
+        &self,
+        $context: &mut RelicHookContext<impl Runner>,
+        card: &SingleCard,
+        _action: &mut UseCardAction,
+      ) {
+        if card.card_info.card_type == $card_type && $context.count_to(3) {
+          $effect;
+        }
+      }
+    }
+  };
+}
+
+cards_per_turn_relic!(Kunai, CardType::Attack, |context| context
+  .power_player_bottom(PowerId::Dexterity, 1));
+cards_per_turn_relic!(Shuriken, CardType::Attack, |context| context
+  .power_player_bottom(PowerId::Strength, 1));
+cards_per_turn_relic!(OrnamentalFan, CardType::Attack, |context| context
+  .action_bottom(GainBlockAction {
+    creature_index: CreatureIndex::Player,
+    amount: 4,
+  }));
+cards_per_turn_relic!(LetterOpener, CardType::Skill, |context| context
+  .action_bottom(DamageAllEnemiesActionIgnoringPowers {
+    info: DamageInfoNoPowers::new(None, 5, DamageType::Thorns),
+  }));
+
+impl RelicBehavior for SelfFormingClay {
+  fn was_hp_lost(&self, context: &mut RelicHookContext<impl Runner>, damage: i32) {
+    if damage > 0 {
+      context.power_player_bottom(PowerId::NextTurnBlock, 3);
+    }
+  }
+}
+
+impl RelicBehavior for Torii {
+  fn on_attacked_to_change_damage(
+    &self,
+    _context: &mut RelicHookContext<impl Runner>,
+    info: &DamageInfoAllPowers,
+    damage: &mut i32,
+  ) {
+    if info.owner.is_some() && info.damage_type == DamageType::Normal && (2..=5).contains(damage) {
+      *damage = 1;
+    }
+  }
+}
+
+impl RelicBehavior for TungstenRod {
+  fn on_lose_hp_last(&self, _context: &mut RelicHookContext<impl Runner>, damage: &mut i32) {
+    if *damage > 0 {
+      *damage -= 1;
+    }
+  }
+}
+
+impl RelicBehavior for RunicCube {
+  fn was_hp_lost(&self, context: &mut RelicHookContext<impl Runner>, damage: i32) {
+    if damage > 0 {
+      context.action_top(DrawCards(1));
+    }
+  }
+}
+
+macro_rules! energy_relic {
+  ($($Relic: ident),*) => {
+    $(impl RelicBehavior for $Relic {
+      fn inherent_energy(&self) -> i32 {
+        1
+      }
+    })*
+  };
+}
+
+energy_relic!(
+  BustedCrown,
+  CoffeeDripper,
+  CursedKey,
+  Ectoplasm,
+  FusionHammer,
+  MarkOfPain,
+  PhilosophersStone,
+  Sozu
+);
+impl RelicBehavior for RunicPyramid {}
+impl RelicBehavior for SacredBark {}
+
+impl RelicBehavior for Necronomicon {
+  fn at_turn_start(&self, context: &mut RelicHookContext<impl Runner>) {
+    context.this_relic_mut().used = false;
+  }
+  fn on_use_card(
+    &self,
+    context: &mut RelicHookContext<impl Runner>,
+    card: &SingleCard,
+    action: &mut UseCardAction,
+  ) {
+    if !context.this_relic().used
+      && card.card_info.card_type == CardType::Attack
+      && card.cost_in_practice(context.state()) >= 2
+    {
+      context.this_relic_mut().used = true;
+      let mut new_action = UseCardAction::new(card.clone(), action.target, context.state());
+      new_action.purge_on_use = true;
+      new_action.energy_on_use = action.energy_on_use;
+      context.state_mut().card_queue.push_back(new_action);
+    }
+  }
+}
+
+impl Display for Relic {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{:?}", self.relic_id)?;
+    if self.counter >= 0 {
+      write!(f, "({})", self.counter)?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::simulation::tests::*;
+
+  fn with_relics(hand: &[CardId], relics: &[RelicId]) -> CombatState {
+    let mut state = combat(hand, &[MonsterId::JawWorm]);
+    state.relics = relics
+      .iter()
+      .map(|&relic_id| Relic::new(relic_id))
+      .collect();
+    state
+  }
+
+  fn monster_hit(amount: i32) -> DamageAction {
+    DamageAction {
+      target: CreatureIndex::Player,
+      info: DamageInfoNoPowers::new(Some(CreatureIndex::Monster(0)), amount, DamageType::Normal)
+        .ignore_powers(),
+    }
+  }
+
+  #[test]
+  fn torii_and_tungsten_rod_reduce_small_hits() {
+    let mut state = with_relics(&[], &[RelicId::Torii]);
+    run(&mut state, monster_hit(4));
+    assert_eq!(state.player.creature.hitpoints, 70 - 1);
+    run(&mut state, monster_hit(6));
+    assert_eq!(state.player.creature.hitpoints, 70 - 1 - 6);
+
+    let mut state = with_relics(&[], &[RelicId::Torii, RelicId::TungstenRod]);
+    run(&mut state, monster_hit(4));
+    assert_eq!(state.player.creature.hitpoints, 70);
+    run(&mut state, monster_hit(6));
+    assert_eq!(state.player.creature.hitpoints, 70 - 5);
+  }
+
+  #[test]
+  fn tungsten_rod_reduces_every_hitpoint_loss() {
+    let mut state = with_relics(&[CardId::Hemokinesis], &[RelicId::TungstenRod]);
+    state.player.creature.block = 10;
+    play(&mut state, CardId::Hemokinesis, 0);
+    // it costs 3 hitpoints, less 1
+    assert_eq!(state.player.creature.hitpoints, 70 - 2);
+    // what Poison does at the start of the turn, which ignores block too
+    run(
+      &mut state,
+      DamageAction {
+        target: CreatureIndex::Player,
+        info: DamageInfoNoPowers::new(None, 3, DamageType::HitpointLoss).ignore_powers(),
+      },
+    );
+    assert_eq!(state.player.creature.hitpoints, 70 - 2 - 2);
+    assert_eq!(state.player.creature.block, 10);
+  }
+
+  #[test]
+  fn kunai_counts_attacks_played_this_turn() {
+    let mut state = with_relics(
+      &[CardId::StrikeR, CardId::StrikeR, CardId::StrikeR],
+      &[RelicId::Kunai],
+    );
+    play(&mut state, CardId::StrikeR, 0);
+    play(&mut state, CardId::StrikeR, 0);
+    assert_eq!(state.relics[0].counter, 2);
+    assert_eq!(state.player.creature.power_amount(PowerId::Dexterity), 0);
+    play(&mut state, CardId::StrikeR, 0);
+    assert_eq!(state.relics[0].counter, 0);
+    assert_eq!(state.player.creature.power_amount(PowerId::Dexterity), 1);
+  }
+
+  #[test]
+  fn necronomicon_replays_the_first_expensive_attack() {
+    let mut state = with_relics(&[CardId::Bash], &[RelicId::Necronomicon]);
+    play(&mut state, CardId::Bash, 0);
+    // the copy hits the Vulnerable that the original applied
+    assert_eq!(state.monsters[0].creature.hitpoints, 40 - 8 - 12);
+    assert_eq!(
+      state.monsters[0].creature.power_amount(PowerId::Vulnerable),
+      4
+    );
+    assert_eq!(state.player.energy, 1);
+    assert!(state.relics[0].used);
+  }
+
+  #[test]
+  fn relics_are_imported() {
+    let observed = |id: &str, counter| communication::Relic {
+      name: id.to_string(),
+      id: id.to_string(),
+      counter,
+    };
+    assert_eq!(
+      Relic::from_communication_mod(&observed("Pen Nib", 4)),
+      Some(Relic {
+        relic_id: RelicId::PenNib,
+        counter: 4,
+        used: false,
+      })
+    );
+    // by the time we see the state, Lantern has already given its energy
+    assert!(
+      Relic::from_communication_mod(&observed("Lantern", -1))
+        .unwrap()
+        .used
+    );
+    assert_eq!(
+      Relic::from_communication_mod(&observed("Not A Relic", -1)),
+      None
+    );
+  }
+}
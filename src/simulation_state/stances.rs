@@ -0,0 +1,190 @@
+use derivative::Derivative;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+use crate::simulation_state::*;
+
+#[derive(
+  Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug, Derivative,
+)]
+#[derivative(Default)]
+pub enum Stance {
+  #[derivative(Default)]
+  Neutral,
+  Calm,
+  Wrath,
+  Divinity,
+}
+
+impl Stance {
+  pub fn from_communication_mod(id: &str) -> Option<Stance> {
+    match id {
+      "Neutral" => Some(Stance::Neutral),
+      "Calm" => Some(Stance::Calm),
+      "Wrath" => Some(Stance::Wrath),
+      "Divinity" => Some(Stance::Divinity),
+      _ => None,
+    }
+  }
+  /// Applied to the player's Normal damage, after the player's powers.
+  pub fn damage_give_multiplier(self) -> f64 {
+    match self {
+      Stance::Wrath => 2.0,
+      Stance::Divinity => 3.0,
+      _ => 1.0,
+    }
+  }
+  /// Applied to Normal damage the player takes, before the player's powers.
+  pub fn damage_receive_multiplier(self) -> f64 {
+    match self {
+      Stance::Wrath => 2.0,
+      _ => 1.0,
+    }
+  }
+}
+
+/// The stance's own effect when the player leaves it. Like the actual game, it happens before
+/// anything else in the queue.
+pub fn on_exit_stance(runner: &mut impl Runner, stance: Stance) {
+  if stance == Stance::Calm {
+    runner.action_top(GainEnergyAction(2));
+  }
+}
+
+pub fn on_enter_stance(runner: &mut impl Runner, stance: Stance) {
+  if stance == Stance::Divinity {
+    runner.action_top(GainEnergyAction(3));
+  }
+}
+
+impl Display for Stance {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    write!(f, "{:?}", self)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::simulation::tests::*;
+  use crate::simulation_state::monsters::exordium::{CultistIntent, JawWormIntent};
+  use crate::simulation_state::monsters::Intent;
+
+  #[test]
+  fn wrath_doubles_the_players_attacks() {
+    let mut state = combat(&[CardId::Eruption, CardId::StrikeP], &[MonsterId::Cultist]);
+    state.monsters[0].move_history = vec![CultistIntent::Incantation.id()];
+    play(&mut state, CardId::Eruption, 0);
+    assert_eq!(state.player.stance, Stance::Wrath);
+    assert_eq!(state.monsters[0].creature.hitpoints, 40 - 9);
+    play(&mut state, CardId::StrikeP, 0);
+    assert_eq!(state.monsters[0].creature.hitpoints, 40 - 9 - 12);
+  }
+
+  #[test]
+  fn wrath_doubles_the_damage_the_player_takes() {
+    let mut state = combat(&[], &[MonsterId::JawWorm]);
+    state.monsters[0].move_history = vec![JawWormIntent::Chomp.id()];
+    state.player.stance = Stance::Wrath;
+    choose(&mut state, EndTurn);
+    assert_eq!(state.player.creature.hitpoints, 70 - 22);
+  }
+
+  #[test]
+  fn leaving_calm_gives_energy() {
+    let mut state = combat(
+      &[CardId::Vigilance, CardId::Eruption],
+      &[MonsterId::Cultist],
+    );
+    state.player.energy = 4;
+    play(&mut state, CardId::Vigilance, 0);
+    assert_eq!(state.player.stance, Stance::Calm);
+    assert_eq!(state.player.energy, 2);
+    play(&mut state, CardId::Eruption, 0);
+    assert_eq!(state.player.stance, Stance::Wrath);
+    assert_eq!(state.player.energy, 2);
+  }
+
+  #[test]
+  fn ten_mantra_enters_divinity() {
+    let mut state = combat(&[CardId::Worship, CardId::Worship], &[MonsterId::Cultist]);
+    state.player.energy = 4;
+    play(&mut state, CardId::Worship, 0);
+    assert_eq!(state.player.creature.power_amount(PowerId::Mantra), 5);
+    assert_eq!(state.player.stance, Stance::Neutral);
+    play(&mut state, CardId::Worship, 0);
+    assert_eq!(state.player.stance, Stance::Divinity);
+    assert_eq!(state.player.creature.power_amount(PowerId::Mantra), 0);
+    assert_eq!(state.player.energy, 4 - 2 - 2 + 3);
+  }
+
+  #[test]
+  fn retaining_cards_stay_in_hand() {
+    let mut state = combat(&[CardId::Protect, CardId::StrikeP], &[MonsterId::Cultist]);
+    state.monsters[0].move_history = vec![CultistIntent::Incantation.id()];
+    state.draw_pile = cards(&[CardId::DefendP; 5]);
+    choose(&mut state, EndTurn);
+    assert_eq!(state.hand.len(), 6);
+    assert!(state
+      .hand
+      .iter()
+      .any(|card| card.card_info.id == CardId::Protect));
+    assert!(state
+      .discard_pile
+      .iter()
+      .any(|card| card.card_info.id == CardId::StrikeP));
+  }
+
+  #[test]
+  fn vault_takes_an_extra_turn() {
+    let mut state = combat(&[CardId::Vault], &[MonsterId::JawWorm]);
+    state.monsters[0].move_history = vec![JawWormIntent::Chomp.id()];
+    state.draw_pile = cards(&[CardId::DefendP; 5]);
+    play(&mut state, CardId::Vault, 0);
+    assert_eq!(state.player.creature.hitpoints, 70);
+    assert_eq!(state.monsters[0].move_history, [JawWormIntent::Chomp.id()]);
+    assert_eq!(state.turn_number, 2);
+    assert_eq!(state.player.energy, 3);
+    assert_eq!(state.hand.len(), 5);
+    assert_eq!(state.exhaust_pile, cards(&[CardId::Vault]));
+
+    // the monsters take the turn after that as usual
+    choose(&mut state, EndTurn);
+    assert_eq!(state.player.creature.hitpoints, 70 - 11);
+  }
+
+  #[test]
+  fn wish_lets_the_player_pick_a_wish() {
+    let mut state = combat(&[CardId::Wish], &[MonsterId::Cultist]);
+    state.hand[0].upgrade();
+    play(&mut state, CardId::Wish, 0);
+    let mut offered = cards(&[
+      CardId::BecomeAlmighty,
+      CardId::FameAndFortune,
+      CardId::LiveForever,
+    ]);
+    for card in &mut offered {
+      card.upgrade();
+    }
+    assert_eq!(state.offered_cards, offered);
+    choose(
+      &mut state,
+      SelectCard {
+        card: offered[2].clone(),
+        choice_type: CardChoiceType::ChooseOne,
+      },
+    );
+    assert_eq!(state.player.creature.power_amount(PowerId::PlatedArmor), 8);
+    assert!(state.offered_cards.is_empty());
+  }
+
+  #[test]
+  fn stances_are_imported() {
+    assert_eq!(Stance::from_communication_mod("Calm"), Some(Stance::Calm));
+    assert_eq!(
+      Stance::from_communication_mod("Divinity"),
+      Some(Stance::Divinity)
+    );
+    assert_eq!(Stance::from_communication_mod("Angry"), None);
+  }
+}
@@ -0,0 +1,434 @@
+//! Emulates the random number streams Slay the Spire uses in combat, so that given the run's seed
+//! we can predict the actual shuffles, intents and other rolls, rather than just sampling them.
+//!
+//! The game's `com.megacrit.cardcrawl.random.Random` wraps libGDX's `RandomXS128` and counts how
+//! many times it has been used; shuffles go through `java.util.Random`. Each floor, the game
+//! reseeds the combat streams with the run seed plus the floor number.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::actions::{ChooseMonsterIntent, DynAction};
+use crate::communication_mod_state as communication;
+use crate::seed_system::{Distribution, SeedView};
+use crate::simulation::{Action, Determinism};
+use crate::simulation_state::monsters::intent_choices_for_num;
+use crate::simulation_state::{CardId, CombatState, SingleCard};
+
+/// libGDX's `RandomXS128`, behind the game's counting wrapper.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SpireRandom {
+  seed0: u64,
+  seed1: u64,
+  counter: u32,
+}
+
+fn murmur_hash3(mut x: u64) -> u64 {
+  x ^= x >> 33;
+  x = x.wrapping_mul(0xff51afd7ed558ccd);
+  x ^= x >> 33;
+  x = x.wrapping_mul(0xc4ceb9fe1a85ec53);
+  x ^= x >> 33;
+  x
+}
+
+impl SpireRandom {
+  pub fn new(seed: i64) -> SpireRandom {
+    let seed = if seed == 0 { i64::MIN } else { seed };
+    let seed0 = murmur_hash3(seed as u64);
+    SpireRandom {
+      seed0,
+      seed1: murmur_hash3(seed0),
+      counter: 0,
+    }
+  }
+  /// Like the game does when loading a save: skips ahead by rolling `counter` times.
+  pub fn with_counter(seed: i64, counter: u32) -> SpireRandom {
+    let mut result = SpireRandom::new(seed);
+    for _ in 0..counter {
+      result.random(999);
+    }
+    result
+  }
+  pub fn counter(&self) -> u32 {
+    self.counter
+  }
+
+  fn next_long(&mut self) -> i64 {
+    let mut s1 = self.seed0;
+    let s0 = self.seed1;
+    self.seed0 = s0;
+    s1 ^= s1 << 23;
+    self.seed1 = s1 ^ s0 ^ (s1 >> 17) ^ (s0 >> 26);
+    self.seed1.wrapping_add(s0) as i64
+  }
+  fn next_long_below(&mut self, n: i64) -> i64 {
+    assert!(n > 0);
+    loop {
+      let bits = ((self.next_long() as u64) >> 1) as i64;
+      let value = bits % n;
+      // rejects the values that would make the result biased, like Java's overflow check does
+      if bits.wrapping_sub(value).wrapping_add(n - 1) >= 0 {
+        return value;
+      }
+    }
+  }
+  fn next_float(&mut self) -> f32 {
+    (((self.next_long() as u64) >> 40) as f64 / (1u64 << 24) as f64) as f32
+  }
+
+  /// From 0 to `range`, inclusive.
+  pub fn random(&mut self, range: i32) -> i32 {
+    self.counter += 1;
+    self.next_long_below(range as i64 + 1) as i32
+  }
+  /// From `start` to `end`, inclusive.
+  pub fn random_range(&mut self, start: i32, end: i32) -> i32 {
+    self.counter += 1;
+    start + self.next_long_below((end - start) as i64 + 1) as i32
+  }
+  pub fn random_float(&mut self) -> f32 {
+    self.counter += 1;
+    self.next_float()
+  }
+  pub fn random_boolean(&mut self, chance: f32) -> bool {
+    self.counter += 1;
+    self.next_float() < chance
+  }
+  pub fn random_long(&mut self) -> i64 {
+    self.counter += 1;
+    self.next_long()
+  }
+}
+
+/// `java.util.Random`, which the game creates from its shuffle stream for every shuffle.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct JavaRandom {
+  seed: u64,
+}
+
+impl JavaRandom {
+  const MULTIPLIER: u64 = 0x5DEECE66D;
+  const MASK: u64 = (1 << 48) - 1;
+
+  pub fn new(seed: i64) -> JavaRandom {
+    JavaRandom {
+      seed: (seed as u64 ^ Self::MULTIPLIER) & Self::MASK,
+    }
+  }
+  fn next(&mut self, bits: u32) -> i32 {
+    self.seed = (self.seed.wrapping_mul(Self::MULTIPLIER).wrapping_add(0xB)) & Self::MASK;
+    (self.seed >> (48 - bits)) as i32
+  }
+  pub fn next_int(&mut self, bound: i32) -> i32 {
+    assert!(bound > 0);
+    let mut r = self.next(31);
+    let m = bound - 1;
+    if bound & m == 0 {
+      return ((bound as i64 * r as i64) >> 31) as i32;
+    }
+    let mut u = r;
+    loop {
+      r = u % bound;
+      if u.wrapping_sub(r).wrapping_add(m) >= 0 {
+        return r;
+      }
+      u = self.next(31);
+    }
+  }
+}
+
+/// `Collections.shuffle` on an `ArrayList`.
+pub fn java_shuffle<T>(list: &mut [T], random: &mut JavaRandom) {
+  for index in (1..list.len()).rev() {
+    let other = random.next_int(index as i32 + 1);
+    list.swap(index, other as usize);
+  }
+}
+
+/// How far along each of the game's combat streams is, as the game counts it. These are all 0 at
+/// the start of a floor; the game's save files record them too.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug, Default)]
+pub struct SpireStreamCounters {
+  pub monster_hp: u32,
+  pub ai: u32,
+  pub shuffle: u32,
+  pub card_random: u32,
+  pub misc: u32,
+}
+
+type CardKey = (CardId, i32);
+
+fn card_key(card: &SingleCard) -> CardKey {
+  (card.card_info.id, card.upgrades)
+}
+
+/// A `SeedView` that makes each random choice the way the game would, given the run's seed.
+///
+/// It's exact for the rolls that work the same way in the simulation as in the game, like
+/// monster hitpoints, intent rolls and the order of the draw pile after a shuffle. Where the
+/// simulation does things differently, it falls back on the closest stream, so predictions can
+/// drift: cards shuffled into the draw pile mid-combat, for instance, aren't tracked, and
+/// `miscRng` isn't used by anything we simulate yet.
+#[derive(Clone, Debug)]
+pub struct SpireSeedView {
+  run_seed: i64,
+  floor: i32,
+  monster_hp: SpireRandom,
+  ai: SpireRandom,
+  shuffle: SpireRandom,
+  card_random: SpireRandom,
+  misc: SpireRandom,
+  /// The game's master deck order, which decides the first shuffle. Without it, we shuffle the
+  /// draw pile as the simulation has it instead.
+  master_deck: Option<Vec<CardKey>>,
+  /// The draw pile as we predict the game has it, top card first.
+  upcoming_draws: VecDeque<CardKey>,
+  /// `num_reshuffles` as of the last shuffle we predicted, or `None` before the first one.
+  shuffled_at: Option<i32>,
+  /// `num_reshuffles` when we first saw a combat that was already underway, so we can't know the
+  /// draw pile's order until the next shuffle.
+  joined_at: Option<i32>,
+  /// The value picked for the action being executed, by `num_actions`.
+  decided: Option<(i32, i32)>,
+}
+
+impl SpireSeedView {
+  pub fn new(run_seed: i64, floor: i32, counters: SpireStreamCounters) -> SpireSeedView {
+    let seed = run_seed.wrapping_add(floor as i64);
+    SpireSeedView {
+      run_seed,
+      floor,
+      monster_hp: SpireRandom::with_counter(seed, counters.monster_hp),
+      ai: SpireRandom::with_counter(seed, counters.ai),
+      shuffle: SpireRandom::with_counter(seed, counters.shuffle),
+      card_random: SpireRandom::with_counter(seed, counters.card_random),
+      misc: SpireRandom::with_counter(seed, counters.misc),
+      master_deck: None,
+      upcoming_draws: VecDeque::new(),
+      shuffled_at: None,
+      joined_at: None,
+      decided: None,
+    }
+  }
+  pub fn with_master_deck<'a>(mut self, deck: impl IntoIterator<Item = &'a SingleCard>) -> Self {
+    self.master_deck = Some(deck.into_iter().map(card_key).collect());
+    self
+  }
+  /// For the start of the floor CommunicationMod is on. Mid-combat, the streams have already
+  /// moved on, so this only predicts correctly if you're at the start of combat; draws aren't
+  /// predicted at all until the next shuffle.
+  pub fn from_communication_mod(observed: &communication::GameState) -> SpireSeedView {
+    let deck: Vec<SingleCard> = observed.deck.iter().map(SingleCard::from).collect();
+    SpireSeedView::new(observed.seed, observed.floor, Default::default()).with_master_deck(&deck)
+  }
+  /// Moves on to the start of another floor, the way the game reseeds its streams when you
+  /// enter a room. The master deck carries over; pass a new one if it's changed.
+  pub fn start_floor(&mut self, floor: i32) {
+    let master_deck = self.master_deck.take();
+    *self = SpireSeedView::new(self.run_seed, floor, Default::default());
+    self.master_deck = master_deck;
+  }
+  pub fn floor(&self) -> i32 {
+    self.floor
+  }
+  pub fn counters(&self) -> SpireStreamCounters {
+    SpireStreamCounters {
+      monster_hp: self.monster_hp.counter(),
+      ai: self.ai.counter(),
+      shuffle: self.shuffle.counter(),
+      card_random: self.card_random.counter(),
+      misc: self.misc.counter(),
+    }
+  }
+
+  /// The index in the simulation's draw pile of the card the game would draw next.
+  fn next_draw(&mut self, state: &CombatState) -> Option<i32> {
+    if self.shuffled_at != Some(state.num_reshuffles) {
+      let nothing_drawn = state.num_reshuffles == 0
+        && state.hand.is_empty()
+        && state.discard_pile.is_empty()
+        && state.exhaust_pile.is_empty();
+      let initial = self.shuffled_at.is_none() && nothing_drawn;
+      if self.shuffled_at.is_none() && !nothing_drawn {
+        let joined_at = *self.joined_at.get_or_insert(state.num_reshuffles);
+        if state.num_reshuffles == joined_at {
+          return None;
+        }
+      }
+      let mut order: Vec<CardKey> = match (&self.master_deck, initial) {
+        (Some(deck), true) => deck.clone(),
+        _ => state.draw_pile.iter().map(card_key).collect(),
+      };
+      java_shuffle(&mut order, &mut JavaRandom::new(self.shuffle.random_long()));
+      if initial {
+        // the game deals the first shuffle onto the draw pile one card at a time, so the last
+        // card ends up on top; reshuffles come off the discard pile the other way around
+        order.reverse();
+      }
+      self.upcoming_draws = order.into();
+      self.shuffled_at = Some(state.num_reshuffles);
+    }
+    // cards that aren't there anymore, like innate ones, have already been drawn some other way
+    while let Some(next) = self.upcoming_draws.pop_front() {
+      if let Some(index) = state
+        .draw_pile
+        .iter()
+        .position(|card| card_key(card) == next)
+      {
+        return Some(index as i32);
+      }
+    }
+    None
+  }
+
+  fn pick(&mut self, state: &CombatState, action: &DynAction) -> Option<i32> {
+    let distribution = match action.determinism(state) {
+      Determinism::Random(distribution) => distribution,
+      _ => return None,
+    };
+    match action {
+      DynAction::DrawCardRandom(_)
+      | DynAction::PlayTopCardAction(_)
+      | DynAction::RevealDrawPileTopAction(_) => self
+        .next_draw(state)
+        .or_else(|| uniform(&mut self.card_random, &distribution)),
+      &DynAction::ChooseMonsterIntent(ChooseMonsterIntent(monster_index)) => {
+        let num = self.ai.random(99);
+        let choices = intent_choices_for_num(state, monster_index, num).unwrap_or(distribution);
+        weighted(&mut self.ai, &choices)
+      }
+      DynAction::InitializeMonsterHitpoints(_)
+      | DynAction::InitializeMonsterInnateDamageAmount(_)
+      | DynAction::InitializeMonsterPowerAmount(_)
+      | DynAction::SpawnMonsterAction(_) => uniform(&mut self.monster_hp, &distribution),
+      DynAction::SummonGremlinAction(_) | DynAction::GainBlockRandomMonsterAction(_) => {
+        uniform(&mut self.ai, &distribution)
+      }
+      _ => weighted(&mut self.card_random, &distribution),
+    }
+  }
+}
+
+/// Like the game's `random(start, end)`, which rolls even when there's only one option.
+fn uniform(random: &mut SpireRandom, distribution: &Distribution<i32>) -> Option<i32> {
+  if distribution.0.is_empty() {
+    return None;
+  }
+  let index = random.random(distribution.0.len() as i32 - 1);
+  Some(distribution.0[index as usize].1)
+}
+
+/// For choices that the game makes with `randomBoolean(chance)` and the like. There's nothing to
+/// roll if there's only one option.
+fn weighted(random: &mut SpireRandom, distribution: &Distribution<i32>) -> Option<i32> {
+  let entries = &distribution.0;
+  match entries.len() {
+    0 => None,
+    1 => Some(entries[0].1),
+    _ if entries.iter().all(|&(weight, _)| weight == entries[0].0) => uniform(random, distribution),
+    _ => {
+      let total: f64 = entries.iter().map(|&(weight, _)| weight).sum();
+      let mut roll = random.random_float() as f64 * total;
+      for &(weight, value) in entries {
+        if roll < weight {
+          return Some(value);
+        }
+        roll -= weight;
+      }
+      entries.last().map(|&(_, value)| value)
+    }
+  }
+}
+
+impl SeedView<CombatState> for SpireSeedView {
+  fn gen(&mut self, state: &CombatState, action: &DynAction, &choice: &i32) -> f64 {
+    // `choose_choice` asks about each option in turn, but the game only rolls once per action
+    if self.decided.map(|(num_actions, _)| num_actions) != Some(state.num_actions) {
+      let picked = self.pick(state, action).unwrap_or(choice);
+      self.decided = Some((state.num_actions, picked));
+    }
+    if self.decided.map(|(_, picked)| picked) == Some(choice) {
+      0.0
+    } else {
+      1.0
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // the expected values come from running the same calls on the JVM
+
+  fn start_of_combat() -> CombatState {
+    CombatState {
+      draw_pile: vec![
+        SingleCard::create(CardId::StrikeR),
+        SingleCard::create(CardId::DefendR),
+        SingleCard::create(CardId::Bash),
+      ],
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn java_random_matches_the_jvm() {
+    let mut random = JavaRandom::new(123456789);
+    let below_10: Vec<i32> = (0..5).map(|_| random.next_int(10)).collect();
+    assert_eq!(below_10, [5, 0, 3, 4, 0]);
+    // powers of two take a different path
+    let below_16: Vec<i32> = (0..3).map(|_| random.next_int(16)).collect();
+    assert_eq!(below_16, [3, 14, 6]);
+  }
+
+  #[test]
+  fn java_shuffle_matches_the_jvm() {
+    let mut list: Vec<i32> = (0..10).collect();
+    java_shuffle(&mut list, &mut JavaRandom::new(-42));
+    assert_eq!(list, [9, 4, 8, 1, 6, 7, 2, 0, 3, 5]);
+  }
+
+  #[test]
+  fn spire_random_matches_the_jvm() {
+    let mut random = SpireRandom::new(1234567);
+    let rolls: Vec<i32> = (0..5).map(|_| random.random(99)).collect();
+    assert_eq!(rolls, [65, 99, 84, 32, 71]);
+    assert_eq!(random.random_long(), 1077285989609818027);
+    assert_eq!(random.random_float(), 0.5736507);
+    assert_eq!(random.counter(), 7);
+
+    // libGDX replaces a seed of 0
+    assert_eq!(SpireRandom::new(0).random(99), 72);
+  }
+
+  #[test]
+  fn with_counter_skips_ahead() {
+    let mut skipped = SpireRandom::with_counter(1234567, 3);
+    let mut rolled = SpireRandom::new(1234567);
+    for _ in 0..3 {
+      rolled.random(999);
+    }
+    assert_eq!(skipped.random(99), rolled.random(99));
+  }
+
+  #[test]
+  fn draws_wait_for_a_shuffle_after_joining_mid_combat() {
+    let mut state = start_of_combat();
+    state.hand.push(SingleCard::create(CardId::StrikeR));
+    let mut view = SpireSeedView::new(1234567, 3, Default::default());
+    assert_eq!(view.next_draw(&state), None);
+    assert_eq!(view.next_draw(&state), None);
+    state.num_reshuffles += 1;
+    assert!(view.next_draw(&state).is_some());
+  }
+
+  #[test]
+  fn first_draw_comes_from_the_first_shuffle() {
+    let state = start_of_combat();
+    let mut view = SpireSeedView::new(1234567, 3, Default::default());
+    assert!(view.next_draw(&state).is_some());
+    assert_eq!(view.counters().shuffle, 1);
+  }
+}
@@ -110,6 +110,20 @@ impl FastStrategy {
         }
         result
       }
+      Choice::SelectCard(SelectCard { card, choice_type }) => {
+        let priority = self.card_priorities[card.card_info.id];
+        match choice_type {
+          // getting rid of a card, at least for now: prefer the ones we'd rather not play
+          CardChoiceType::ExhaustCard
+          | CardChoiceType::HandTopdeck
+          | CardChoiceType::DiscardCard
+          | CardChoiceType::RecycleCard
+          | CardChoiceType::ScryDiscard { .. }
+          | CardChoiceType::ExhaustAnyCards
+          | CardChoiceType::GambleDiscard { .. } => -priority,
+          _ => priority,
+        }
+      }
       _ => 0.0,
     }
   }
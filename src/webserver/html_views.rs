@@ -5,7 +5,7 @@ use typed_html::{html, text};
 
 use crate::seed_system::TrivialSeed;
 use crate::simulation::{DisplayChoices, Runner, StandardRunner};
-use crate::simulation_state::CombatState;
+use crate::simulation_state::{CombatState, FidelityAudit, ImportFidelity, ImportReport};
 use crate::start_and_strategy_ai::SearchState;
 
 pub type Element = Box<dyn FlowContent<String>>;
@@ -46,6 +46,53 @@ impl CombatState {
   }
 }
 
+impl ImportReport {
+  pub fn view(&self) -> Element {
+    let lines = self.to_string();
+    let lines = lines.lines().map(|line| {
+      html! {
+        <div class="import-report-line">
+          {text! ("{}", line)}
+        </div>
+      }
+    });
+    match self.fidelity() {
+      ImportFidelity::Low => html! {
+        <div class="import-report import-report-low">
+          {lines}
+        </div>
+      },
+      ImportFidelity::Approximate => html! {
+        <div class="import-report import-report-approximate">
+          {lines}
+        </div>
+      },
+      ImportFidelity::Exact => html! {
+        <div class="import-report">
+          {lines}
+        </div>
+      },
+    }
+  }
+}
+
+impl FidelityAudit {
+  pub fn view(&self) -> Element {
+    let warnings = self.warnings.iter().map(|warning| {
+      html! {
+        <div class="fidelity-warning">
+          {text! ("{}", warning)}
+        </div>
+      }
+    });
+    html! {
+      <div class="fidelity-audit">
+        {warnings}
+      </div>
+    }
+  }
+}
+
 impl SearchState {
   pub fn view(&self) -> Element {
     let starting_points = self.starting_points.iter().map(|start| {
@@ -14,11 +14,12 @@ use crate::communication_mod_state;
 use crate::simulation_state::*;
 use std::fs::File;
 use typed_html::dom::DOMTree;
-use typed_html::html;
+use typed_html::{html, text};
 
 pub struct ServerConstants {
   data_files: PathBuf,
   state_file: PathBuf,
+  unknown_monster_model: UnknownMonsterModel,
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
@@ -45,6 +46,7 @@ pub struct ProcessingThreadState {
   constants: Arc<ServerConstants>,
   server_shared: Arc<Mutex<ServerSharedState>>,
   combat_state: Option<CombatState>,
+  import_report: Option<ImportReport>,
   analysis_flows_spec: Option<AnalysisFlowsSpec>,
   analysis_flows: Option<AnalysisFlows>,
   last_file_check: Instant,
@@ -79,6 +81,7 @@ impl ProcessingThreadState {
       constants,
       server_shared,
       combat_state: last_combat_state,
+      import_report: None,
       analysis_flows_spec: None,
       analysis_flows: None,
       last_file_check: Instant::now(),
@@ -123,9 +126,17 @@ impl ProcessingThreadState {
           match interpreted {
             Ok(state) => {
               let state = state.game_state.as_ref().and_then(|game_state| {
-                CombatState::from_communication_mod(game_state, self.combat_state.as_ref())
+                CombatState::from_communication_mod(
+                  game_state,
+                  self.combat_state.as_ref(),
+                  self.constants.unknown_monster_model,
+                )
               });
-              if let Some(state) = state {
+              if let Some((state, import_report)) = state {
+                if import_report.fidelity() != ImportFidelity::Exact {
+                  eprintln!("{}", import_report);
+                }
+                self.import_report = Some(import_report);
                 self.set_combat_state(state);
               }
             }
@@ -175,12 +186,30 @@ impl ProcessingThreadState {
       if self.last_report.elapsed() > Duration::from_millis(100)
         && flows.time_used_for_rendering * 20 <= flows.time_used
       {
+        let import_report = self.import_report.as_ref();
+        let audit = self.combat_state.as_ref().unwrap().fidelity_audit();
+        let low_fidelity = import_report.map_or(audit.anything_missing(), |report| {
+          report.fidelity_with_audit(&audit) == ImportFidelity::Low
+        });
+        // when we're simulating a different fight from the real one, the analyses are
+        // confidently wrong, so don't show them as recommendations
+        let analyses = if low_fidelity {
+          html! {
+            <div class="analysis-refused">
+              {text!("No recommendations: too much of this combat isn't simulated (see above).")}
+            </div>
+          }
+        } else {
+          flows.html_report()
+        };
         let report: DOMTree<String> = html! {
           <div id="content">
+            {import_report.map(ImportReport::view)}
+            {audit.view()}
             <div id="starting_state">
               {self.combat_state.as_ref().unwrap().view()}
             </div>
-            {flows.html_report()}
+            {analyses}
           </div>
         };
         let html_string = report.to_string();
@@ -202,6 +231,7 @@ pub fn run(
   static_files: PathBuf,
   data_files: PathBuf,
   state_file: PathBuf,
+  unknown_monster_model: UnknownMonsterModel,
   address: &str,
   port: u16,
 ) {
@@ -215,6 +245,7 @@ pub fn run(
     constants: Arc::new(ServerConstants {
       data_files,
       state_file,
+      unknown_monster_model,
     }),
     persistent_state,
     inputs: Vec::new(),